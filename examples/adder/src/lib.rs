@@ -1,4 +1,4 @@
-use near_sdk::{env, near, Promise};
+use near_sdk::{env, near, TypedPromise};
 
 #[derive(Debug, PartialEq, Eq)]
 #[near(serializers=[borsh, json])]
@@ -11,7 +11,7 @@ pub struct Adder {}
 #[near]
 impl Adder {
     /// Call functions a, b, and c, d,  asynchronously and handle results with `add_callback_vec`.
-    pub fn call_all() -> Promise {
+    pub fn call_all() -> TypedPromise<Pair> {
         Self::ext(env::current_account_id())
             .a()
             .and(Self::ext(env::current_account_id()).b())
@@ -1,5 +1,5 @@
 use near_sdk::require;
-use near_sdk::{env, near, Promise, PromiseError};
+use near_sdk::{env, near, PromiseError, TypedPromise};
 
 const A_VALUE: u8 = 8;
 
@@ -9,7 +9,7 @@ pub struct Callback;
 #[near]
 impl Callback {
     /// Call functions a, b, and c asynchronously and handle results with `handle_callbacks`.
-    pub fn call_all(fail_b: bool, c_value: u8, d_value: u8) -> Promise {
+    pub fn call_all(fail_b: bool, c_value: u8, d_value: u8) -> TypedPromise<(bool, bool, bool)> {
         Self::ext(env::current_account_id())
             .a()
             .and(Self::ext(env::current_account_id()).b(fail_b))
@@ -19,7 +19,11 @@ impl Callback {
     }
 
     /// Call functions a, b, and c asynchronously and handle results with `handle_callbacks`.
-    pub fn call_all_reverse(fail_b: bool, c_value: u8, d_value: u8) -> Promise {
+    pub fn call_all_reverse(
+        fail_b: bool,
+        c_value: u8,
+        d_value: u8,
+    ) -> TypedPromise<(bool, bool, bool)> {
         Self::ext(env::current_account_id())
             .b(fail_b)
             .and(Self::ext(env::current_account_id()).c(c_value))
@@ -29,7 +33,7 @@ impl Callback {
     }
 
     /// Calls function c with a value that will always succeed
-    pub fn a() -> Promise {
+    pub fn a() -> TypedPromise<u8> {
         Self::ext(env::current_account_id()).c(A_VALUE)
     }
 
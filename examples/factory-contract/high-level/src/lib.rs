@@ -1,5 +1,5 @@
 use near_sdk::PromiseError;
-use near_sdk::{env, ext_contract, near, AccountId, NearToken, Promise};
+use near_sdk::{env, ext_contract, near, AccountId, NearToken, Promise, TypedPromise};
 
 #[derive(Default)]
 #[near(contract_state)]
@@ -28,7 +28,11 @@ impl FactoryContract {
     pub fn simple_call(&mut self, account_id: AccountId, message: String) {
         ext_status_message::ext(account_id).set_status(message);
     }
-    pub fn complex_call(&mut self, account_id: AccountId, message: String) -> Promise {
+    pub fn complex_call(
+        &mut self,
+        account_id: AccountId,
+        message: String,
+    ) -> TypedPromise<Option<String>> {
         // 1) call status_message to record a message from the signer.
         // 2) call status_message to retrieve the message of the signer.
         // 3) return that message as its own result.
@@ -43,7 +47,7 @@ impl FactoryContract {
         &self,
         account_id: AccountId,
         #[callback_result] set_status_result: Result<(), PromiseError>,
-    ) -> Result<Promise, &'static str> {
+    ) -> Result<TypedPromise<Option<String>>, &'static str> {
         match set_status_result {
             Ok(_) => Ok(ext_status_message::ext(account_id).get_status(env::signer_account_id())),
             Err(_) => Err("Failed to set status"),
@@ -1,11 +1,8 @@
 use near_sdk::{
-    env, log, near, serde_json, store::IterableMap, AccountId, CryptoHash, Gas, GasWeight,
-    NearToken, PromiseError,
+    env, log, near, serde_json, store::IterableMap, AccountId, Gas, NearToken, Promise,
+    PromiseError, YieldId,
 };
 
-// Register used to receive data id from `promise_await_data`.
-const DATA_ID_REGISTER: u64 = 0;
-
 // Prepaid gas for a `sign_on_finish` call
 const SIGN_ON_FINISH_CALL_GAS: Gas = Gas::from_tgas(5);
 
@@ -15,7 +12,7 @@ const CHAINED_CALL_GAS: Gas = Gas::from_tgas(5);
 #[near(serializers = [borsh, json])]
 #[derive(Clone, Debug)]
 pub struct SignatureRequest {
-    pub data_id: CryptoHash,
+    pub data_id: YieldId,
     pub account_id: AccountId,
     pub message: String,
 }
@@ -42,53 +39,50 @@ impl MpcContract {
         let index = self.next_available_request_index;
         self.next_available_request_index += 1;
 
-        let yield_promise = env::promise_yield_create(
-            "sign_on_finish",
-            &serde_json::to_vec(&(index,)).unwrap(),
+        let yielded = Promise::yield_on(
+            "sign_on_finish".to_owned(),
+            serde_json::to_vec(&(index,)).unwrap(),
             SIGN_ON_FINISH_CALL_GAS,
-            GasWeight(0),
-            DATA_ID_REGISTER,
         );
 
         // Store the request in the contract's local state
-        let data_id: CryptoHash =
-            env::read_register(DATA_ID_REGISTER).expect("").try_into().expect("");
         self.requests.insert(
             index,
-            SignatureRequest { data_id, account_id: env::signer_account_id(), message },
+            SignatureRequest {
+                data_id: yielded.yield_id(),
+                account_id: env::signer_account_id(),
+                message,
+            },
         );
 
         // The yield promise is composable with the usual promise API features. We can choose to
         // chain another function call and it will receive the output of the `sign_on_finish`
         // callback. Note that this chained promise can be a cross-contract call.
-        env::promise_then(
-            yield_promise,
-            env::current_account_id(),
-            "do_something",
-            &[],
-            NearToken::from_near(0),
-            CHAINED_CALL_GAS,
-        );
-
+        //
         // The return value for this function call will be the value
         // returned by the `sign_on_finish` callback.
-        env::promise_return(yield_promise);
+        yielded.as_return().then(Promise::new(env::current_account_id()).function_call(
+            "do_something".to_owned(),
+            vec![],
+            NearToken::from_near(0),
+            CHAINED_CALL_GAS,
+        ));
     }
 
     /// Called by MPC participants to submit a signature
-    pub fn sign_respond(&mut self, data_id: CryptoHash, signature: String) {
+    pub fn sign_respond(&mut self, data_id: YieldId, signature: String) {
         // check that caller is allowed to respond, signature is valid, etc.
         // ...
 
-        log!("submitting response {} for data id {:?}", &signature, &data_id);
-        env::promise_yield_resume(&data_id, &serde_json::to_vec(&signature).unwrap());
+        log!("submitting response {} for data id {}", &signature, &data_id);
+        Promise::resume(&data_id, &signature);
     }
 
     /// Callback receiving the externally submitted data (or a PromiseError)
     pub fn sign_on_finish(
         &mut self,
         request_index: u64,
-        #[callback_result] signature: Result<String, PromiseError>,
+        #[yield_result] signature: Result<String, PromiseError>,
     ) -> String {
         // Clean up the local state
         self.requests.remove(&request_index);
@@ -108,7 +102,7 @@ impl MpcContract {
         for (_, request) in self.requests.iter() {
             log!(
                 "{}: account_id={} payload={}",
-                hex::encode(request.data_id),
+                request.data_id,
                 request.account_id,
                 request.message
             );
@@ -215,6 +209,11 @@ mod tests {
             logs[0],
             format!("fn do_something invoked with result 'signature received: {}'", SIGNATURE_TEXT)
         );
+        // `sign` returns the result of `sign_on_finish`, not of the chained `do_something`.
+        assert_eq!(
+            alice_result.json::<String>()?,
+            format!("signature received: {}", SIGNATURE_TEXT)
+        );
         Ok(())
     }
 
@@ -235,6 +234,7 @@ mod tests {
         assert!(alice_result.is_success());
         assert_eq!(logs.len(), 1);
         assert_eq!(logs[0], "fn do_something invoked with result 'signature request timed out'");
+        assert_eq!(alice_result.json::<String>()?, "signature request timed out");
         Ok(())
     }
 }
@@ -0,0 +1,30 @@
+use near_sdk::store::offchain::{StateDump, StateDumpError};
+use near_sdk::store::LookupMap;
+use near_sdk::{near, AccountId, BorshStorageKey};
+
+/// Storage layout of the status message contract.
+#[derive(BorshStorageKey)]
+#[near]
+pub struct RecordsKey;
+
+#[near(serializers = [borsh])]
+pub struct StatusMessage {
+    pub records: LookupMap<AccountId, String>,
+}
+
+impl Default for StatusMessage {
+    fn default() -> Self {
+        Self { records: LookupMap::new(RecordsKey) }
+    }
+}
+
+/// Reads the status message of `account_id` from the contract state.
+pub fn get_status(
+    state: &mut StateDump,
+    account_id: &AccountId,
+) -> Result<Option<String>, StateDumpError> {
+    let Some(contract) = state.state::<StatusMessage>()? else {
+        return Ok(None);
+    };
+    Ok(state.view(|| contract.records.get(account_id).cloned()))
+}
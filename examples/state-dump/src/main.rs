@@ -0,0 +1,18 @@
+use near_sdk::store::offchain::StateDump;
+use near_sdk::AccountId;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(path), Some(account_id)) = (args.next(), args.next()) else {
+        eprintln!("usage: state-dump <view_state.json> <account_id>");
+        std::process::exit(2);
+    };
+    let account_id: AccountId = account_id.parse().expect("invalid account id");
+    let json = std::fs::read_to_string(path).expect("cannot read view state");
+    let mut state = StateDump::from_view_state_json(&json).expect("invalid view state");
+
+    match state_dump::get_status(&mut state, &account_id).expect("cannot decode state") {
+        Some(message) => println!("{}", message),
+        None => println!("no status for {}", account_id),
+    }
+}
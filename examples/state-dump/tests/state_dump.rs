@@ -0,0 +1,64 @@
+use near_sdk::store::offchain::StateDump;
+use near_sdk::{env, AccountId};
+use state_dump::{get_status, StatusMessage};
+
+fn alice() -> AccountId {
+    "alice.near".parse().unwrap()
+}
+
+fn dump_with_status(message: &str) -> StateDump {
+    let mut state = StateDump::new();
+    let state_value = state.view(|| {
+        let mut contract = StatusMessage::default();
+        contract.records.insert(alice(), message.to_string());
+        contract.records.flush();
+        near_sdk::borsh::to_vec(&contract).unwrap()
+    });
+    state.insert(b"STATE", &state_value);
+    state
+}
+
+#[test]
+fn reads_status_from_dump() {
+    let mut state = dump_with_status("hello");
+    assert_eq!(get_status(&mut state, &alice()).unwrap().as_deref(), Some("hello"));
+    assert_eq!(get_status(&mut state, &"bob.near".parse().unwrap()).unwrap(), None);
+    assert_eq!(get_status(&mut StateDump::new(), &alice()).unwrap(), None);
+}
+
+#[test]
+fn hashing_and_logging_run_natively() {
+    assert_eq!(env::sha256(b"")[..2], [0xe3, 0xb0]);
+    assert_eq!(env::keccak256(b"")[..2], [0xc5, 0xd2]);
+    env::log_str("logged off-chain");
+}
+
+#[test]
+fn panics_are_catchable() {
+    let err = std::panic::catch_unwind(|| env::panic_str("boom")).unwrap_err();
+    assert_eq!(err.downcast_ref::<String>().map(String::as_str), Some("boom"));
+
+    let err = std::panic::catch_unwind(env::block_timestamp).unwrap_err();
+    assert!(err.downcast_ref::<&str>().unwrap().contains("block_timestamp"));
+}
+
+/// `near-sdk` must not replace `abort` or other symbols of the binary, so aborting still ends the
+/// process instead of unwinding.
+#[test]
+fn process_abort_is_not_intercepted() {
+    if std::env::var_os("STATE_DUMP_ABORT").is_some() {
+        let _ = std::panic::catch_unwind(|| std::process::abort());
+        std::process::exit(0);
+    }
+    let status = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["process_abort_is_not_intercepted", "--exact", "--nocapture"])
+        .env("STATE_DUMP_ABORT", "1")
+        .status()
+        .unwrap();
+    assert!(!status.success());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(6));
+    }
+}
@@ -0,0 +1,136 @@
+//! Events of [`AccessControl`](super::AccessControl), in the events format (nep-297):
+//! <https://github.com/near/NEPs/blob/master/specs/Standards/EventsFormat.md>
+//!
+//! The three events are [`RoleGranted`], [`RoleRevoked`] and [`OwnerTransferred`]. They are
+//! emitted by [`AccessControl`](super::AccessControl) when the roles or the owner change.
+
+use crate::event::NearEvent;
+use near_sdk::serde::Serialize;
+use near_sdk::AccountIdRef;
+
+/// Data to log when an account is granted a role. To log this event, call
+/// [`.emit()`](RoleGranted::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleGranted<'a> {
+    pub role: &'a str,
+    pub account_id: &'a AccountIdRef,
+}
+
+impl RoleGranted<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits a role granted event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`RoleGranted`] represents the data of each grant.
+    pub fn emit_many(data: &[RoleGranted<'_>]) {
+        new_access_control_v1(AccessControlEventKind::RoleGranted(data)).emit()
+    }
+}
+
+/// Data to log when a role of an account is revoked. To log this event, call
+/// [`.emit()`](RoleRevoked::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleRevoked<'a> {
+    pub role: &'a str,
+    pub account_id: &'a AccountIdRef,
+}
+
+impl RoleRevoked<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits a role revoked event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`RoleRevoked`] represents the data of each revocation.
+    pub fn emit_many(data: &[RoleRevoked<'_>]) {
+        new_access_control_v1(AccessControlEventKind::RoleRevoked(data)).emit()
+    }
+}
+
+/// Data to log when the owner changes. To log this event, call
+/// [`.emit()`](OwnerTransferred::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerTransferred<'a> {
+    pub old_owner_id: &'a AccountIdRef,
+    pub new_owner_id: &'a AccountIdRef,
+}
+
+impl OwnerTransferred<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_access_control_v1(AccessControlEventKind::OwnerTransferred(&[self])).emit()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub(crate) struct AccessControlEvent<'a> {
+    version: &'static str,
+    #[serde(flatten)]
+    event_kind: AccessControlEventKind<'a>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum AccessControlEventKind<'a> {
+    RoleGranted(&'a [RoleGranted<'a>]),
+    RoleRevoked(&'a [RoleRevoked<'a>]),
+    OwnerTransferred(&'a [OwnerTransferred<'a>]),
+}
+
+fn new_access_control_v1(event_kind: AccessControlEventKind) -> NearEvent {
+    NearEvent::AccessControl(AccessControlEvent { version: "1.0.0", event_kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils;
+
+    #[test]
+    fn role_granted() {
+        let account_id = AccountIdRef::new_or_panic("bob");
+        RoleGranted { role: "Admin", account_id }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"access_control","version":"1.0.0","event":"role_granted","data":[{"role":"Admin","account_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn role_revoked() {
+        let account_id = AccountIdRef::new_or_panic("bob");
+        RoleRevoked { role: "Admin", account_id }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"access_control","version":"1.0.0","event":"role_revoked","data":[{"role":"Admin","account_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn owner_transferred() {
+        OwnerTransferred {
+            old_owner_id: AccountIdRef::new_or_panic("alice"),
+            new_owner_id: AccountIdRef::new_or_panic("bob"),
+        }
+        .emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"access_control","version":"1.0.0","event":"owner_transferred","data":[{"old_owner_id":"alice","new_owner_id":"bob"}]}"#
+        );
+    }
+}
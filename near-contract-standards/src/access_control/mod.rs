@@ -0,0 +1,212 @@
+//! Role-based access control of contract methods.
+//!
+//! [`AccessControl`] stores the owner of the contract and the members of each role. A contract
+//! which implements [`AccessControllable`], from `near_sdk::access_control`, can annotate its
+//! methods with
+//! `#[access_control(role = "...")]` to only allow members of the role to call them, or with
+//! `#[only(owner)]` to only allow the owner. Several roles can be listed, of which the predecessor
+//! needs any. Methods which take `&mut self` can be annotated. The ABI has no field for the
+//! required roles, so they are appended to the documentation of the method.
+//!
+//! ```
+//! use near_contract_standards::access_control::{AccessControl, AccessControllable};
+//! use near_sdk::{near, AccountId, PanicOnDefault};
+//!
+//! #[near(contract_state)]
+//! #[derive(PanicOnDefault)]
+//! pub struct Contract {
+//!     acl: AccessControl,
+//!     value: u64,
+//! }
+//!
+//! impl AccessControllable for Contract {
+//!     type Acl = AccessControl;
+//!
+//!     fn acl(&self) -> &AccessControl {
+//!         &self.acl
+//!     }
+//! }
+//!
+//! #[near]
+//! impl Contract {
+//!     #[init]
+//!     pub fn new(owner_id: AccountId) -> Self {
+//!         Self { acl: AccessControl::new(b"r", owner_id), value: 0 }
+//!     }
+//!
+//!     #[only(owner)]
+//!     pub fn add_admin(&mut self, account_id: AccountId) {
+//!         self.acl.grant_role("Admin", account_id);
+//!     }
+//!
+//!     #[access_control(role = "Admin")]
+//!     pub fn set_value(&mut self, value: u64) {
+//!         self.value = value;
+//!     }
+//! }
+//!
+//! # use near_sdk::{test_utils::VMContextBuilder, testing_env};
+//! let owner: AccountId = "owner.near".parse().unwrap();
+//! let admin: AccountId = "admin.near".parse().unwrap();
+//! testing_env!(VMContextBuilder::new().predecessor_account_id(owner.clone()).build());
+//! let mut contract = Contract::new(owner);
+//! contract.add_admin(admin.clone());
+//!
+//! testing_env!(VMContextBuilder::new().predecessor_account_id(admin).build());
+//! contract.set_value(1);
+//! assert_eq!(contract.value, 1);
+//! ```
+
+pub mod events;
+
+use std::collections::BTreeSet;
+
+use near_sdk::store::LookupMap;
+use near_sdk::{near, AccountId, AccountIdRef, IntoStorageKey};
+
+pub use near_sdk::access_control::{AccessCheck, AccessControllable};
+
+use self::events::{OwnerTransferred, RoleGranted, RoleRevoked};
+
+/// Owner of the contract and members of its roles. The owner isn't a member of any role unless
+/// granted one.
+#[near]
+pub struct AccessControl {
+    owner_id: AccountId,
+    roles: LookupMap<String, BTreeSet<AccountId>>,
+}
+
+impl AccessControl {
+    /// Creates an access control with the given owner, whose roles are stored under `prefix`.
+    pub fn new<S>(prefix: S, owner_id: AccountId) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self { owner_id, roles: LookupMap::new(prefix) }
+    }
+
+    pub fn owner_id(&self) -> &AccountId {
+        &self.owner_id
+    }
+
+    /// Transfers the ownership to `owner_id` and emits an [`OwnerTransferred`] event. Panics if the
+    /// predecessor isn't the current owner.
+    pub fn set_owner(&mut self, owner_id: AccountId) {
+        self.assert_owner();
+        OwnerTransferred { old_owner_id: &self.owner_id, new_owner_id: &owner_id }.emit();
+        self.owner_id = owner_id;
+    }
+
+    /// Members of the role.
+    pub fn members(&self, role: &str) -> impl Iterator<Item = &AccountId> {
+        self.roles.get(role).into_iter().flatten()
+    }
+
+    /// Grants the role to the account and emits a [`RoleGranted`] event. Returns `false` if the
+    /// account already had the role.
+    pub fn grant_role(&mut self, role: &str, account_id: AccountId) -> bool {
+        let members = self.roles.entry(role.to_string()).or_default();
+        if members.contains(&account_id) {
+            return false;
+        }
+        RoleGranted { role, account_id: &account_id }.emit();
+        members.insert(account_id);
+        true
+    }
+
+    /// Revokes the role of the account and emits a [`RoleRevoked`] event. Returns `false` if the
+    /// account didn't have the role.
+    pub fn revoke_role(&mut self, role: &str, account_id: &AccountIdRef) -> bool {
+        let Some(members) = self.roles.get_mut(role) else {
+            return false;
+        };
+        if !members.remove(account_id) {
+            return false;
+        }
+        if members.is_empty() {
+            self.roles.remove(role);
+        }
+        RoleRevoked { role, account_id }.emit();
+        true
+    }
+}
+
+impl AccessCheck for AccessControl {
+    fn is_owner(&self, account_id: &AccountIdRef) -> bool {
+        self.owner_id == account_id
+    }
+
+    fn has_role(&self, role: &str, account_id: &AccountIdRef) -> bool {
+        self.roles.get(role).is_some_and(|members| members.contains(account_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn set_predecessor(account_id: &str) {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account(account_id)).build());
+    }
+
+    #[test]
+    fn grant_and_revoke() {
+        set_predecessor("owner");
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        assert!(acl.grant_role("Admin", account("alice")));
+        assert!(!acl.grant_role("Admin", account("alice")));
+        assert!(acl.grant_role("Admin", account("bob")));
+        assert!(acl.has_role("Admin", &account("alice")));
+        assert!(!acl.has_role("Minter", &account("alice")));
+        assert_eq!(acl.members("Admin").collect::<Vec<_>>(), [&account("alice"), &account("bob")]);
+
+        assert!(acl.revoke_role("Admin", &account("alice")));
+        assert!(!acl.revoke_role("Admin", &account("alice")));
+        assert!(!acl.revoke_role("Minter", &account("alice")));
+        assert!(!acl.has_role("Admin", &account("alice")));
+        assert_eq!(get_logs().len(), 3);
+    }
+
+    #[test]
+    fn assert_any_role() {
+        set_predecessor("alice");
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.grant_role("Minter", account("alice"));
+        acl.assert_role("Minter");
+        acl.assert_any_role(&["Admin", "Minter"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Predecessor must have role Admin or Minter")]
+    fn assert_any_role_fails() {
+        set_predecessor("bob");
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.grant_role("Minter", account("alice"));
+        acl.assert_any_role(&["Admin", "Minter"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner must be predecessor")]
+    fn assert_owner_fails() {
+        set_predecessor("owner");
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.assert_owner();
+        acl.set_owner(account("alice"));
+        assert_eq!(acl.owner_id(), &account("alice"));
+        acl.assert_owner();
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner must be predecessor")]
+    fn set_owner_by_other_account_fails() {
+        set_predecessor("alice");
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.set_owner(account("alice"));
+    }
+}
@@ -10,6 +10,9 @@ use near_sdk::serde_json;
 pub(crate) enum NearEvent<'a> {
     Nep171(crate::non_fungible_token::events::Nep171Event<'a>),
     Nep141(crate::fungible_token::events::Nep141Event<'a>),
+    AccessControl(crate::access_control::events::AccessControlEvent<'a>),
+    Pausable(crate::pausable::events::PausableEvent<'a>),
+    Upgrade(crate::upgrade::events::UpgradeEvent<'a>),
 }
 
 impl<'a> NearEvent<'a> {
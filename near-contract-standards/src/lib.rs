@@ -10,14 +10,19 @@ pub mod non_fungible_token;
 /// Storage management deals with handling [state storage](https://docs.near.org/docs/concepts/storage-staking) on NEAR. This follows the [storage management standard](https://nomicon.io/Standards/StorageManagement.html).
 pub mod storage_management;
 
-/// This upgrade standard is a use case where a staging area exists for a WASM
-/// blob, allowing it to be stored for a period of time before deployed.
-#[deprecated(
-    since = "4.1.0",
-    note = "This was removed because there is no standard (NEP) for upgradable contracts."
-)]
+/// Self-upgrade of contracts, with a staging area where a WASM blob is stored for a period of
+/// time before it's deployed and the state migrated.
 pub mod upgrade;
 
 pub(crate) mod event;
 
 pub mod contract_metadata;
+
+/// Role-based access control of contract methods with `#[access_control(...)]` and `#[only(owner)]`.
+pub mod access_control;
+
+/// Pausing of contract methods with `#[pause]`.
+pub mod pausable;
+
+/// Verification of off-chain signed messages as described in [NEP-413](https://github.com/near/NEPs/blob/master/neps/nep-0413.md).
+pub mod nep413;
@@ -0,0 +1,255 @@
+use near_sdk::store::LookupSet;
+use near_sdk::{env, near, require, CurveType, IntoStorageKey, PublicKey, Signature};
+
+/// Prefix of the signed data, `2^31 + 413`, which makes it impossible to produce a valid
+/// transaction from a signed message.
+pub const NEP413_TAG: u32 = (1 << 31) + 413;
+
+/// Payload of an off-chain message signed by a wallet as described in
+/// [NEP-413](https://github.com/near/NEPs/blob/master/neps/nep-0413.md).
+///
+/// Wallets sign the sha256 hash of the Borsh serialized [`NEP413_TAG`] followed by the Borsh
+/// serialized payload, see [`SignMessage::hash`]. In JSON the nonce is base64 encoded.
+///
+/// # Example
+/// ```
+/// use near_contract_standards::nep413::{self, SignMessage};
+/// use near_sdk::{PublicKey, Signature};
+///
+/// let payload = SignMessage {
+///     message: "Login with NEAR".to_string(),
+///     nonce: [42; 32],
+///     recipient: "example.near".to_string(),
+///     callback_url: None,
+/// };
+/// let public_key: PublicKey = "ed25519:GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB".parse().unwrap();
+/// let signature: Signature = "ed25519:3wiKakbmmUWmyaFjSgnZqP9SJ8LDg9BWfcMcQNtXMJX9sdbsaoAp4mtkng5DBrFsZcgLcMwseoixdNEBuA9p32we"
+///     .parse()
+///     .unwrap();
+/// assert!(nep413::verify(&payload, &signature, &public_key));
+/// ```
+#[near(serializers=[borsh, json])]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignMessage {
+    /// The message that was signed.
+    pub message: String,
+    /// 32 random bytes chosen by the requesting application, used to prevent replays.
+    #[serde(with = "base64_nonce")]
+    #[cfg_attr(feature = "abi", schemars(with = "String"))]
+    pub nonce: [u8; 32],
+    /// The recipient of the message, usually the account ID of the contract verifying it.
+    pub recipient: String,
+    /// URL the wallet redirects to after signing, if any.
+    pub callback_url: Option<String>,
+}
+
+impl SignMessage {
+    /// Returns the hash which is signed by the wallet.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut data = NEP413_TAG.to_le_bytes().to_vec();
+        data.extend(near_sdk::borsh::to_vec(self).unwrap_or_else(|_| env::abort()));
+        env::sha256_array(&data)
+    }
+}
+
+/// Verifies that `payload` was signed with `public_key`. Only ed25519 keys are supported by
+/// wallets, so `false` is returned for any other curve.
+///
+/// Note that this does not check that the key belongs to any particular account, nor the
+/// recipient or the nonce of the payload, see [`NonceRegistry`] for that.
+pub fn verify(payload: &SignMessage, signature: &Signature, public_key: &PublicKey) -> bool {
+    public_key.curve_type() == CurveType::ED25519 && public_key.verify(&payload.hash(), signature)
+}
+
+/// Set of nonces of the messages which were already accepted by the contract, protecting
+/// against the replay of signed messages.
+///
+/// # Example
+/// ```
+/// use near_contract_standards::nep413::{NonceRegistry, SignMessage};
+/// use near_sdk::{near, PanicOnDefault, PublicKey, Signature};
+///
+/// #[near(contract_state)]
+/// #[derive(PanicOnDefault)]
+/// pub struct Contract {
+///     nonces: NonceRegistry,
+/// }
+///
+/// #[near]
+/// impl Contract {
+///     #[init]
+///     pub fn new() -> Self {
+///         Self { nonces: NonceRegistry::new(b"n") }
+///     }
+///
+///     pub fn login(&mut self, payload: SignMessage, signature: Signature, public_key: PublicKey) {
+///         // The key must be checked to belong to the user, e.g. with an access key query
+///         // done off-chain or by comparing its implicit account ID.
+///         self.nonces.verify_and_consume(&payload, &signature, &public_key);
+///     }
+/// }
+/// ```
+#[near]
+pub struct NonceRegistry {
+    nonces: LookupSet<[u8; 32]>,
+}
+
+impl NonceRegistry {
+    /// Creates an empty registry, with entries stored under `prefix`.
+    pub fn new<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self { nonces: LookupSet::new(prefix) }
+    }
+
+    /// Whether a message with the given nonce was already accepted.
+    pub fn is_used(&self, nonce: &[u8; 32]) -> bool {
+        self.nonces.contains(nonce)
+    }
+
+    /// Verifies the signature of `payload`, that it is addressed to the current account and that
+    /// its nonce wasn't used before, and marks the nonce as used.
+    ///
+    /// # Panics
+    /// If any of the checks above fails.
+    pub fn verify_and_consume(
+        &mut self,
+        payload: &SignMessage,
+        signature: &Signature,
+        public_key: &PublicKey,
+    ) {
+        require!(
+            payload.recipient == env::current_account_id().as_str(),
+            "The message is addressed to another recipient"
+        );
+        require!(verify(payload, signature, public_key), "Invalid signature");
+        require!(self.nonces.insert(payload.nonce), "The nonce was already used");
+    }
+}
+
+mod base64_nonce {
+    use near_sdk::base64::engine::general_purpose::STANDARD;
+    use near_sdk::base64::Engine;
+    use near_sdk::serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(nonce: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&STANDARD.encode(nonce))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        STANDARD
+            .decode(s.as_str())
+            .map_err(de::Error::custom)?
+            .try_into()
+            .map_err(|_| de::Error::custom("nonce must be 32 bytes long"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::{serde_json, testing_env};
+
+    fn payload() -> SignMessage {
+        SignMessage {
+            message: "Login with NEAR".to_string(),
+            nonce: [42; 32],
+            recipient: "example.near".to_string(),
+            callback_url: None,
+        }
+    }
+
+    fn public_key() -> PublicKey {
+        "ed25519:GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB".parse().unwrap()
+    }
+
+    fn signature() -> Signature {
+        "ed25519:3wiKakbmmUWmyaFjSgnZqP9SJ8LDg9BWfcMcQNtXMJX9sdbsaoAp4mtkng5DBrFsZcgLcMwseoixdNEBuA9p32we"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn hash() {
+        let mut data = vec![0x9d, 0x01, 0x00, 0x80];
+        data.extend([15, 0, 0, 0]);
+        data.extend(b"Login with NEAR");
+        data.extend([42; 32]);
+        data.extend([12, 0, 0, 0]);
+        data.extend(b"example.near");
+        data.push(0);
+        assert_eq!(payload().hash(), env::sha256_array(&data));
+        assert_eq!(
+            payload().hash(),
+            [
+                1, 172, 0, 237, 166, 189, 234, 198, 254, 54, 152, 70, 145, 25, 161, 59, 199, 163,
+                222, 91, 178, 184, 251, 52, 160, 244, 33, 193, 197, 92, 216, 117
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_signature() {
+        assert!(verify(&payload(), &signature(), &public_key()));
+        let other = SignMessage { message: "Login with NEAR!".to_string(), ..payload() };
+        assert!(!verify(&other, &signature(), &public_key()));
+        let other =
+            SignMessage { callback_url: Some("https://example.com".to_string()), ..payload() };
+        assert!(!verify(&other, &signature(), &public_key()));
+    }
+
+    #[test]
+    fn json() {
+        let json = serde_json::to_string(&payload()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"message":"Login with NEAR","nonce":"KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio=","recipient":"example.near","callbackUrl":null}"#
+        );
+        assert_eq!(serde_json::from_str::<SignMessage>(&json).unwrap(), payload());
+        assert!(serde_json::from_str::<SignMessage>(
+            r#"{"message":"","nonce":"KioqKio=","recipient":"example.near","callbackUrl":null}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn consume_nonce() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id("example.near".parse().unwrap())
+            .build());
+        let mut registry = NonceRegistry::new(b"n");
+        assert!(!registry.is_used(&[42; 32]));
+        registry.verify_and_consume(&payload(), &signature(), &public_key());
+        assert!(registry.is_used(&[42; 32]));
+    }
+
+    #[test]
+    #[should_panic(expected = "The nonce was already used")]
+    fn replayed_nonce() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id("example.near".parse().unwrap())
+            .build());
+        let mut registry = NonceRegistry::new(b"n");
+        registry.verify_and_consume(&payload(), &signature(), &public_key());
+        registry.verify_and_consume(&payload(), &signature(), &public_key());
+    }
+
+    #[test]
+    #[should_panic(expected = "The message is addressed to another recipient")]
+    fn wrong_recipient() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id("other.near".parse().unwrap())
+            .build());
+        NonceRegistry::new(b"n").verify_and_consume(&payload(), &signature(), &public_key());
+    }
+}
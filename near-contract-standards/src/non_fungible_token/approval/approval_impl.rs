@@ -61,6 +61,7 @@ impl NonFungibleTokenApproval for NonFungibleToken {
             ext_nft_approval_receiver::ext(account_id)
                 .with_static_gas(env::prepaid_gas().saturating_sub(GAS_FOR_NFT_APPROVE))
                 .nft_on_approve(token_id, owner_id, approval_id, msg)
+                .into()
         })
     }
 
@@ -0,0 +1,87 @@
+//! Events of [`Pausable`](super::Pausable), in the events format (nep-297):
+//! <https://github.com/near/NEPs/blob/master/specs/Standards/EventsFormat.md>
+//!
+//! The two events are [`Paused`] and [`Unpaused`], emitted when a feature is paused or unpaused.
+
+use crate::event::NearEvent;
+use near_sdk::serde::Serialize;
+use near_sdk::AccountIdRef;
+
+/// Data to log when a feature is paused. To log this event, call [`.emit()`](Paused::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Paused<'a> {
+    pub feature: &'a str,
+    pub by: &'a AccountIdRef,
+}
+
+impl Paused<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_pausable_v1(PausableEventKind::Paused(&[self])).emit()
+    }
+}
+
+/// Data to log when a feature is unpaused. To log this event, call [`.emit()`](Unpaused::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Unpaused<'a> {
+    pub feature: &'a str,
+    pub by: &'a AccountIdRef,
+}
+
+impl Unpaused<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_pausable_v1(PausableEventKind::Unpaused(&[self])).emit()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub(crate) struct PausableEvent<'a> {
+    version: &'static str,
+    #[serde(flatten)]
+    event_kind: PausableEventKind<'a>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum PausableEventKind<'a> {
+    Paused(&'a [Paused<'a>]),
+    Unpaused(&'a [Unpaused<'a>]),
+}
+
+fn new_pausable_v1(event_kind: PausableEventKind) -> NearEvent {
+    NearEvent::Pausable(PausableEvent { version: "1.0.0", event_kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils;
+
+    #[test]
+    fn paused() {
+        Paused { feature: "deposits", by: AccountIdRef::new_or_panic("bob") }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"pausable","version":"1.0.0","event":"paused","data":[{"feature":"deposits","by":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn unpaused() {
+        Unpaused { feature: "deposits", by: AccountIdRef::new_or_panic("bob") }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"pausable","version":"1.0.0","event":"unpaused","data":[{"feature":"deposits","by":"bob"}]}"#
+        );
+    }
+}
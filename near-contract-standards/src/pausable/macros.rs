@@ -0,0 +1,38 @@
+/// Implements [`PausableContract`](crate::pausable::PausableContract) for the contract and exports
+/// the [`PauseManagement`](crate::pausable::PauseManagement) methods. Takes the name of the
+/// contract struct, its [`Pausable`](crate::pausable::Pausable) field and the
+/// [`AccessControl`](crate::access_control::AccessControl) field checked when pausing and
+/// unpausing features.
+#[macro_export]
+macro_rules! impl_pausable {
+    ($contract: ident, $pausable: ident, $acl: ident) => {
+        impl $crate::pausable::PausableContract for $contract {
+            type Pausable = $crate::pausable::Pausable;
+
+            fn pausable(&self) -> &$crate::pausable::Pausable {
+                &self.$pausable
+            }
+        }
+
+        #[::near_sdk::near]
+        impl $crate::pausable::PauseManagement for $contract {
+            fn pause_feature(&mut self, feature: String) -> bool {
+                $crate::pausable::assert_pause_manager(&self.$acl);
+                self.$pausable.pause(&feature)
+            }
+
+            fn unpause_feature(&mut self, feature: String) -> bool {
+                $crate::pausable::assert_pause_manager(&self.$acl);
+                self.$pausable.unpause(&feature)
+            }
+
+            fn is_feature_paused(&self, feature: String) -> bool {
+                $crate::pausable::PauseCheck::is_paused(&self.$pausable, &feature)
+            }
+
+            fn paused_features(&self) -> Vec<String> {
+                self.$pausable.paused_features().cloned().collect()
+            }
+        }
+    };
+}
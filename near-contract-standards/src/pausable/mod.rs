@@ -0,0 +1,234 @@
+//! Pausing of contract methods, e.g. to freeze deposits during an incident.
+//!
+//! [`Pausable`] stores the names of the paused features. A contract which implements
+//! [`PausableContract`], from `near_sdk::pausable`, can annotate its methods with `#[pause]`,
+//! which makes them panic while the feature named after the method is paused. The feature can be
+//! set with `#[pause(feature = "deposits")]` to pause several methods together, and members of some
+//! roles of the contract's [`AccessControl`](crate::access_control::AccessControl) can be allowed to
+//! call the method anyway with `#[pause(except(roles = ["Admin"]))]`. The check is done before the
+//! arguments are deserialized, so it doesn't apply when the method is called directly, e.g. in unit
+//! tests.
+//!
+//! [`impl_pausable!`](crate::impl_pausable) implements [`PausableContract`] and exports the
+//! [`PauseManagement`] methods, with which the owner and the members of [`PAUSE_MANAGER_ROLE`]
+//! pause and unpause features, and anyone can list the paused features.
+//!
+//! ```
+//! use near_contract_standards::access_control::{AccessControl, AccessControllable};
+//! use near_contract_standards::impl_pausable;
+//! use near_contract_standards::pausable::Pausable;
+//! use near_sdk::{env, near, AccountId, NearToken, PanicOnDefault};
+//!
+//! #[near(contract_state)]
+//! #[derive(PanicOnDefault)]
+//! pub struct Contract {
+//!     acl: AccessControl,
+//!     pausable: Pausable,
+//!     total: NearToken,
+//! }
+//!
+//! impl AccessControllable for Contract {
+//!     type Acl = AccessControl;
+//!
+//!     fn acl(&self) -> &AccessControl {
+//!         &self.acl
+//!     }
+//! }
+//!
+//! impl_pausable!(Contract, pausable, acl);
+//!
+//! #[near]
+//! impl Contract {
+//!     #[payable]
+//!     #[pause(feature = "deposits", except(roles = ["Admin"]))]
+//!     pub fn deposit(&mut self) {
+//!         self.total = self.total.saturating_add(env::attached_deposit());
+//!     }
+//! }
+//! ```
+
+pub mod events;
+mod macros;
+
+use std::collections::BTreeSet;
+
+use near_sdk::{env, near};
+
+pub use near_sdk::pausable::{PausableContract, PauseCheck};
+
+use crate::access_control::AccessCheck;
+
+use self::events::{Paused, Unpaused};
+
+/// Role whose members can pause and unpause features through [`PauseManagement`], besides the
+/// owner.
+pub const PAUSE_MANAGER_ROLE: &str = "PauseManager";
+
+/// Methods to pause and unpause features of a contract, exported by
+/// [`impl_pausable!`](crate::impl_pausable).
+pub trait PauseManagement {
+    /// Pauses the feature. Returns `false` if it was already paused. Only the owner and the members
+    /// of [`PAUSE_MANAGER_ROLE`] can call it.
+    fn pause_feature(&mut self, feature: String) -> bool;
+
+    /// Unpauses the feature. Returns `false` if it wasn't paused. Only the owner and the members of
+    /// [`PAUSE_MANAGER_ROLE`] can call it.
+    fn unpause_feature(&mut self, feature: String) -> bool;
+
+    fn is_feature_paused(&self, feature: String) -> bool;
+
+    fn paused_features(&self) -> Vec<String>;
+}
+
+/// Panics unless the predecessor is the owner or a member of [`PAUSE_MANAGER_ROLE`].
+pub fn assert_pause_manager(acl: &impl AccessCheck) {
+    let predecessor = env::predecessor_account_id();
+    if !acl.is_owner(&predecessor) && !acl.has_role(PAUSE_MANAGER_ROLE, &predecessor) {
+        env::panic_str(&format!("Predecessor must be the owner or have role {PAUSE_MANAGER_ROLE}"));
+    }
+}
+
+/// Names of the paused features of the contract.
+#[near]
+#[derive(Default)]
+pub struct Pausable {
+    paused: BTreeSet<String>,
+}
+
+impl Pausable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn paused_features(&self) -> impl Iterator<Item = &String> {
+        self.paused.iter()
+    }
+
+    /// Pauses the feature and emits a [`Paused`] event. Returns `false` if it was already paused.
+    pub fn pause(&mut self, feature: &str) -> bool {
+        if !self.paused.insert(feature.to_string()) {
+            return false;
+        }
+        Paused { feature, by: &env::predecessor_account_id() }.emit();
+        true
+    }
+
+    /// Unpauses the feature and emits an [`Unpaused`] event. Returns `false` if it wasn't paused.
+    pub fn unpause(&mut self, feature: &str) -> bool {
+        if !self.paused.remove(feature) {
+            return false;
+        }
+        Unpaused { feature, by: &env::predecessor_account_id() }.emit();
+        true
+    }
+}
+
+impl PauseCheck for Pausable {
+    fn is_paused(&self, feature: &str) -> bool {
+        self.paused.contains(feature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_control::AccessControl;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId};
+
+    #[near(contract_state)]
+    struct Contract {
+        acl: AccessControl,
+        pausable: Pausable,
+    }
+
+    impl Default for Contract {
+        fn default() -> Self {
+            let mut acl = AccessControl::new(b"r", account("owner"));
+            acl.grant_role(PAUSE_MANAGER_ROLE, account("manager"));
+            Self { acl, pausable: Pausable::new() }
+        }
+    }
+
+    crate::impl_pausable!(Contract, pausable, acl);
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn set_predecessor(account_id: &str) {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account(account_id)).build());
+    }
+
+    #[test]
+    fn pause_and_unpause() {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account("owner")).build());
+        let mut pausable = Pausable::new();
+        assert!(pausable.pause("deposits"));
+        assert!(!pausable.pause("deposits"));
+        assert!(pausable.is_paused("deposits"));
+        assert!(!pausable.is_paused("withdrawals"));
+        assert_eq!(pausable.paused_features().collect::<Vec<_>>(), ["deposits"]);
+        pausable.assert_not_paused("withdrawals");
+
+        assert!(pausable.unpause("deposits"));
+        assert!(!pausable.unpause("deposits"));
+        pausable.assert_not_paused("deposits");
+        assert_eq!(get_logs().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Feature deposits is paused")]
+    fn assert_not_paused_fails() {
+        let mut pausable = Pausable::new();
+        pausable.pause("deposits");
+        pausable.assert_not_paused("deposits");
+    }
+
+    #[test]
+    fn assert_not_paused_except() {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account("admin")).build());
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.grant_role("Admin", account("admin"));
+        let mut pausable = Pausable::new();
+        pausable.pause("deposits");
+        pausable.assert_not_paused_except("deposits", &acl, &["Admin"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Feature deposits is paused")]
+    fn assert_not_paused_except_fails() {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account("bob")).build());
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.grant_role("Admin", account("admin"));
+        let mut pausable = Pausable::new();
+        pausable.pause("deposits");
+        pausable.assert_not_paused_except("deposits", &acl, &["Admin"]);
+    }
+
+    #[test]
+    fn pause_management() {
+        set_predecessor("owner");
+        let mut contract = Contract::default();
+        assert!(contract.pause_feature("deposits".to_string()));
+        set_predecessor("manager");
+        assert!(contract.pause_feature("withdrawals".to_string()));
+        assert!(contract.unpause_feature("deposits".to_string()));
+        assert!(!contract.unpause_feature("deposits".to_string()));
+
+        set_predecessor("bob");
+        assert!(contract.is_feature_paused("withdrawals".to_string()));
+        assert!(!contract.is_feature_paused("deposits".to_string()));
+        assert_eq!(contract.paused_features(), ["withdrawals"]);
+        contract.pausable().assert_not_paused("deposits");
+    }
+
+    #[test]
+    #[should_panic(expected = "Predecessor must be the owner or have role PauseManager")]
+    fn pause_feature_by_other_account_fails() {
+        set_predecessor("owner");
+        let mut contract = Contract::default();
+        set_predecessor("bob");
+        contract.pause_feature("deposits".to_string());
+    }
+}
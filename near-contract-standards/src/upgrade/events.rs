@@ -0,0 +1,171 @@
+//! Events of [`Upgrader`](super::Upgrader), in the events format (nep-297):
+//! <https://github.com/near/NEPs/blob/master/specs/Standards/EventsFormat.md>
+//!
+//! The events are [`CodeStaged`] and [`StagingCancelled`] while the code is staged,
+//! [`UpgradeStarted`] when it's deployed, then [`UpgradeCompleted`] or [`UpgradeFailed`] depending
+//! on whether the migration succeeded, and [`TimelockChanged`].
+
+use crate::event::NearEvent;
+use near_sdk::json_types::{Base58CryptoHash, U64};
+use near_sdk::serde::Serialize;
+use near_sdk::AccountIdRef;
+
+/// Data to log when the hash of the staged code is checked. To log this event, call
+/// [`.emit()`](CodeStaged::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CodeStaged<'a> {
+    pub code_hash: Base58CryptoHash,
+    pub deploy_after: U64,
+    pub by: &'a AccountIdRef,
+}
+
+impl CodeStaged<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::CodeStaged(&[self])).emit()
+    }
+}
+
+/// Data to log when the staged code is discarded. To log this event, call
+/// [`.emit()`](StagingCancelled::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StagingCancelled<'a> {
+    pub by: &'a AccountIdRef,
+}
+
+impl StagingCancelled<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::StagingCancelled(&[self])).emit()
+    }
+}
+
+/// Data to log when the staged code is deployed. To log this event, call
+/// [`.emit()`](UpgradeStarted::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeStarted<'a> {
+    pub code_hash: Base58CryptoHash,
+    pub by: &'a AccountIdRef,
+}
+
+impl UpgradeStarted<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::UpgradeStarted(&[self])).emit()
+    }
+}
+
+/// Data to log when the code is deployed and the state migrated. To log this event, call
+/// [`.emit()`](UpgradeCompleted::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeCompleted {
+    pub code_hash: Base58CryptoHash,
+}
+
+impl UpgradeCompleted {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::UpgradeCompleted(&[self])).emit()
+    }
+}
+
+/// Data to log when the migration failed, which rolled back the deployment of the code, or when
+/// the upgrade was aborted. To log this event, call [`.emit()`](UpgradeFailed::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeFailed {
+    pub code_hash: Base58CryptoHash,
+}
+
+impl UpgradeFailed {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::UpgradeFailed(&[self])).emit()
+    }
+}
+
+/// Data to log when the timelock changes. To log this event, call
+/// [`.emit()`](TimelockChanged::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TimelockChanged {
+    pub old_timelock: U64,
+    pub new_timelock: U64,
+}
+
+impl TimelockChanged {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        new_upgrade_v1(UpgradeEventKind::TimelockChanged(&[self])).emit()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub(crate) struct UpgradeEvent<'a> {
+    version: &'static str,
+    #[serde(flatten)]
+    event_kind: UpgradeEventKind<'a>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum UpgradeEventKind<'a> {
+    CodeStaged(&'a [CodeStaged<'a>]),
+    StagingCancelled(&'a [StagingCancelled<'a>]),
+    UpgradeStarted(&'a [UpgradeStarted<'a>]),
+    UpgradeCompleted(&'a [UpgradeCompleted]),
+    UpgradeFailed(&'a [UpgradeFailed]),
+    TimelockChanged(&'a [TimelockChanged]),
+}
+
+fn new_upgrade_v1(event_kind: UpgradeEventKind) -> NearEvent {
+    NearEvent::Upgrade(UpgradeEvent { version: "1.0.0", event_kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils;
+
+    #[test]
+    fn code_staged() {
+        CodeStaged {
+            code_hash: [0; 32].into(),
+            deploy_after: 10.into(),
+            by: AccountIdRef::new_or_panic("bob"),
+        }
+        .emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"upgrade","version":"1.0.0","event":"code_staged","data":[{"code_hash":"11111111111111111111111111111111","deploy_after":"10","by":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn upgrade_failed() {
+        UpgradeFailed { code_hash: [0; 32].into() }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"upgrade","version":"1.0.0","event":"upgrade_failed","data":[{"code_hash":"11111111111111111111111111111111"}]}"#
+        );
+    }
+}
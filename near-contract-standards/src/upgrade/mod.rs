@@ -1,82 +1,409 @@
-use near_sdk::json_types::U64;
-use near_sdk::{env, near, require, AccountId, Duration, Promise, Timestamp};
+//! Self-upgrade of contracts with staged code.
+//!
+//! [`Upgrader`] stores new code of the contract, staged in chunks over several transactions. Once
+//! [`finalize`](Upgrader::finalize) checked the sha256 hash of the staged code, it can be
+//! [`deploy`](Upgrader::deploy)ed after a timelock, in a batch which also calls the `migrate`
+//! method of the new code, e.g. the one generated from `#[migrate]` functions. The code can be
+//! staged and deployed by the owner of the contract, as given by its [`AccessCheck`] such as
+//! [`AccessControl`](crate::access_control::AccessControl), and the members of [`UPGRADER_ROLE`],
+//! and the steps are logged as [`events`].
+//!
+//! If the migration fails, the whole batch is rolled back, which
+//! [`on_upgrade`](Upgrader::on_upgrade) detects in the callback of the batch. The migration isn't
+//! chained with `.then()` after the deployment, since a failing migration would then leave the
+//! new code deployed over the unmigrated state, and the contract couldn't deploy its previous code
+//! back.
+//!
+//! The new state must keep the [`Upgrader`] of the previous one, so that the callback can complete
+//! the upgrade, and the new code must have the callback too. Otherwise the upgrade stays in
+//! progress, blocking the next ones, until the owner calls
+//! [`abort_upgrade`](Upgrader::abort_upgrade).
+//!
+//! ```
+//! use near_contract_standards::access_control::AccessControl;
+//! use near_contract_standards::upgrade::Upgrader;
+//! use near_sdk::json_types::Base58CryptoHash;
+//! use near_sdk::{env, near, AccountId, Gas, PanicOnDefault, Promise};
+//!
+//! /// Code can be deployed a day after it's staged.
+//! const TIMELOCK: u64 = 24 * 60 * 60 * 1_000_000_000;
+//!
+//! #[near(contract_state)]
+//! #[derive(PanicOnDefault)]
+//! pub struct Contract {
+//!     acl: AccessControl,
+//!     upgrader: Upgrader,
+//! }
+//!
+//! #[near]
+//! impl Contract {
+//!     #[init]
+//!     pub fn new(owner_id: AccountId) -> Self {
+//!         Self { acl: AccessControl::new(b"r", owner_id), upgrader: Upgrader::new(b"u", TIMELOCK) }
+//!     }
+//!
+//!     pub fn stage_code(&mut self, #[serializer(borsh)] chunk: Vec<u8>) {
+//!         self.upgrader.stage(&self.acl, chunk);
+//!     }
+//!
+//!     pub fn finalize_code(&mut self, code_hash: Base58CryptoHash) {
+//!         self.upgrader.finalize(&self.acl, code_hash.into());
+//!     }
+//!
+//!     pub fn deploy_code(&mut self) -> Promise {
+//!         self.upgrader
+//!             .deploy(&self.acl, Gas::from_tgas(50))
+//!             .then(Self::ext(env::current_account_id()).on_upgrade())
+//!     }
+//!
+//!     #[private]
+//!     pub fn on_upgrade(&mut self) -> bool {
+//!         self.upgrader.on_upgrade()
+//!     }
+//!
+//!     pub fn abort_upgrade(&mut self) {
+//!         self.upgrader.abort_upgrade(&self.acl);
+//!     }
+//! }
+//! ```
 
-type WrappedDuration = U64;
+pub mod events;
+mod legacy;
 
-pub trait Ownable {
-    fn assert_owner(&self) {
-        require!(env::predecessor_account_id() == self.get_owner(), "Owner must be predecessor");
-    }
-    fn get_owner(&self) -> AccountId;
-    fn set_owner(&mut self, owner: AccountId);
-}
+#[allow(deprecated)]
+pub use self::legacy::{Ownable, Upgradable, Upgrade};
 
-pub trait Upgradable {
-    fn get_staging_duration(&self) -> WrappedDuration;
-    fn stage_code(&mut self, code: Vec<u8>, timestamp: Timestamp);
-    fn deploy_code(&mut self) -> Promise;
+use near_sdk::store::Vector;
+use near_sdk::{
+    env, near, require, CryptoHash, Duration, Gas, IntoStorageKey, NearToken, Promise,
+    PromiseResult, Timestamp,
+};
 
-    /// Implement migration for the next version.
-    /// Should be `unimplemented` for a new contract.
-    /// TODO: consider adding version of the contract stored in the storage?
-    fn migrate(&mut self) {
-        unimplemented!();
-    }
+use crate::access_control::AccessCheck;
+
+use self::events::{
+    CodeStaged, StagingCancelled, TimelockChanged, UpgradeCompleted, UpgradeFailed, UpgradeStarted,
+};
+
+/// Role whose members can stage and deploy code, besides the owner.
+pub const UPGRADER_ROLE: &str = "Upgrader";
+
+/// Staged code whose hash was checked.
+#[near]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StagedCode {
+    pub code_hash: CryptoHash,
+    /// Timestamp from which the code can be deployed.
+    pub deploy_after: Timestamp,
 }
 
+/// Staging area of the new code of the contract.
 #[near]
-pub struct Upgrade {
-    pub owner: AccountId,
-    pub staging_duration: Duration,
-    pub staging_timestamp: Timestamp,
+pub struct Upgrader {
+    chunks: Vector<Vec<u8>>,
+    timelock: Duration,
+    staged: Option<StagedCode>,
+    /// Hash of the code being deployed, until the callback of the deployment.
+    pending: Option<CryptoHash>,
 }
 
-impl Upgrade {
-    pub fn new(owner: AccountId, staging_duration: Duration) -> Self {
-        Self { owner, staging_duration, staging_timestamp: 0 }
+impl Upgrader {
+    /// Creates an upgrader whose staged code is stored under `prefix`, and can be deployed
+    /// `timelock` nanoseconds after its hash is checked.
+    pub fn new<S>(prefix: S, timelock: Duration) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self { chunks: Vector::new(prefix), timelock, staged: None, pending: None }
     }
-}
 
-impl Ownable for Upgrade {
-    fn get_owner(&self) -> AccountId {
-        self.owner.clone()
+    pub fn timelock(&self) -> Duration {
+        self.timelock
     }
 
-    fn set_owner(&mut self, owner: AccountId) {
-        self.assert_owner();
-        self.owner = owner;
+    /// Staged code, once its hash is checked.
+    pub fn staged(&self) -> Option<&StagedCode> {
+        self.staged.as_ref()
+    }
+
+    /// Whether the code is being deployed.
+    pub fn is_upgrading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Sets the timelock of the code staged from now on and emits a [`TimelockChanged`] event.
+    /// Only the owner can change it.
+    pub fn set_timelock(&mut self, acl: &impl AccessCheck, timelock: Duration) {
+        acl.assert_owner();
+        TimelockChanged { old_timelock: self.timelock.into(), new_timelock: timelock.into() }
+            .emit();
+        self.timelock = timelock;
+    }
+
+    /// Appends a chunk to the staged code. The hash of the code must be checked again afterwards.
+    pub fn stage(&mut self, acl: &impl AccessCheck, chunk: Vec<u8>) {
+        self.assert_can_stage(acl);
+        self.chunks.push(chunk);
+        self.staged = None;
+    }
+
+    /// Checks the sha256 hash of the staged code, which starts the timelock, and emits a
+    /// [`CodeStaged`] event.
+    pub fn finalize(&mut self, acl: &impl AccessCheck, code_hash: CryptoHash) {
+        self.assert_can_stage(acl);
+        require!(!self.chunks.is_empty(), "No code is staged");
+        require!(env::sha256_array(&self.code()) == code_hash, "Staged code has a different hash");
+        let deploy_after = env::block_timestamp().saturating_add(self.timelock);
+        self.staged = Some(StagedCode { code_hash, deploy_after });
+        CodeStaged {
+            code_hash: code_hash.into(),
+            deploy_after: deploy_after.into(),
+            by: &env::predecessor_account_id(),
+        }
+        .emit();
     }
-}
 
-impl Upgradable for Upgrade {
-    fn get_staging_duration(&self) -> WrappedDuration {
-        self.staging_duration.into()
+    /// Discards the staged code and emits a [`StagingCancelled`] event.
+    pub fn cancel(&mut self, acl: &impl AccessCheck) {
+        self.assert_can_stage(acl);
+        self.chunks.clear();
+        self.staged = None;
+        StagingCancelled { by: &env::predecessor_account_id() }.emit();
     }
 
-    fn stage_code(&mut self, code: Vec<u8>, timestamp: Timestamp) {
-        self.assert_owner();
+    /// Deploys the staged code once its timelock is over, in a batch which then calls `migrate`
+    /// with `migrate_gas`, and emits an [`UpgradeStarted`] event. The contract must call
+    /// [`on_upgrade`](Self::on_upgrade) in the callback of the returned promise.
+    pub fn deploy(&mut self, acl: &impl AccessCheck, migrate_gas: Gas) -> Promise {
+        self.assert_can_stage(acl);
+        let Some(staged) = self.staged else {
+            env::panic_str("No code is staged");
+        };
+        if env::block_timestamp() < staged.deploy_after {
+            env::panic_str(&format!(
+                "Staged code can't be deployed before {}",
+                staged.deploy_after
+            ));
+        }
+        self.pending = Some(staged.code_hash);
+        UpgradeStarted { code_hash: staged.code_hash.into(), by: &env::predecessor_account_id() }
+            .emit();
+        // The actions of a batch are rolled back together, so the code is only deployed if the
+        // state is migrated.
+        Promise::new(env::current_account_id()).deploy_contract(self.code()).function_call(
+            "migrate".to_string(),
+            vec![],
+            NearToken::from_yoctonear(0),
+            migrate_gas,
+        )
+    }
+
+    /// Completes the upgrade in the callback of [`deploy`](Self::deploy), discarding the staged
+    /// code and emitting an [`UpgradeCompleted`] event. Returns `false` and emits an
+    /// [`UpgradeFailed`] event if the deployment was rolled back, in which case the code stays
+    /// staged.
+    pub fn on_upgrade(&mut self) -> bool {
         require!(
-            env::block_timestamp() + self.staging_duration < timestamp,
-            "Timestamp must be later than staging duration"
+            env::predecessor_account_id() == env::current_account_id(),
+            "Method on_upgrade is private"
         );
-        // Writes directly into storage to avoid serialization penalty by using default struct.
-        env::storage_write(b"upgrade", &code);
-        self.staging_timestamp = timestamp;
-    }
-
-    fn deploy_code(&mut self) -> Promise {
-        if self.staging_timestamp < env::block_timestamp() {
-            env::panic_str(
-                format!(
-                    "Deploy code too early: staging ends on {}",
-                    self.staging_timestamp + self.staging_duration
-                )
-                .as_str(),
-            );
+        let Some(code_hash) = self.pending.take() else {
+            env::panic_str("No upgrade is in progress");
+        };
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            self.chunks.clear();
+            self.staged = None;
+            UpgradeCompleted { code_hash: code_hash.into() }.emit();
+            true
+        } else {
+            UpgradeFailed { code_hash: code_hash.into() }.emit();
+            false
         }
-        let code = env::storage_read(b"upgrade")
-            .unwrap_or_else(|| env::panic_str("No upgrade code available"));
-        env::storage_remove(b"upgrade");
-        Promise::new(env::current_account_id()).deploy_contract(code)
+    }
+
+    /// Gives up on the upgrade in progress when the callback of [`deploy`](Self::deploy) can't
+    /// complete it, e.g. because the new code lacks [`on_upgrade`](Self::on_upgrade) or its state
+    /// doesn't keep the [`Upgrader`], and emits an [`UpgradeFailed`] event. The code stays staged.
+    /// Only the owner can abort an upgrade, after checking whether the code was deployed.
+    pub fn abort_upgrade(&mut self, acl: &impl AccessCheck) {
+        acl.assert_owner();
+        let Some(code_hash) = self.pending.take() else {
+            env::panic_str("No upgrade is in progress");
+        };
+        UpgradeFailed { code_hash: code_hash.into() }.emit();
+    }
+
+    fn code(&self) -> Vec<u8> {
+        self.chunks.iter().flatten().copied().collect()
+    }
+
+    fn assert_can_stage(&self, acl: &impl AccessCheck) {
+        let predecessor = env::predecessor_account_id();
+        if !acl.is_owner(&predecessor) && !acl.has_role(UPGRADER_ROLE, &predecessor) {
+            env::panic_str(&format!("Predecessor must be the owner or have role {UPGRADER_ROLE}"));
+        }
+        require!(self.pending.is_none(), "An upgrade is in progress");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_control::AccessControl;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::{test_vm_config, testing_env, AccountId, RuntimeFeesConfig};
+
+    const CODE: &[u8] = b"\0asm new code";
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn context(predecessor: &str, block_timestamp: Timestamp) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(account("contract"))
+            .predecessor_account_id(account(predecessor))
+            .block_timestamp(block_timestamp);
+        builder
+    }
+
+    /// Sets up the callback of the deployment batch, which had the `result`.
+    fn callback(result: PromiseResult) {
+        testing_env!(
+            context("contract", 100).build(),
+            test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    fn staged_upgrader() -> (AccessControl, Upgrader) {
+        testing_env!(context("owner", 0).build());
+        let mut acl = AccessControl::new(b"r", account("owner"));
+        acl.grant_role(UPGRADER_ROLE, account("alice"));
+        let mut upgrader = Upgrader::new(b"u", 100);
+
+        testing_env!(context("alice", 0).build());
+        upgrader.stage(&acl, CODE[..5].to_vec());
+        upgrader.stage(&acl, CODE[5..].to_vec());
+        upgrader.finalize(&acl, env::sha256_array(CODE));
+        (acl, upgrader)
+    }
+
+    #[test]
+    fn stage_and_upgrade() {
+        let (acl, mut upgrader) = staged_upgrader();
+        let code_hash = env::sha256_array(CODE);
+        assert_eq!(upgrader.staged(), Some(&StagedCode { code_hash, deploy_after: 100 }));
+        assert_eq!(get_logs().len(), 1);
+
+        testing_env!(context("alice", 100).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+        assert!(upgrader.is_upgrading());
+
+        callback(PromiseResult::Successful(vec![]));
+        assert!(upgrader.on_upgrade());
+        assert!(!upgrader.is_upgrading());
+        assert_eq!(upgrader.staged(), None);
+        assert!(upgrader.chunks.is_empty());
+    }
+
+    #[test]
+    fn deploy_and_migrate_in_one_batch() {
+        let (acl, mut upgrader) = staged_upgrader();
+
+        testing_env!(context("alice", 100).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, account("contract"));
+        assert!(matches!(
+            &receipts[0].actions[..],
+            [
+                MockAction::DeployContract { code, .. },
+                MockAction::FunctionCallWeight { method_name, prepaid_gas, .. },
+            ] if code == CODE && method_name == b"migrate" && *prepaid_gas == Gas::from_tgas(50)
+        ));
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_upgrade() {
+        let (acl, mut upgrader) = staged_upgrader();
+
+        testing_env!(context("owner", 100).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+
+        // A failing `migrate` fails the batch, so its deployment is rolled back as well.
+        callback(PromiseResult::Failed);
+        assert!(!upgrader.on_upgrade());
+        assert!(!upgrader.is_upgrading());
+        assert!(upgrader.staged().is_some());
+        assert!(get_logs()[0].contains(r#""event":"upgrade_failed""#));
+    }
+
+    #[test]
+    fn stage_discards_hash() {
+        let (acl, mut upgrader) = staged_upgrader();
+        upgrader.stage(&acl, b"more".to_vec());
+        assert_eq!(upgrader.staged(), None);
+        upgrader.cancel(&acl);
+        assert!(upgrader.chunks.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Staged code has a different hash")]
+    fn finalize_wrong_hash() {
+        let (acl, mut upgrader) = staged_upgrader();
+        upgrader.finalize(&acl, [0; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Staged code can't be deployed before 100")]
+    fn deploy_before_timelock() {
+        let (acl, mut upgrader) = staged_upgrader();
+        testing_env!(context("alice", 99).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+    }
+
+    #[test]
+    #[should_panic(expected = "Predecessor must be the owner or have role Upgrader")]
+    fn stage_without_role() {
+        let (acl, mut upgrader) = staged_upgrader();
+        testing_env!(context("bob", 0).build());
+        upgrader.stage(&acl, CODE.to_vec());
+    }
+
+    #[test]
+    fn abort_stuck_upgrade() {
+        let (acl, mut upgrader) = staged_upgrader();
+        testing_env!(context("alice", 100).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+
+        // The callback never completed, so every step is rejected until the owner aborts.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            upgrader.stage(&acl, CODE.to_vec());
+        }));
+        assert!(result.is_err());
+
+        testing_env!(context("owner", 100).build());
+        upgrader.abort_upgrade(&acl);
+        assert!(!upgrader.is_upgrading());
+        assert!(upgrader.staged().is_some());
+        assert!(get_logs()[0].contains(r#""event":"upgrade_failed""#));
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+        assert!(upgrader.is_upgrading());
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner must be predecessor")]
+    fn abort_upgrade_by_upgrader_fails() {
+        let (acl, mut upgrader) = staged_upgrader();
+        testing_env!(context("alice", 100).build());
+        let _ = upgrader.deploy(&acl, Gas::from_tgas(50));
+        upgrader.abort_upgrade(&acl);
     }
 }
@@ -80,7 +80,16 @@ impl ImplItemMethodInfo {
         let attr_signature_info = &self.attr_signature_info;
 
         let function_name_str = attr_signature_info.ident.to_string();
-        let function_doc = match parse_rustdoc(&attr_signature_info.non_bindgen_attrs) {
+        let mut doc = parse_rustdoc(&attr_signature_info.non_bindgen_attrs);
+        // The ABI has no field for access control, so the required roles are documented.
+        if let Some(access_control) = &attr_signature_info.access_control {
+            let roles = format!("Required roles: {}", access_control.required_roles().join(", "));
+            doc = Some(match doc {
+                Some(doc) => format!("{doc}\n\n{roles}"),
+                None => roles,
+            });
+        }
+        let function_doc = match doc {
             Some(doc) => quote! { ::std::option::Option::Some(::std::string::String::from(#doc)) },
             None => quote! { ::std::option::Option::None },
         };
@@ -133,13 +142,48 @@ impl ImplItemMethodInfo {
                     callbacks.push(generate_abi_type(typ, &arg.serializer_ty));
                 }
                 BindgenArgType::CallbackResultArg => {
-                    let typ = if let Some(ok_type) = utils::extract_ok_type(typ) {
-                        ok_type
+                    let error = || {
+                        syn::Error::new_spanned(
+                            &arg.ty,
+                            "Function parameters marked with #[callback_result] should have type \
+                             Result<T, PromiseError>, a tuple of such results or a Vec of them",
+                        )
+                        .into_compile_error()
+                    };
+                    if let Type::Tuple(type_tuple) = typ {
+                        for elem in &type_tuple.elems {
+                            let Some(typ) = utils::extract_ok_type(elem) else {
+                                return error();
+                            };
+                            callbacks.push(generate_abi_type(typ, &arg.serializer_ty));
+                        }
+                    } else if let Some(elem) = utils::extract_vec_type(typ) {
+                        let Some(typ) = utils::extract_ok_type(elem) else {
+                            return error();
+                        };
+                        if callback_vec.is_some() {
+                            return syn::Error::new(
+                                Span::call_site(),
+                                "A function can only have one parameter receiving all the \
+                                 remaining callback results.",
+                            )
+                            .to_compile_error();
+                        }
+                        let tokens = generate_abi_type(typ, &arg.serializer_ty);
+                        callback_vec = Some(quote! { ::std::option::Option::Some(#tokens) });
                     } else {
+                        let Some(typ) = utils::extract_ok_type(typ) else {
+                            return error();
+                        };
+                        callbacks.push(generate_abi_type(typ, &arg.serializer_ty));
+                    }
+                }
+                BindgenArgType::YieldResultArg => {
+                    let Some(typ) = utils::extract_ok_type(typ) else {
                         return syn::Error::new_spanned(
                             &arg.ty,
-                            "Function parameters marked with \
-                                #[callback_result] should have type Result<T, PromiseError>",
+                            "Function parameters marked with #[yield_result] should have type \
+                             Result<T, PromiseError>",
                         )
                         .into_compile_error();
                     };
@@ -330,6 +374,20 @@ mod tests {
         local_insta_assert_snapshot!(pretty_print_fn_body_syn_str(actual));
     }
 
+    #[test]
+    fn test_generate_abi_access_control() {
+        let impl_type: Type = syn::parse_str("Test").unwrap();
+        let mut method = parse_quote! {
+            /// Mints tokens.
+            #[access_control(role = "Admin", role = "Minter")]
+            pub fn mint(&mut self, amount: u64) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = method_info.abi_struct();
+
+        local_insta_assert_snapshot!(pretty_print_fn_body_syn_str(actual));
+    }
+
     #[test]
     fn test_generate_abi_fallible_borsh() {
         let impl_type: Type = syn::parse_str("Test").unwrap();
@@ -224,80 +224,96 @@ impl AttrSigInfo {
         result
     }
 
-    /// Create code that deserializes arguments that were decorated with `#[callback*]`
+    /// Create code that deserializes arguments that were decorated with `#[callback*]`.
+    ///
+    /// A `#[callback_result]` argument consumes one promise result if its type is
+    /// `Result<T, PromiseError>`, one result per element if it is a tuple of such results and all
+    /// the remaining results if it is a `Vec` of them.
     pub fn callback_deserialization(&self) -> TokenStream2 {
-        self.args
-            .iter()
-            .filter(|arg| {
-                matches!(
-                    arg.bindgen_ty,
-                    BindgenArgType::CallbackArg | BindgenArgType::CallbackResultArg
-                )
-            })
-            .enumerate()
-            .fold(TokenStream2::new(), |acc, (idx, arg)| {
-                let idx = idx as u64;
-                let ArgInfo { mutability, ident, ty, bindgen_ty, serializer_ty, .. } = arg;
-                match &bindgen_ty {
-                    BindgenArgType::CallbackArg => {
-                        let error_msg = format!("Callback computation {} was not successful", idx);
-                        let read_data = quote! {
-                            let data: ::std::vec::Vec<u8> = match ::near_sdk::env::promise_result(#idx) {
-                                ::near_sdk::PromiseResult::Successful(x) => x,
-                                _ => ::near_sdk::env::panic_str(#error_msg)
-                            };
+        let mut idx = 0u64;
+        let mut result = TokenStream2::new();
+        for arg in self.args.iter() {
+            let ArgInfo { mutability, ident, ty, bindgen_ty, serializer_ty, .. } = arg;
+            match &bindgen_ty {
+                BindgenArgType::CallbackArg => {
+                    let error_msg = format!("Callback computation {} was not successful", idx);
+                    let read_data = quote! {
+                        let data: ::std::vec::Vec<u8> = match ::near_sdk::env::promise_result(#idx) {
+                            ::near_sdk::PromiseResult::Successful(x) => x,
+                            _ => ::near_sdk::env::panic_str(#error_msg)
                         };
-                        let invocation = deserialize_data(serializer_ty);
-                        quote! {
-                            #acc
-                            #read_data
-                            let #mutability #ident: #ty = #invocation;
+                    };
+                    let invocation = deserialize_data(serializer_ty);
+                    result.extend(quote! {
+                        #read_data
+                        let #mutability #ident: #ty = #invocation;
+                    });
+                    idx += 1;
+                }
+                BindgenArgType::CallbackResultArg => {
+                    let value = if let syn::Type::Tuple(type_tuple) = ty {
+                        let mut elems = vec![];
+                        for elem in &type_tuple.elems {
+                            elems.push(callback_result(
+                                elem,
+                                serializer_ty,
+                                quote! { #idx },
+                                CALLBACK_RESULT_TYPE_ERROR,
+                            ));
+                            idx += 1;
                         }
-                    }
-                    BindgenArgType::CallbackResultArg => {
-                        let ok_type = if let Some(ok_type) = utils::extract_ok_type(ty) {
-                            ok_type
-                        } else {
-                            return syn::Error::new_spanned(ty, "Function parameters marked with \
-                                #[callback_result] should have type Result<T, PromiseError>").into_compile_error()
-                        };
-                        let deserialize = deserialize_data(serializer_ty);
-                        let deserialization_branch = match ok_type {
-                            // The unit type in this context is a bit special because functions
-                            // without an explicit return type do not serialize their response.
-                            // But when someone tries to refer to their callback result with
-                            // `#[callback_result]` they specify the callback type as
-                            // `Result<(), PromiseError>` which cannot be correctly deserialized from
-                            // an empty byte array.
-                            //
-                            // So instead of going through serde, we consider deserialization to be
-                            // successful if the byte array is empty or try the normal
-                            // deserialization otherwise.
-                            syn::Type::Tuple(type_tuple) if type_tuple.elems.is_empty() =>
-                                quote! {
-                                    ::near_sdk::PromiseResult::Successful(data) if data.is_empty() =>
-                                        ::std::result::Result::Ok(()),
-                                    ::near_sdk::PromiseResult::Successful(data) => ::std::result::Result::Ok(#deserialize)
-                                },
-                            _ =>
-                                quote! {
-                                    ::near_sdk::PromiseResult::Successful(data) => ::std::result::Result::Ok(#deserialize)
-                                }
-                        };
-                        let result = quote! {
-                            match ::near_sdk::env::promise_result(#idx) {
-                                #deserialization_branch,
-                                ::near_sdk::PromiseResult::Failed => ::std::result::Result::Err(::near_sdk::PromiseError::Failed),
-                            }
-                        };
+                        quote! { (#(#elems,)*) }
+                    } else if let Some(elem) = utils::extract_vec_type(ty) {
+                        let elem = callback_result(
+                            elem,
+                            serializer_ty,
+                            quote! { i },
+                            CALLBACK_RESULT_TYPE_ERROR,
+                        );
                         quote! {
-                            #acc
-                            let #mutability #ident: #ty = #result;
+                            ::std::iter::Iterator::collect(::std::iter::Iterator::map(
+                                #idx..::near_sdk::env::promise_results_count(),
+                                |i| #elem,
+                            ))
                         }
-                    }
-                    _ => unreachable!()
+                    } else {
+                        let value = callback_result(
+                            ty,
+                            serializer_ty,
+                            quote! { #idx },
+                            CALLBACK_RESULT_TYPE_ERROR,
+                        );
+                        idx += 1;
+                        value
+                    };
+                    result.extend(quote! {
+                        let #mutability #ident: #ty = #value;
+                    });
                 }
-            })
+                BindgenArgType::YieldResultArg => {
+                    let value = callback_result(
+                        ty,
+                        serializer_ty,
+                        quote! { #idx },
+                        "Function parameters marked with #[yield_result] should have type \
+                         Result<T, PromiseError>",
+                    );
+                    let error_msg = format!(
+                        "Method {} should be called as the callback of a yielded promise",
+                        self.ident
+                    );
+                    result.extend(quote! {
+                        if ::near_sdk::env::promise_results_count() != 1 {
+                            ::near_sdk::env::panic_str(#error_msg);
+                        }
+                        let #mutability #ident: #ty = #value;
+                    });
+                    idx += 1;
+                }
+                _ => {}
+            }
+        }
+        result
     }
 
     /// Create code that deserializes arguments that were decorated with `#[callback_vec]`.
@@ -325,6 +341,51 @@ impl AttrSigInfo {
     }
 }
 
+const CALLBACK_RESULT_TYPE_ERROR: &str = "Function parameters marked with #[callback_result] \
+     should have type Result<T, PromiseError>, a tuple of such results or a Vec of them";
+
+/// Create an expression reading the promise result at `idx` into `ty`, which should be
+/// `Result<T, PromiseError>`, or a compile error with `type_error` otherwise.
+fn callback_result(
+    ty: &syn::Type,
+    serializer_ty: &SerializerType,
+    idx: TokenStream2,
+    type_error: &str,
+) -> TokenStream2 {
+    let ok_type = if let Some(ok_type) = utils::extract_ok_type(ty) {
+        ok_type
+    } else {
+        return syn::Error::new_spanned(ty, type_error).into_compile_error();
+    };
+    let deserialize = deserialize_data(serializer_ty);
+    let deserialization_branch = match ok_type {
+        // The unit type in this context is a bit special because functions
+        // without an explicit return type do not serialize their response.
+        // But when someone tries to refer to their callback result with
+        // `#[callback_result]` they specify the callback type as
+        // `Result<(), PromiseError>` which cannot be correctly deserialized from
+        // an empty byte array.
+        //
+        // So instead of going through serde, we consider deserialization to be
+        // successful if the byte array is empty or try the normal
+        // deserialization otherwise.
+        syn::Type::Tuple(type_tuple) if type_tuple.elems.is_empty() => quote! {
+            ::near_sdk::PromiseResult::Successful(data) if data.is_empty() =>
+                ::std::result::Result::Ok(()),
+            ::near_sdk::PromiseResult::Successful(data) => ::std::result::Result::Ok(#deserialize)
+        },
+        _ => quote! {
+            ::near_sdk::PromiseResult::Successful(data) => ::std::result::Result::Ok(#deserialize)
+        },
+    };
+    quote! {
+        match ::near_sdk::env::promise_result(#idx) {
+            #deserialization_branch,
+            ::near_sdk::PromiseResult::Failed => ::std::result::Result::Err(::near_sdk::PromiseError::Failed),
+        }
+    }
+}
+
 fn deserialize_data(ty: &SerializerType) -> TokenStream2 {
     match ty {
         SerializerType::JSON => quote! {
@@ -1,7 +1,10 @@
-use crate::core_impl::{serializer, AttrSigInfo};
-use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use crate::core_impl::info_extractor::{BindgenArgType, SerializerType};
+use crate::core_impl::{serializer, utils, AttrSigInfo, MethodKind, ReturnKind};
+use proc_macro2::{Ident, Literal, Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote, ToTokens};
-use syn::{parse_quote, Attribute, Generics, Path, Signature};
+use syn::{
+    parse_quote, Attribute, GenericArgument, Generics, Path, PathArguments, Signature, Type,
+};
 
 /// Generates inner ext code for structs and modules. If intended for a struct, generic details
 /// for the struct should be passed in through `generic_details` and the `ext` method will be
@@ -17,8 +20,8 @@ pub(crate) fn generate_ext_structs(
             #name {
                 account_id,
                 deposit: ::near_sdk::NearToken::from_near(0),
-                static_gas: ::near_sdk::Gas::from_gas(0),
-                gas_weight: ::near_sdk::GasWeight::default(),
+                static_gas: ::std::option::Option::None,
+                gas_weight: ::std::option::Option::None,
             }
         }
     };
@@ -36,8 +39,8 @@ pub(crate) fn generate_ext_structs(
       pub struct #name {
           pub(crate) account_id: ::near_sdk::AccountId,
           pub(crate) deposit: ::near_sdk::NearToken,
-          pub(crate) static_gas: ::near_sdk::Gas,
-          pub(crate) gas_weight: ::near_sdk::GasWeight,
+          pub(crate) static_gas: ::std::option::Option<::near_sdk::Gas>,
+          pub(crate) gas_weight: ::std::option::Option<::near_sdk::GasWeight>,
       }
 
       impl #name {
@@ -46,11 +49,11 @@ pub(crate) fn generate_ext_structs(
               self
           }
           pub fn with_static_gas(mut self, static_gas: ::near_sdk::Gas) -> Self {
-              self.static_gas = static_gas;
+              self.static_gas = ::std::option::Option::Some(static_gas);
               self
           }
           pub fn with_unused_gas_weight(mut self, gas_weight: u64) -> Self {
-              self.gas_weight = ::near_sdk::GasWeight(gas_weight);
+              self.gas_weight = ::std::option::Option::Some(::near_sdk::GasWeight(gas_weight));
               self
           }
       }
@@ -125,22 +128,193 @@ fn generate_ext_function(attr_signature_info: &AttrSigInfo) -> TokenStream2 {
         }
     }
     let Signature { generics, .. } = original_sig;
+    // Gas set on the `Ext` struct takes precedence over the defaults from `#[gas(...)]`.
+    let static_gas = Literal::u64_unsuffixed(attr_signature_info.gas.static_gas.unwrap_or(0));
+    let gas_weight = Literal::u64_unsuffixed(attr_signature_info.gas.weight.unwrap_or(1));
+    let promise = quote! {
+        ::near_sdk::Promise::new(self.account_id)
+        .function_call_weight(
+            ::std::string::String::from(#ident_str),
+            __args,
+            self.deposit,
+            self.static_gas.unwrap_or(::near_sdk::Gas::from_gas(#static_gas)),
+            self.gas_weight.unwrap_or(::near_sdk::GasWeight(#gas_weight)),
+        )
+    };
+    let (return_ty, promise) = match promise_result_type(attr_signature_info) {
+        Some(result_ty) => {
+            let serializer = match result_serializer(attr_signature_info) {
+                SerializerType::JSON => quote! { ::near_sdk::serializer::Json },
+                SerializerType::Borsh => quote! { ::near_sdk::serializer::Borsh },
+            };
+            match callback_input_type(attr_signature_info) {
+                Some(input_ty) => (
+                    quote! { ::near_sdk::TypedCallback<#input_ty, #result_ty, #serializer> },
+                    quote! { ::near_sdk::TypedCallback::new(#promise) },
+                ),
+                None => (
+                    quote! { ::near_sdk::TypedPromise<#result_ty, #serializer> },
+                    quote! { ::near_sdk::TypedPromise::new(#promise) },
+                ),
+            }
+        }
+        None => (quote! { ::near_sdk::Promise }, promise),
+    };
     quote! {
         #new_non_bindgen_attrs
-        pub fn #ident #generics(self, #pat_type_list) -> ::near_sdk::Promise {
+        pub fn #ident #generics(self, #pat_type_list) -> #return_ty {
             let __args = #serialize;
-            ::near_sdk::Promise::new(self.account_id)
-            .function_call_weight(
-                ::std::string::String::from(#ident_str),
-                __args,
-                self.deposit,
-                self.static_gas,
-                self.gas_weight,
-            )
+            #promise
         }
     }
 }
 
+fn result_serializer(attr_signature_info: &AttrSigInfo) -> &SerializerType {
+    match &attr_signature_info.method_kind {
+        MethodKind::Call(call_method) => &call_method.result_serializer,
+        MethodKind::View(view_method) => &view_method.result_serializer,
+        MethodKind::Init(_) => &SerializerType::JSON,
+    }
+}
+
+/// Returns the first generic argument of `ty` if it is a path ending with `ident`, e.g. `T` for
+/// `near_sdk::PromiseOrValue<T>`.
+fn generic_argument_of<'a>(ty: &'a Type, ident: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else { return None };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != ident {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Type of the value the promise calling the method resolves to, or `None` if it is not known,
+/// i.e. for methods returning an untyped `Promise`.
+fn promise_result_type(attr_signature_info: &AttrSigInfo) -> Option<TokenStream2> {
+    if let MethodKind::Init(_) = attr_signature_info.method_kind {
+        // Init methods don't return a value, they just save the newly created contract state.
+        return Some(quote! { () });
+    }
+    match &attr_signature_info.returns.kind {
+        ReturnKind::Default => Some(quote! { () }),
+        ReturnKind::HandlesResult(ty) => match utils::extract_ok_type(ty) {
+            Some(ty) => value_type(ty),
+            // E.g. an alias of `Result`.
+            None => Some(quote! { <#ty as ::near_sdk::__private::ResultTypeExt>::Okay }),
+        },
+        ReturnKind::General(ty) => value_type(ty),
+    }
+}
+
+/// Type of the value serialized when a method returns `ty`.
+fn value_type(ty: &Type) -> Option<TokenStream2> {
+    if let Some(ty) = generic_argument_of(ty, "PromiseOrValue")
+        .or_else(|| generic_argument_of(ty, "TypedPromise"))
+    {
+        return owned_type(ty).map(|ty| ty.to_token_stream());
+    }
+    match ty {
+        Type::Path(type_path)
+            if type_path.path.segments.last().is_some_and(|s| s.ident == "Promise") =>
+        {
+            None
+        }
+        ty => owned_type(ty).map(|ty| ty.to_token_stream()),
+    }
+}
+
+/// The value returned by reference is deserialized into its owned type, so `&T` is replaced with
+/// `T`, `&str` with `String` and `&[T]` with `Vec<T>`. Returns `None` for types with other
+/// lifetimes, which can't be named outside of the method.
+fn owned_type(ty: &Type) -> Option<Type> {
+    Some(match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(type_path) if type_path.path.is_ident("str") => {
+                syn::parse_quote! { ::std::string::String }
+            }
+            Type::Slice(type_slice) => {
+                let elem = owned_type(&type_slice.elem)?;
+                syn::parse_quote! { ::std::vec::Vec<#elem> }
+            }
+            elem => owned_type(elem)?,
+        },
+        Type::Path(type_path) => {
+            let mut type_path = type_path.clone();
+            for segment in type_path.path.segments.iter_mut() {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in args.args.iter_mut() {
+                        match arg {
+                            GenericArgument::Type(ty) => *ty = owned_type(ty)?,
+                            GenericArgument::Lifetime(_) => return None,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Type::Path(type_path)
+        }
+        Type::Tuple(type_tuple) => {
+            let mut type_tuple = type_tuple.clone();
+            for elem in type_tuple.elems.iter_mut() {
+                *elem = owned_type(elem)?;
+            }
+            Type::Tuple(type_tuple)
+        }
+        Type::Array(type_array) => {
+            let mut type_array = type_array.clone();
+            *type_array.elem = owned_type(&type_array.elem)?;
+            Type::Array(type_array)
+        }
+        Type::Paren(type_paren) => owned_type(&type_paren.elem)?,
+        Type::Group(type_group) => owned_type(&type_group.elem)?,
+        ty => ty.clone(),
+    })
+}
+
+/// Type of the value the method expects from the promises it is scheduled after, or `None` if
+/// the method has no callback arguments.
+fn callback_input_type(attr_signature_info: &AttrSigInfo) -> Option<TokenStream2> {
+    let mut inputs = vec![];
+    for arg in &attr_signature_info.args {
+        let ty = &arg.ty;
+        match arg.bindgen_ty {
+            BindgenArgType::Regular => {}
+            BindgenArgType::CallbackArg | BindgenArgType::CallbackArgVec => {
+                inputs.push(ty.to_token_stream())
+            }
+            BindgenArgType::CallbackResultArg => {
+                let ok_type = |ty| match utils::extract_ok_type(ty) {
+                    Some(ty) => ty.to_token_stream(),
+                    None => ty.to_token_stream(),
+                };
+                if let Type::Tuple(type_tuple) = ty {
+                    inputs.extend(type_tuple.elems.iter().map(ok_type));
+                } else if let Some(elem) = utils::extract_vec_type(ty) {
+                    let elem = ok_type(elem);
+                    inputs.push(quote! { ::std::vec::Vec<#elem> });
+                } else {
+                    inputs.push(ok_type(ty));
+                }
+            }
+            BindgenArgType::YieldResultArg => match utils::extract_ok_type(ty) {
+                Some(ty) => inputs.push(ty.to_token_stream()),
+                None => inputs.push(ty.to_token_stream()),
+            },
+        }
+    }
+    match inputs.len() {
+        0 => None,
+        1 => inputs.pop(),
+        _ => Some(quote! { (#(#inputs),*) }),
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -206,4 +380,42 @@ mod tests {
        
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn ext_gas_attr() {
+        let impl_type: Type = parse_quote! { Hello };
+        let mut method: ImplItemFn = parse_quote! {
+            #[gas(static = "5 Tgas", weight = 0)]
+            pub fn method(&self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = generate_ext_function(&method_info.attr_signature_info);
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn ext_gas_attr_invalid() {
+        let impl_type: Type = parse_quote! { Hello };
+        let mut method: ImplItemFn = parse_quote! {
+            #[gas(static = "5 NEAR")]
+            pub fn method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "Expected an amount of gas like \"5 Tgas\", \"100 Ggas\" or \"1000 gas\"."
+        );
+    }
+
+    #[test]
+    fn owned_reference_types() {
+        let owned = |ty: Type| owned_type(&ty).map(|ty| ty.to_token_stream().to_string());
+        assert_eq!(owned(parse_quote! { &Item }).unwrap(), "Item");
+        assert_eq!(owned(parse_quote! { &str }).unwrap(), ":: std :: string :: String");
+        assert_eq!(owned(parse_quote! { &[&str] }).unwrap(), ":: std :: vec :: Vec < :: std :: string :: String >");
+        assert_eq!(owned(parse_quote! { Option<&Item> }).unwrap(), "Option < Item >");
+        assert_eq!(owned(parse_quote! { &'a Item }).unwrap(), "Item");
+        assert_eq!(owned(parse_quote! { Cow<'a, str> }), None);
+    }
 }
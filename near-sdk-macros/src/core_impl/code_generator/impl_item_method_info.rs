@@ -22,6 +22,8 @@ impl ImplItemMethodInfo {
         let deposit_check = self.deposit_check_tokens();
         let is_private_check = self.private_check_tokens();
         let state_check = self.state_check_tokens();
+        let pause_check = self.pause_check_tokens();
+        let log_flush = self.log_flush_tokens();
 
         let body = match self.attr_signature_info.returns.kind {
             // Extractor errors if Init method doesn't return anything, so we don't need extra check
@@ -39,12 +41,14 @@ impl ImplItemMethodInfo {
                 #panic_hook
                 #is_private_check
                 #deposit_check
+                #pause_check
                 #arg_struct
                 #arg_parsing
                 #callback_deser
                 #callback_vec_deser
                 #state_check
                 #body
+                #log_flush
             }
         }
     }
@@ -99,6 +103,13 @@ impl ImplItemMethodInfo {
         }
     }
 
+    fn log_flush_tokens(&self) -> TokenStream2 {
+        // Logs the number of messages `near_sdk::logging` dropped, if any.
+        quote! {
+            ::near_sdk::logging::flush();
+        }
+    }
+
     fn panic_hook_tokens(&self) -> TokenStream2 {
         quote! {
             ::near_sdk::env::setup_panic_hook();
@@ -195,6 +206,33 @@ impl ImplItemMethodInfo {
         }
     }
 
+    fn pause_check_tokens(&self) -> TokenStream2 {
+        // The contract is loaded before the arguments so that a paused method fails early, and
+        // isn't loaded again by `contract_init_tokens`.
+        match &self.attr_signature_info.pause {
+            Some(pause) => {
+                let struct_type = &self.struct_type;
+                let state_version_check = self.state_version_check_tokens();
+                let check = pause.check(&self.attr_signature_info.ident.to_string());
+                quote! {
+                    #state_version_check
+                    let mut contract: #struct_type = ::near_sdk::env::state_read().unwrap_or_default();
+                    #check
+                }
+            }
+            None => quote! {},
+        }
+    }
+
+    fn state_version_check_tokens(&self) -> TokenStream2 {
+        // Versioned contracts refuse to read a state of another version, which must be migrated.
+        let struct_type = &self.struct_type;
+        let write_missing = matches!(self.attr_signature_info.method_kind, MethodKind::Call(_));
+        quote! {
+            ::near_sdk::__private::check_state_version::<#struct_type>(#write_missing);
+        }
+    }
+
     fn state_check_tokens(&self) -> TokenStream2 {
         use MethodKind::*;
 
@@ -228,8 +266,10 @@ impl ImplItemMethodInfo {
 
         let contract_deser = |receiver: &Receiver| {
             let mutability = receiver.mutability;
+            let state_version_check = self.state_version_check_tokens();
 
             quote! {
+                #state_version_check
                 let #mutability contract: #struct_type = ::near_sdk::env::state_read().unwrap_or_default();
             }
         };
@@ -237,6 +277,9 @@ impl ImplItemMethodInfo {
         // In Call and View methods, the contract is deserialized from the state.
         // In Init methods the contract is created with the constructor.
         match &self.attr_signature_info.method_kind {
+            // Paused methods load the contract in `pause_check_tokens`.
+            Call(_) if self.attr_signature_info.pause.is_some() => quote! {},
+
             Call(call_method) => {
                 if let Some(receiver) = &call_method.receiver {
                     contract_deser(receiver)
@@ -277,7 +320,15 @@ impl ImplItemMethodInfo {
                 }
             }
 
-            Init(_) => contract_ser(),
+            // The version of versioned contracts is written with the state.
+            Init(_) => {
+                let contract_ser = contract_ser();
+                let struct_type = &self.struct_type;
+                quote! {
+                    #contract_ser
+                    ::near_sdk::__private::write_state_version::<#struct_type>();
+                }
+            }
 
             // View methods don't update the state.
             View(_) => quote! {},
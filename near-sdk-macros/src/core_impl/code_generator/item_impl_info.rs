@@ -1,7 +1,8 @@
 use crate::core_impl::ext::generate_ext_function_wrappers;
+use crate::core_impl::MethodKind;
 use crate::ItemImplInfo;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{spanned::Spanned, Ident};
 
 impl ItemImplInfo {
@@ -23,16 +24,76 @@ impl ItemImplInfo {
             Err(e) => syn::Error::new(self.ty.span(), e).to_compile_error(),
         }
     }
+
+    /// Generates the `METHOD_NAMES` constant of `#[near(method_names)]`, with the public change
+    /// methods, which are the ones worth calling with a function call access key. The constant is
+    /// defined on the contract type, so only one impl block of a type can generate it.
+    pub fn method_names_const(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        let method_names = self
+            .methods
+            .iter()
+            .filter(|method| {
+                matches!(
+                    &method.attr_signature_info.method_kind,
+                    MethodKind::Call(call_method) if !call_method.is_private
+                )
+            })
+            .map(|method| method.attr_signature_info.ident.to_string());
+        quote! {
+            impl #ty {
+                /// Names of the public change methods of the contract, see
+                /// [`FunctionCallKeyPermission`](::near_sdk::FunctionCallKeyPermission).
+                pub const METHOD_NAMES: &'static [&'static str] = &[#(#method_names),*];
+            }
+        }
+    }
 }
 // Rustfmt removes comas.
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
-    use syn::{parse_quote, parse_str, ImplItemFn, Type};
-    use crate::core_impl::info_extractor::ImplItemMethodInfo;
+    use syn::{parse_quote, parse_str, ImplItemFn, ItemImpl, Type};
+    use crate::core_impl::info_extractor::{ImplItemMethodInfo, ItemImplInfo};
     use crate::core_impl::utils::test_helpers::{local_insta_assert_snapshot, pretty_print_syn_str};
 
 
+    #[test]
+    fn method_names_const() {
+        let mut item: ItemImpl = parse_quote! {
+            impl Hello {
+                #[init]
+                pub fn new() -> Self { Self {} }
+                pub fn get(&self) -> u64 { 0 }
+                pub fn set(&mut self, value: u64) {}
+                #[payable]
+                pub fn deposit(&mut self) {}
+                #[private]
+                pub fn on_deposit(&mut self) {}
+                fn helper(&self) {}
+            }
+        };
+        let info = ItemImplInfo::new(&mut item).unwrap();
+        let actual = info.method_names_const();
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn migrate_chain() {
+        let mut item: ItemImpl = parse_quote! {
+            impl Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(old: HelloV1) -> HelloV2 { HelloV2 {} }
+                #[migrate(from = "HelloV2")]
+                pub fn from_v2(old: HelloV2) -> Self { Self {} }
+            }
+        };
+        let info = ItemImplInfo::new(&mut item).unwrap();
+        let wrapper = info.wrapper_code();
+        let actual = quote::quote! { #item #wrapper };
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
     #[test]
     fn trait_implt() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -170,6 +231,99 @@ mod tests {
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
 
+    #[test]
+    fn callback_args_results_tuple() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[private] pub fn method(&self, #[callback_result] x: (Result<u64, PromiseError>, Result<(), PromiseError>), #[callback_result] #[serializer(borsh)] y: Result<String, PromiseError>) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn callback_args_results_vec() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[private] pub fn method(&self, #[callback_unwrap] x: u64, #[callback_result] y: Vec<Result<String, PromiseError>>) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn yield_result_arg() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[private] pub fn method(&mut self, request: u64, #[yield_result] x: Result<String, PromiseError>) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn yield_result_arg_with_callbacks() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[private] pub fn method(&self, #[callback_unwrap] x: u64, #[yield_result] y: Result<String, PromiseError>) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "A #[yield_result] argument can't be combined with other callback arguments."
+        );
+    }
+
+    #[test]
+    fn callback_after_callback_vec() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[private] pub fn method(&self, #[callback_result] x: Vec<Result<u64, PromiseError>>, #[callback_unwrap] y: u64) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "A callback argument can't follow an argument receiving all the remaining promise results."
+        );
+    }
+
+    #[test]
+    fn pause_check() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[pause(feature = "deposits", except(roles = ["Admin"]))]
+            pub fn method(&mut self, k: u64) -> u64 { k }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn pause_on_view() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemFn = parse_quote! {
+            #[pause] pub fn method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!(actual.to_string(), "`pause` can only be used on methods which take `&mut self`.");
+    }
+
+    #[test]
+    fn pause_on_other_receivers() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        for mut method in [
+            parse_quote! { #[pause] #[private] pub fn method(&self) { } },
+            parse_quote! { #[pause] pub fn method(mut self) { } },
+        ] {
+            let actual = ImplItemMethodInfo::new(&mut method, None, impl_type.clone()).map(|_| ()).unwrap_err();
+            assert_eq!(actual.to_string(), "`pause` can only be used on methods which take `&mut self`.");
+        }
+    }
+
     #[test]
     fn callback_args_vec() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
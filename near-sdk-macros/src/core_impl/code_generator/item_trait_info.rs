@@ -1,11 +1,12 @@
 use crate::core_impl::ext::{generate_ext_function_wrappers, generate_ext_structs};
-use crate::core_impl::info_extractor::ItemTraitInfo;
+use crate::core_impl::info_extractor::{ItemTraitInfo, MethodKind};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
 impl ItemTraitInfo {
-    /// Generate code that wraps external calls.
-    pub fn wrap_trait_ext(&self) -> TokenStream2 {
+    /// Generate code that wraps external calls, and the `METHOD_NAMES` constant if `method_names`
+    /// is set.
+    pub fn wrap_trait_ext(&self, method_names: bool) -> TokenStream2 {
         let mod_name = &self.mod_name;
         let ext_structs = generate_ext_structs(&self.original.ident, None);
 
@@ -14,14 +15,42 @@ impl ItemTraitInfo {
             self.methods.iter().map(|m| &m.attr_sig_info),
         );
 
+        let method_names_const = if method_names {
+            self.method_names_const()
+        } else {
+            quote! {}
+        };
+
         quote! {
             pub mod #mod_name {
                 use super::*;
                 #ext_structs
                 #ext_methods
+                #method_names_const
             }
         }
     }
+
+    /// Generates the `METHOD_NAMES` constant of `#[ext_contract(mod_name, method_names)]`, with
+    /// the change methods of the trait, which are the ones worth calling with a function call
+    /// access key.
+    fn method_names_const(&self) -> TokenStream2 {
+        let method_names = self
+            .methods
+            .iter()
+            .filter(|m| {
+                matches!(
+                    &m.attr_sig_info.method_kind,
+                    MethodKind::Call(call_method) if !call_method.is_private
+                )
+            })
+            .map(|m| m.attr_sig_info.ident.to_string());
+        quote! {
+            /// Names of the public change methods of the contract, see
+            /// [`FunctionCallKeyPermission`](::near_sdk::FunctionCallKeyPermission).
+            pub const METHOD_NAMES: &'static [&'static str] = &[#(#method_names),*];
+        }
+    }
 }
 
 // Rustfmt removes comas.
@@ -52,7 +81,7 @@ mod tests {
             }
         ).unwrap();
         let info = ItemTraitInfo::new(&mut t, None).unwrap();
-        let actual = info.wrap_trait_ext();
+        let actual = info.wrap_trait_ext(false);
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
 
@@ -67,8 +96,24 @@ mod tests {
             }
         ).unwrap();
         let info = ItemTraitInfo::new(&mut t, None).unwrap();
-        let actual = info.wrap_trait_ext();
+        let actual = info.wrap_trait_ext(false);
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 
+    #[test]
+    fn ext_method_names() {
+        let mut t: ItemTrait = syn::parse2(
+            quote!{
+                pub trait Game {
+                    fn play(&mut self, position: u8);
+                    fn board(&self) -> Vec<u8>;
+                    fn resign(&mut self);
+                }
+            }
+        ).unwrap();
+        let info = ItemTraitInfo::new(&mut t, None).unwrap();
+        let actual = info.wrap_trait_ext(true);
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
 }
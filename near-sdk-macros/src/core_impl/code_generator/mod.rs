@@ -10,3 +10,5 @@ pub(crate) mod ext;
 pub(crate) mod metadata;
 
 pub(crate) mod serializer;
+
+pub(crate) mod state_version;
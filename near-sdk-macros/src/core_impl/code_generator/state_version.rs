@@ -0,0 +1,63 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Generics;
+
+/// Implements `VersionedState` for a state declared with `version = N`.
+pub(crate) fn generate_versioned_state(
+    near_sdk_crate: &TokenStream,
+    ident: &Ident,
+    generics: &Generics,
+    version: Option<u32>,
+) -> TokenStream {
+    let Some(version) = version else {
+        return quote! {};
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #near_sdk_crate::VersionedState for #ident #ty_generics #where_clause {
+            const STATE_VERSION: u32 = #version;
+        }
+    }
+}
+
+/// Implements the version of the contract state, which the method wrappers check the stored
+/// version against, and `VersionedState` if the state is versioned.
+pub(crate) fn generate_contract_state_version(
+    ident: &Ident,
+    generics: &Generics,
+    version: Option<u32>,
+) -> TokenStream {
+    let versioned_state = generate_versioned_state(&quote! {::near_sdk}, ident, generics, version);
+    let state_version = match version {
+        Some(version) => quote! { ::core::option::Option::Some(#version) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #versioned_state
+        impl #impl_generics ::near_sdk::__private::ContractStateVersion for #ident #ty_generics #where_clause {
+            const STATE_VERSION: ::core::option::Option<u32> = #state_version;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_contract_state_version;
+    use crate::core_impl::utils::test_helpers::{
+        local_insta_assert_snapshot, pretty_print_syn_str,
+    };
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn contract_state_version() {
+        let unversioned: ItemStruct = parse_quote! { struct Hello {} };
+        let versioned: ItemStruct = parse_quote! { struct HelloV2<T> { value: T } };
+        let unversioned =
+            generate_contract_state_version(&unversioned.ident, &unversioned.generics, None);
+        let versioned =
+            generate_contract_state_version(&versioned.ident, &versioned.generics, Some(2));
+        let actual = quote::quote! { #unversioned #versioned };
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+}
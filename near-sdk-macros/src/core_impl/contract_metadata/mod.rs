@@ -19,6 +19,8 @@ const STANDARD_NEP330_VERSION: &str = "1.3.0";
 #[derive(FromMeta)]
 struct MacroConfig {
     contract_metadata: Option<ContractMetadata>,
+    /// Version of the contract state, see [`contract_state_version`].
+    version: Option<u32>,
 }
 
 #[derive(serde::Serialize, Default, FromMeta)]
@@ -135,15 +137,20 @@ pub(crate) fn contract_source_metadata_const(attr: proc_macro::TokenStream) -> T
         }
     };
 
-    let metadata = serde_json::to_string(
-        &args
-            .contract_metadata
-            .expect("Attribute input must be present given standard was followed")
-            .populate(),
-    )
-    .expect("ContractMetadata implements Serialize");
+    let metadata = serde_json::to_string(&args.contract_metadata.unwrap_or_default().populate())
+        .expect("ContractMetadata implements Serialize");
 
     quote! {
         const CONTRACT_SOURCE_METADATA: &'static str = #metadata;
     }
 }
+
+/// Version of the contract state given with `version = N`, which `#[near(contract_state)]`
+/// forwards. Invalid attributes are reported by [`contract_source_metadata_const`].
+pub(crate) fn contract_state_version(attr: proc_macro::TokenStream) -> Option<u32> {
+    if attr.is_empty() {
+        return None;
+    }
+    let attr_args = NestedMeta::parse_meta_list(attr.into()).ok()?;
+    MacroConfig::from_list(&attr_args).ok()?.version
+}
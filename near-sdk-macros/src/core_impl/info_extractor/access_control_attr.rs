@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, LitStr};
+
+/// Accounts allowed to call the method, set with `#[access_control(role = "Admin")]` or
+/// `#[only(owner)]` and checked with `near_sdk::access_control`.
+#[derive(Clone)]
+pub enum AccessControlAttr {
+    /// Members of any of the roles.
+    Roles(Vec<String>),
+    /// The owner of the contract.
+    Owner,
+}
+
+impl AccessControlAttr {
+    /// Parses `#[access_control(role = "Admin", role = "Minter")]`.
+    pub fn parse_roles(attr: &Attribute) -> syn::Result<Self> {
+        let mut roles = vec![];
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("role") {
+                let role: LitStr = meta.value()?.parse()?;
+                if role.value().is_empty() {
+                    return Err(Error::new(role.span(), "Role can't be empty."));
+                }
+                roles.push(role.value());
+                Ok(())
+            } else {
+                Err(meta.error("Expected `role`."))
+            }
+        })?;
+        if roles.is_empty() {
+            return Err(Error::new(attr.span(), "Expected at least one `role`."));
+        }
+        Ok(Self::Roles(roles))
+    }
+
+    /// Parses `#[only(owner)]`.
+    pub fn parse_only(attr: &Attribute) -> syn::Result<Self> {
+        let mut owner = false;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("owner") && !owner {
+                owner = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `owner`."))
+            }
+        })?;
+        if !owner {
+            return Err(Error::new(attr.span(), "Expected `owner`."));
+        }
+        Ok(Self::Owner)
+    }
+
+    /// Roles listed in the ABI, where the owner is named `owner`.
+    #[cfg(feature = "__abi-generate")]
+    pub fn required_roles(&self) -> Vec<String> {
+        match self {
+            Self::Roles(roles) => roles.clone(),
+            Self::Owner => vec!["owner".to_string()],
+        }
+    }
+
+    /// Statement which panics if the predecessor is not allowed to call the method.
+    pub fn check(&self) -> TokenStream2 {
+        let acl = quote! { ::near_sdk::access_control::AccessControllable::acl(self) };
+        match self {
+            Self::Roles(roles) => quote! {
+                ::near_sdk::access_control::AccessCheck::assert_any_role(#acl, &[#(#roles),*]);
+            },
+            Self::Owner => quote! { ::near_sdk::access_control::AccessCheck::assert_owner(#acl); },
+        }
+    }
+}
@@ -13,6 +13,9 @@ pub enum BindgenArgType {
     CallbackResultArg,
     /// An argument that we read from all `env::promise_result()`.
     CallbackArgVec,
+    /// An argument that we read from the `env::promise_result()` of a yielded promise, which
+    /// handles the timeout.
+    YieldResultArg,
 }
 
 /// A single argument of a function after it was processed by the bindgen.
@@ -92,6 +95,9 @@ impl ArgInfo {
                 "callback_vec" => {
                     bindgen_ty = BindgenArgType::CallbackArgVec;
                 }
+                "yield_result" => {
+                    bindgen_ty = BindgenArgType::YieldResultArg;
+                }
                 "serializer" => {
                     let args = match AttributeConfig::from_attributes(&original.attrs) {
                         Ok(args) => args,
@@ -132,6 +138,7 @@ impl ArgInfo {
                 && attr_str != "serializer"
                 && attr_str != "callback_result"
                 && attr_str != "callback_unwrap"
+                && attr_str != "yield_result"
         });
 
         match (&pat_info, &result_sanitize_and_ty, more_errors.is_empty()) {
@@ -1,6 +1,7 @@
 use super::visitor::Visitor;
 use super::{
-    ArgInfo, BindgenArgType, HandleResultAttr, InitAttr, MethodKind, SerializerAttr, SerializerType,
+    AccessControlAttr, ArgInfo, BindgenArgType, GasAttr, HandleResultAttr, InitAttr, LockAttr,
+    MethodKind, PauseAttr, ReturnKind, SerializerAttr, SerializerType,
 };
 use crate::core_impl::{utils, Returns};
 use proc_macro2::{Span, TokenStream as TokenStream2};
@@ -24,6 +25,17 @@ pub struct AttrSigInfo {
     pub input_serializer: SerializerType,
     /// The original method signature.
     pub original_sig: Signature,
+    /// Default gas of the calls made through the `Ext` struct.
+    pub gas: GasAttr,
+    /// Whether the method body is profiled as a gas span, set with `#[gas_profile]`.
+    pub gas_profile: bool,
+    /// Accounts allowed to call the method, set with `#[access_control(...)]` or `#[only(...)]`.
+    pub access_control: Option<AccessControlAttr>,
+    /// Pause check done by the wrapper before deserializing the arguments, set with `#[pause]`.
+    pub pause: Option<PauseAttr>,
+    /// Callback lock acquired by the method, released if it's private or only checked, set with
+    /// `#[lock("...")]` or `#[lock("...", check)]`.
+    pub lock: Option<LockAttr>,
 }
 
 use darling::FromAttributes;
@@ -96,6 +108,14 @@ impl AttrSigInfo {
 
         let ident = original_sig.ident.clone();
         let mut non_bindgen_attrs = vec![];
+        let mut gas = None;
+        let mut gas_profile = false;
+        let mut access_control = None;
+        let mut access_control_span = Span::call_site();
+        let mut pause = None;
+        let mut pause_span = Span::call_site();
+        let mut lock = None;
+        let mut lock_span = Span::call_site();
 
         let args = AttributeConfig::from_attributes(original_attrs)?;
         // Visit attributes
@@ -147,6 +167,52 @@ impl AttrSigInfo {
                         visitor.visit_handle_result_attr(&handle_result);
                     }
                 }
+                "gas" => {
+                    if gas.is_some() {
+                        return Err(Error::new(attr.span(), "Duplicate `gas` attribute."));
+                    }
+                    gas = Some(GasAttr::parse(attr)?);
+                }
+                "access_control" | "only" => {
+                    if access_control.is_some() {
+                        return Err(Error::new(
+                            attr.span(),
+                            "Only one `access_control` or `only` attribute can be used.",
+                        ));
+                    }
+                    access_control = Some(if attr_str == "only" {
+                        AccessControlAttr::parse_only(attr)?
+                    } else {
+                        AccessControlAttr::parse_roles(attr)?
+                    });
+                    access_control_span = attr.span();
+                }
+                "pause" => {
+                    if pause.is_some() {
+                        return Err(Error::new(attr.span(), "Duplicate `pause` attribute."));
+                    }
+                    pause = Some(PauseAttr::parse(attr)?);
+                    pause_span = attr.span();
+                }
+                "lock" => {
+                    if lock.is_some() {
+                        return Err(Error::new(attr.span(), "Duplicate `lock` attribute."));
+                    }
+                    lock = Some(LockAttr::parse(attr)?);
+                    lock_span = attr.span();
+                }
+                "gas_profile" => {
+                    if !matches!(attr.meta, syn::Meta::Path(_)) {
+                        return Err(Error::new(
+                            attr.span(),
+                            "`gas_profile` attribute doesn't take arguments.",
+                        ));
+                    }
+                    if gas_profile {
+                        return Err(Error::new(attr.span(), "Duplicate `gas_profile` attribute."));
+                    }
+                    gas_profile = true;
+                }
                 _ => {
                     non_bindgen_attrs.push((*attr).clone());
                 }
@@ -166,6 +232,83 @@ impl AttrSigInfo {
 
         let (method_kind, returns) = visitor.build()?;
 
+        // The predecessor can only be checked in call methods, and the check needs the state.
+        if access_control.is_some() && !takes_mut_self(&method_kind) {
+            return Err(Error::new(
+                access_control_span,
+                "`access_control` and `only` can only be used on methods which take `&mut self`.",
+            ));
+        }
+
+        // The pause flag is read from the state, which the wrapper loads as mutable to pass it on.
+        if pause.is_some() && !takes_mut_self(&method_kind) {
+            return Err(Error::new(
+                pause_span,
+                "`pause` can only be used on methods which take `&mut self`.",
+            ));
+        }
+
+        // The lock is kept in storage, which view methods can't write.
+        if lock.is_some() && !matches!(method_kind, MethodKind::Call(_)) {
+            return Err(Error::new(lock_span, "`lock` can only be used on call methods."));
+        }
+
+        // A lock acquired by a method which doesn't schedule a callback would never be released.
+        if let Some(lock) = &lock {
+            let is_callback = matches!(&method_kind, MethodKind::Call(call) if call.is_private);
+            let returns_promise = match &returns.kind {
+                ReturnKind::General(ty) => utils::type_is_promise(ty),
+                ReturnKind::HandlesResult(ty) => {
+                    utils::extract_ok_type(ty).is_some_and(utils::type_is_promise)
+                }
+                ReturnKind::Default => false,
+            };
+            if !lock.check && !is_callback && !returns_promise {
+                return Err(Error::new(
+                    lock_span,
+                    "`lock` can only acquire the lock in methods returning `Promise` or \
+                     `TypedPromise`, use `#[lock(\"...\", check)]` to only check that it isn't held.",
+                ));
+            }
+        }
+
+        // The callback of a yielded promise receives a single promise result.
+        if let Some(arg) =
+            args.iter().find(|arg| matches!(arg.bindgen_ty, BindgenArgType::YieldResultArg))
+        {
+            if args.iter().filter(|arg| !matches!(arg.bindgen_ty, BindgenArgType::Regular)).count()
+                > 1
+            {
+                return Err(Error::new_spanned(
+                    &arg.original,
+                    "A #[yield_result] argument can't be combined with other callback arguments.",
+                ));
+            }
+        }
+
+        // `Vec`s of callback results receive all the promise results left by the arguments before.
+        let mut callback_args = args.iter().filter(|arg| {
+            matches!(
+                arg.bindgen_ty,
+                BindgenArgType::CallbackArg
+                    | BindgenArgType::CallbackResultArg
+                    | BindgenArgType::CallbackArgVec
+            )
+        });
+        if callback_args.by_ref().any(|arg| match arg.bindgen_ty {
+            BindgenArgType::CallbackArgVec => true,
+            BindgenArgType::CallbackResultArg => utils::extract_vec_type(&arg.ty).is_some(),
+            _ => false,
+        }) {
+            if let Some(arg) = callback_args.next() {
+                return Err(Error::new_spanned(
+                    &arg.original,
+                    "A callback argument can't follow an argument receiving all the remaining \
+                     promise results.",
+                ));
+            }
+        }
+
         self_occurrences.extend(args.iter().flat_map(|arg| arg.self_occurrences.clone()));
 
         original_attrs.clone_from(&non_bindgen_attrs);
@@ -185,6 +328,11 @@ impl AttrSigInfo {
             returns,
             input_serializer: SerializerType::JSON,
             original_sig: original_sig.clone(),
+            gas: gas.unwrap_or_default(),
+            gas_profile,
+            access_control,
+            pause,
+            lock,
         };
 
         let input_serializer =
@@ -208,6 +356,16 @@ impl AttrSigInfo {
     }
 }
 
+// Whether the method is a call method taking `&mut self`.
+fn takes_mut_self(method_kind: &MethodKind) -> bool {
+    matches!(
+        method_kind,
+        MethodKind::Call(call) if call.receiver.as_ref().is_some_and(|receiver| {
+            receiver.reference.is_some() && receiver.mutability.is_some()
+        })
+    )
+}
+
 // Generate errors for a given collection of spans. Returns `Ok` if no spans are provided.
 fn report_spans(spans: &[Span], msg: &str) -> Result<(), syn::Error> {
     if spans.is_empty() {
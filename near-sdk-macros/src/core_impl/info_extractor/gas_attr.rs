@@ -0,0 +1,85 @@
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Lit};
+
+/// Default gas of the calls made through the `Ext` struct of the method, set with
+/// `#[gas(static = "5 Tgas", weight = 1)]`.
+#[derive(Clone, Default)]
+pub struct GasAttr {
+    /// Static gas in gas units.
+    pub static_gas: Option<u64>,
+    /// Weight of the unused gas.
+    pub weight: Option<u64>,
+}
+
+impl GasAttr {
+    pub fn parse(attr: &Attribute) -> syn::Result<Self> {
+        let mut gas_attr = GasAttr::default();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("static") {
+                let value: Lit = meta.value()?.parse()?;
+                let gas = match &value {
+                    Lit::Str(s) => parse_gas(&s.value()),
+                    Lit::Int(i) => i.base10_parse().ok(),
+                    _ => None,
+                };
+                gas_attr.static_gas = Some(gas.ok_or_else(|| {
+                    Error::new(
+                        value.span(),
+                        "Expected an amount of gas like \"5 Tgas\", \"100 Ggas\" or \"1000 gas\".",
+                    )
+                })?);
+                Ok(())
+            } else if meta.path.is_ident("weight") {
+                let value: syn::LitInt = meta.value()?.parse()?;
+                gas_attr.weight = Some(value.base10_parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `static` or `weight`."))
+            }
+        })?;
+        if gas_attr.static_gas.is_none() && gas_attr.weight.is_none() {
+            return Err(Error::new(attr.span(), "Expected `static` and/or `weight` to be set."));
+        }
+        Ok(gas_attr)
+    }
+}
+
+/// Parses amounts like `5 Tgas` or `2.5 Tgas` into gas units.
+fn parse_gas(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let decimals = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "gas" => 0,
+        "ggas" => 9,
+        "tgas" => 12,
+        "pgas" => 15,
+        _ => return None,
+    };
+    let number = number.replace('_', "");
+    let (int, frac) = number.split_once('.').unwrap_or((&number, ""));
+    if int.is_empty() || frac.len() > decimals {
+        return None;
+    }
+    let digits = format!("{int}{frac:0<decimals$}");
+    digits.parse::<u128>().ok()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_gas;
+
+    #[test]
+    fn gas_amounts() {
+        assert_eq!(parse_gas("5 Tgas"), Some(5_000_000_000_000));
+        assert_eq!(parse_gas("2.5 Tgas"), Some(2_500_000_000_000));
+        assert_eq!(parse_gas("100 Ggas"), Some(100_000_000_000));
+        assert_eq!(parse_gas("1_000 gas"), Some(1_000));
+        assert_eq!(parse_gas("1000"), Some(1_000));
+        assert_eq!(parse_gas("1 PGas"), Some(1_000_000_000_000_000));
+        assert_eq!(parse_gas("1.5 gas"), None);
+        assert_eq!(parse_gas("5 Near"), None);
+        assert_eq!(parse_gas("Tgas"), None);
+        assert_eq!(parse_gas("100000 Pgas"), None);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::core_impl::info_extractor::AttrSigInfo;
+use crate::core_impl::info_extractor::{AttrSigInfo, MethodKind};
 use crate::core_impl::utils;
 use quote::ToTokens;
-use syn::{ImplItemFn as ImplItemMethod, Path, Type, Visibility};
+use syn::{parse_quote, ImplItemFn as ImplItemMethod, Path, Type, Visibility};
 
 /// Information extracted from `ImplItemMethod`.
 pub struct ImplItemMethodInfo {
@@ -20,11 +20,36 @@ impl ImplItemMethodInfo {
         impl_trait: Option<Path>,
         struct_type: Type,
     ) -> syn::Result<Option<Self>> {
-        let ImplItemMethod { attrs, sig, .. } = original;
+        let ImplItemMethod { attrs, sig, block, .. } = original;
         utils::sig_is_supported(sig)?;
         if impl_trait.is_some() || matches!(original.vis, Visibility::Public(_)) {
             let source_type = &struct_type.to_token_stream();
             let attr_signature_info = AttrSigInfo::new(attrs, sig, source_type)?;
+            if let Some(lock) = &attr_signature_info.lock {
+                let is_callback = matches!(
+                    &attr_signature_info.method_kind,
+                    MethodKind::Call(call) if call.is_private
+                );
+                let statement = lock.statement(is_callback);
+                *block = parse_quote! {{
+                    #statement
+                    #block
+                }};
+            }
+            if let Some(access_control) = &attr_signature_info.access_control {
+                let check = access_control.check();
+                *block = parse_quote! {{
+                    #check
+                    #block
+                }};
+            }
+            if attr_signature_info.gas_profile {
+                let name = attr_signature_info.ident.to_string();
+                *block = parse_quote! {{
+                    let __near_gas_span = ::near_sdk::gas_profile::GasSpan::method(#name);
+                    #block
+                }};
+            }
             Ok(Some(Self { attr_signature_info, struct_type, impl_trait }))
         } else {
             Ok(None)
@@ -112,4 +137,222 @@ mod tests {
         let expected = "View function can't be payable.";
         assert_eq!(expected.to_string(), actual.to_string());
     }
+
+    #[test]
+    fn gas_profile_wraps_body() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[gas_profile]
+            pub fn method(&self) -> u64 { 1 }
+        };
+        let info = ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        assert!(info.attr_signature_info.gas_profile);
+        assert!(method.attrs.is_empty());
+        let expected: syn::Block = parse_quote! {{
+            let __near_gas_span = ::near_sdk::gas_profile::GasSpan::method("method");
+            { 1 }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn gas_profile_with_arguments_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[gas_profile(name = "x")]
+            pub fn method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`gas_profile` attribute doesn't take arguments.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn access_control_checks_roles() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[access_control(role = "Admin", role = "Minter")]
+            pub fn method(&mut self) { self.x = 1; }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::access_control::AccessCheck::assert_any_role(
+                ::near_sdk::access_control::AccessControllable::acl(self),
+                &["Admin", "Minter"]
+            );
+            { self.x = 1; }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn only_owner_checks_owner() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[only(owner)]
+            pub fn method(&mut self) { }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::access_control::AccessCheck::assert_owner(
+                ::near_sdk::access_control::AccessControllable::acl(self)
+            );
+            { }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn access_control_on_view_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[only(owner)]
+            pub fn method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`access_control` and `only` can only be used on methods which take `&mut self`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn access_control_on_other_receivers_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let expected = "`access_control` and `only` can only be used on methods which take `&mut self`.";
+        for mut method in [
+            parse_quote! { #[only(owner)] #[private] pub fn method(&self) { } },
+            parse_quote! { #[access_control(role = "Admin")] pub fn method(mut self) { } },
+        ] {
+            let actual = ImplItemMethodInfo::new(&mut method, None, impl_type.clone()).map(|_| ()).unwrap_err();
+            assert_eq!(expected, actual.to_string());
+        }
+    }
+
+    #[test]
+    fn access_control_combined_with_only_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[only(owner)]
+            #[access_control(role = "Admin")]
+            pub fn method(&mut self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Only one `access_control` or `only` attribute can be used.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn access_control_without_roles_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[access_control(owner)]
+            pub fn method(&mut self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Expected `role`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn lock_acquires_after_access_check() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[only(owner)]
+            #[lock("loan")]
+            pub fn method(&mut self) -> Promise { promise() }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::access_control::AccessCheck::assert_owner(
+                ::near_sdk::access_control::AccessControllable::acl(self)
+            );
+            {
+                ::near_sdk::CallbackLock::new("loan").acquire();
+                { promise() }
+            }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn lock_acquired_by_handled_typed_promise() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[handle_result]
+            #[lock("loan")]
+            pub fn method(&mut self) -> Result<near_sdk::TypedPromise<u128>, String> { promise() }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::CallbackLock::new("loan").acquire();
+            { promise() }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn lock_without_promise_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[lock("loan")]
+            pub fn withdraw(&mut self) -> u128 { 0 }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`lock` can only acquire the lock in methods returning `Promise` or \
+                        `TypedPromise`, use `#[lock(\"...\", check)]` to only check that it isn't held.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn lock_check_only() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[lock("loan", check)]
+            pub fn withdraw(&mut self) { }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::CallbackLock::new("loan").assert_not_held();
+            { }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn lock_unknown_mode_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[lock("loan", release)]
+            pub fn withdraw(&mut self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!("Expected `check`.", actual.to_string());
+    }
+
+    #[test]
+    fn lock_released_by_private_callback() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private]
+            #[lock("loan")]
+            pub fn method(&mut self) { }
+        };
+        ImplItemMethodInfo::new(&mut method, None, impl_type).unwrap().unwrap();
+        let expected: syn::Block = parse_quote! {{
+            ::near_sdk::CallbackLock::new("loan").release();
+            { }
+        }};
+        assert_eq!(expected, method.block);
+    }
+
+    #[test]
+    fn lock_on_view_fails() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[lock("loan")]
+            pub fn method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, None, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`lock` can only be used on call methods.";
+        assert_eq!(expected, actual.to_string());
+    }
 }
@@ -1,3 +1,4 @@
+use super::{migrate_method, MigrateAttr};
 use crate::ImplItemMethodInfo;
 use syn::spanned::Spanned;
 use syn::{Error, ImplItem, ItemImpl, Type};
@@ -22,9 +23,22 @@ impl ItemImplInfo {
         let trait_ = original.trait_.as_ref().map(|(_not, path, _for)| path);
 
         let mut methods = vec![];
+        let mut migrations = vec![];
         let mut errors = vec![];
         for subitem in &mut original.items {
             if let ImplItem::Fn(m) = subitem {
+                // Migrations aren't exported, they're run by the generated `migrate` method.
+                match MigrateAttr::take(m) {
+                    Ok(Some(migration)) => {
+                        migrations.push(migration);
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                }
                 match ImplItemMethodInfo::new(m, trait_.cloned(), ty.clone()) {
                     Ok(Some(method_info)) => methods.push(method_info),
                     Ok(None) => {} // do nothing
@@ -33,6 +47,27 @@ impl ItemImplInfo {
             }
         }
 
+        if !migrations.is_empty() {
+            if let Some(trait_) = trait_ {
+                errors.push(Error::new(
+                    trait_.span(),
+                    "`migrate` functions can only be used in inherent impl blocks.",
+                ));
+            } else {
+                match migrate_method(&migrations, &ty) {
+                    Ok(mut method) => {
+                        match ImplItemMethodInfo::new(&mut method, None, ty.clone()) {
+                            Ok(Some(method_info)) => methods.push(method_info),
+                            Ok(None) => {}
+                            Err(e) => errors.push(e),
+                        }
+                        original.items.push(ImplItem::Fn(method));
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+
         if !errors.is_empty() {
             // Combine all errors into one
             let combined_error = errors.into_iter().reduce(|mut l, r| {
@@ -0,0 +1,52 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Error, Ident, LitStr, Token};
+
+/// Callback lock of a method, set with `#[lock("name")]` or `#[lock("name", check)]` and held with
+/// `near_sdk::CallbackLock`.
+#[derive(Clone)]
+pub struct LockAttr {
+    /// Name of the lock.
+    pub name: String,
+    /// Whether the method only checks that the lock isn't held, without acquiring it.
+    pub check: bool,
+}
+
+impl Parse for LockAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: LitStr = input.parse()?;
+        if name.value().is_empty() {
+            return Err(Error::new(name.span(), "Lock name can't be empty."));
+        }
+        let check = if input.is_empty() {
+            false
+        } else {
+            input.parse::<Token![,]>()?;
+            let mode: Ident = input.parse()?;
+            if mode != "check" {
+                return Err(Error::new(mode.span(), "Expected `check`."));
+            }
+            true
+        };
+        Ok(LockAttr { name: name.value(), check })
+    }
+}
+
+impl LockAttr {
+    pub fn parse(attr: &Attribute) -> syn::Result<Self> {
+        attr.parse_args()
+    }
+
+    /// Statement which checks the lock, acquires it, or releases it in a callback.
+    pub fn statement(&self, is_callback: bool) -> TokenStream2 {
+        let name = &self.name;
+        if self.check {
+            quote! { ::near_sdk::CallbackLock::new(#name).assert_not_held(); }
+        } else if is_callback {
+            quote! { ::near_sdk::CallbackLock::new(#name).release(); }
+        } else {
+            quote! { ::near_sdk::CallbackLock::new(#name).acquire(); }
+        }
+    }
+}
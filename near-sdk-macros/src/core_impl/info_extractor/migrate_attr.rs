@@ -0,0 +1,206 @@
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Error, FnArg, Ident, ImplItemFn, LitStr, ReturnType, Type};
+
+/// Migration of the contract state from a previous version, set with
+/// `#[migrate(from = "ContractV1")]` on a function which takes the previous state and returns the
+/// next one.
+pub struct MigrateAttr {
+    /// Name of the function.
+    pub ident: Ident,
+    /// Previous state, which implements `near_sdk::VersionedState`.
+    pub from: Type,
+    /// Next state, either the contract or the `from` of another migration.
+    pub to: Type,
+}
+
+impl MigrateAttr {
+    /// Removes the `migrate` attribute of the method, returning the migration if it has one.
+    pub fn take(method: &mut ImplItemFn) -> syn::Result<Option<Self>> {
+        let Some(index) = method.attrs.iter().position(|attr| attr.path().is_ident("migrate"))
+        else {
+            return Ok(None);
+        };
+        let attr = method.attrs.remove(index);
+        if method.attrs.iter().any(|attr| attr.path().is_ident("migrate")) {
+            return Err(Error::new(attr.span(), "Duplicate `migrate` attribute."));
+        }
+
+        let mut from = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("from") {
+                let value: LitStr = meta.value()?.parse()?;
+                from = Some(value.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected `from`."))
+            }
+        })?;
+        let from = from.ok_or_else(|| Error::new(attr.span(), "Expected `from`."))?;
+
+        let sig = &method.sig;
+        if sig.inputs.len() != 1 || matches!(sig.inputs.first(), Some(FnArg::Receiver(_))) {
+            return Err(Error::new(
+                sig.inputs.span(),
+                "A `migrate` function takes the previous state as its only argument.",
+            ));
+        }
+        let to = match &sig.output {
+            ReturnType::Type(_, ty) => (**ty).clone(),
+            ReturnType::Default => {
+                return Err(Error::new(
+                    sig.ident.span(),
+                    "A `migrate` function must return the next state.",
+                ))
+            }
+        };
+        Ok(Some(Self { ident: sig.ident.clone(), from, to }))
+    }
+}
+
+/// Generates the `migrate` init method of the contract `ty`, which runs the migrations from the
+/// version of the stored state to the contract.
+pub fn migrate_method(migrations: &[MigrateAttr], ty: &Type) -> syn::Result<ImplItemFn> {
+    let type_str = |ty: &Type| ty.to_token_stream().to_string();
+    let contract = type_str(ty);
+    let from_index = |ty: &Type| migrations.iter().position(|m| type_str(&m.from) == type_str(ty));
+
+    for (i, migration) in migrations.iter().enumerate() {
+        if from_index(&migration.from) != Some(i) {
+            return Err(Error::new_spanned(
+                &migration.from,
+                format!("Duplicate `migrate` function from `{}`.", type_str(&migration.from)),
+            ));
+        }
+    }
+
+    let mut branches = vec![];
+    for migration in migrations {
+        let mut state = quote! { state };
+        let mut current = migration;
+        let mut steps = 0;
+        loop {
+            let ident = &current.ident;
+            state = quote! { Self::#ident(#state) };
+            let to = type_str(&current.to);
+            if to == "Self" || to == contract {
+                break;
+            }
+            current = match from_index(&current.to) {
+                Some(next) => &migrations[next],
+                None => {
+                    return Err(Error::new_spanned(
+                        &current.to,
+                        format!("No `migrate` function from `{to}`."),
+                    ))
+                }
+            };
+            // A chain without cycles runs each migration at most once.
+            steps += 1;
+            if steps == migrations.len() {
+                return Err(Error::new_spanned(
+                    &migration.from,
+                    "`migrate` functions form a cycle.",
+                ));
+            }
+        }
+
+        let from = &migration.from;
+        branches.push(quote! {
+            if version == <#from as ::near_sdk::VersionedState>::STATE_VERSION {
+                let state: #from = ::near_sdk::env::state_read()
+                    .unwrap_or_else(|| ::near_sdk::env::panic_str("The contract is not initialized"));
+                return #state;
+            }
+        });
+    }
+
+    Ok(parse_quote! {
+        /// Migrates the contract state from the stored version with the `#[migrate]` functions.
+        #[init(ignore_state)]
+        pub fn migrate() -> Self {
+            // Init methods don't support `#[private]`.
+            if ::near_sdk::env::current_account_id() != ::near_sdk::env::predecessor_account_id() {
+                ::near_sdk::env::panic_str("Method migrate is private");
+            }
+            let version = ::near_sdk::env::state_version().unwrap_or(0);
+            #(#branches)*
+            ::near_sdk::env::panic_str(&::std::format!(
+                "Cannot migrate the contract state from version {version} to {}",
+                <Self as ::near_sdk::VersionedState>::STATE_VERSION
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core_impl::info_extractor::ItemImplInfo;
+    use syn::{parse_quote, ItemImpl};
+
+    fn error(mut item: ItemImpl) -> String {
+        ItemImplInfo::new(&mut item).map(|_| ()).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn missing_migration() {
+        let item = parse_quote! {
+            impl Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(old: HelloV1) -> HelloV2 { HelloV2 {} }
+            }
+        };
+        assert_eq!(error(item), "No `migrate` function from `HelloV2`.");
+    }
+
+    #[test]
+    fn cycle() {
+        let item = parse_quote! {
+            impl Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(old: HelloV1) -> HelloV2 { HelloV2 {} }
+                #[migrate(from = "HelloV2")]
+                fn from_v2(old: HelloV2) -> HelloV1 { HelloV1 {} }
+            }
+        };
+        assert_eq!(error(item), "`migrate` functions form a cycle.");
+    }
+
+    #[test]
+    fn duplicate_from() {
+        let item = parse_quote! {
+            impl Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(old: HelloV1) -> Self { Self {} }
+                #[migrate(from = "HelloV1")]
+                fn also_from_v1(old: HelloV1) -> Self { Self {} }
+            }
+        };
+        assert_eq!(error(item), "Duplicate `migrate` function from `HelloV1`.");
+    }
+
+    #[test]
+    fn migrate_with_receiver() {
+        let item = parse_quote! {
+            impl Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(&self) -> Self { Self {} }
+            }
+        };
+        assert_eq!(
+            error(item),
+            "A `migrate` function takes the previous state as its only argument."
+        );
+    }
+
+    #[test]
+    fn migrate_in_trait_impl() {
+        let item = parse_quote! {
+            impl Migrations for Hello {
+                #[migrate(from = "HelloV1")]
+                fn from_v1(old: HelloV1) -> Self { Self {} }
+            }
+        };
+        assert_eq!(error(item), "`migrate` functions can only be used in inherent impl blocks.");
+    }
+}
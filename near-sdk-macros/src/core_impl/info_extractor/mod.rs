@@ -26,6 +26,21 @@ mod item_impl_info;
 mod init_attr;
 pub use init_attr::InitAttr;
 
+mod gas_attr;
+pub use gas_attr::GasAttr;
+
+mod access_control_attr;
+pub use access_control_attr::AccessControlAttr;
+
+mod pause_attr;
+pub use pause_attr::PauseAttr;
+
+mod lock_attr;
+pub use lock_attr::LockAttr;
+
+mod migrate_attr;
+pub use migrate_attr::{migrate_method, MigrateAttr};
+
 mod visitor;
 
 pub use item_impl_info::ItemImplInfo;
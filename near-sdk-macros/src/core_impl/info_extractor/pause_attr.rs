@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Error, ExprArray, Lit, LitStr};
+
+/// Pause check of a method, set with `#[pause]`, `#[pause(feature = "deposits")]` or
+/// `#[pause(except(roles = ["Admin"]))]` and checked with `near_sdk::pausable`.
+#[derive(Clone)]
+pub struct PauseAttr {
+    /// Name of the feature which pauses the method, the method name by default.
+    pub feature: Option<String>,
+    /// Roles whose members can call the method while it's paused.
+    pub except_roles: Vec<String>,
+}
+
+impl PauseAttr {
+    pub fn parse(attr: &Attribute) -> syn::Result<Self> {
+        let mut pause_attr = PauseAttr { feature: None, except_roles: vec![] };
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(pause_attr);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("feature") {
+                let feature: LitStr = meta.value()?.parse()?;
+                if feature.value().is_empty() {
+                    return Err(Error::new(feature.span(), "Feature can't be empty."));
+                }
+                pause_attr.feature = Some(feature.value());
+                Ok(())
+            } else if meta.path.is_ident("except") {
+                meta.parse_nested_meta(|meta| {
+                    if !meta.path.is_ident("roles") {
+                        return Err(meta.error("Expected `roles`."));
+                    }
+                    let roles: ExprArray = meta.value()?.parse()?;
+                    for role in roles.elems {
+                        match role {
+                            syn::Expr::Lit(syn::ExprLit { lit: Lit::Str(role), .. })
+                                if !role.value().is_empty() =>
+                            {
+                                pause_attr.except_roles.push(role.value())
+                            }
+                            role => return Err(Error::new_spanned(role, "Expected a role name.")),
+                        }
+                    }
+                    Ok(())
+                })
+            } else {
+                Err(meta.error("Expected `feature` or `except`."))
+            }
+        })?;
+        Ok(pause_attr)
+    }
+
+    /// Statement which panics if the feature of the method is paused, given the deserialized
+    /// `contract`.
+    pub fn check(&self, method_name: &str) -> TokenStream2 {
+        let feature = self.feature.as_deref().unwrap_or(method_name);
+        let pausable = quote! { ::near_sdk::pausable::PausableContract::pausable(&contract) };
+        if self.except_roles.is_empty() {
+            quote! { ::near_sdk::pausable::PauseCheck::assert_not_paused(#pausable, #feature); }
+        } else {
+            let roles = &self.except_roles;
+            quote! {
+                ::near_sdk::pausable::PauseCheck::assert_not_paused_except(
+                    #pausable,
+                    #feature,
+                    ::near_sdk::access_control::AccessControllable::acl(&contract),
+                    &[#(#roles),*],
+                );
+            }
+        }
+    }
+}
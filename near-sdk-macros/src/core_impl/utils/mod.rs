@@ -24,6 +24,19 @@ pub(crate) fn type_is_result(ty: &Type) -> bool {
     }
 }
 
+/// Checks whether the given type is a `Promise` or a `TypedPromise`, possibly qualified.
+/// Like `type_is_result`, it won't match a type alias.
+pub(crate) fn type_is_promise(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().is_some_and(|segment| {
+                segment.ident == "Promise" || segment.ident == "TypedPromise"
+            })
+        }
+        _ => false,
+    }
+}
+
 /// Extracts the Ok type from a `Result` type.
 ///
 /// For example, given `Result<String, u8>` type it will return `String` type.
@@ -50,7 +63,6 @@ pub(crate) fn extract_ok_type(ty: &Type) -> Option<&Type> {
 /// Checks whether the given path is literally "Vec".
 /// Note that it won't match a fully qualified name `std::vec::Vec` or a type alias like
 /// `type MyVec = Vec<String>`.
-#[cfg(feature = "__abi-generate")]
 fn path_is_vec(path: &Path) -> bool {
     path.leading_colon.is_none()
         && path.segments.len() == 1
@@ -60,7 +72,6 @@ fn path_is_vec(path: &Path) -> bool {
 /// Extracts the inner generic type from a `Vec<_>` type.
 ///
 /// For example, given `Vec<String>` this function will return `String`.
-#[cfg(feature = "__abi-generate")]
 pub(crate) fn extract_vec_type(ty: &Type) -> Option<&Type> {
     match ty {
         Type::Path(type_path) if type_path.qself.is_none() && path_is_vec(&type_path.path) => {
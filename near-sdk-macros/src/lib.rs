@@ -3,7 +3,11 @@ extern crate proc_macro;
 
 mod core_impl;
 
-use core_impl::{ext::generate_ext_structs, metadata::generate_contract_metadata_method};
+use core_impl::{
+    ext::generate_ext_structs,
+    metadata::generate_contract_metadata_method,
+    state_version::{generate_contract_state_version, generate_versioned_state},
+};
 
 use proc_macro::TokenStream;
 
@@ -41,6 +45,40 @@ struct NearMacroArgs {
     contract_state: Option<bool>,
     contract_metadata: Option<core_impl::ContractMetadata>,
     inside_nearsdk: Option<bool>,
+    method_names: Option<bool>,
+    version: Option<u32>,
+}
+
+/// Arguments of `#[near_bindgen]` on impl blocks, which `#[near]` forwards.
+#[derive(FromMeta)]
+struct ImplBlockArgs {
+    method_names: Option<bool>,
+}
+
+/// Arguments of `#[ext_contract(mod_name, method_names)]`, both optional.
+struct ExtContractArgs {
+    mod_name: Option<Ident>,
+    method_names: bool,
+}
+
+impl syn::parse::Parse for ExtContractArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { mod_name: None, method_names: false });
+        }
+        let mod_name = input.parse()?;
+        let method_names = if input.is_empty() {
+            false
+        } else {
+            input.parse::<syn::Token![,]>()?;
+            let flag: Ident = input.parse()?;
+            if flag != "method_names" {
+                return Err(syn::Error::new(flag.span(), "expected `method_names`"));
+            }
+            true
+        };
+        Ok(Self { mod_name: Some(mod_name), method_names })
+    }
 }
 
 fn has_nested_near_macros(item: TokenStream) -> bool {
@@ -103,11 +141,16 @@ pub fn near(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut expanded: proc_macro2::TokenStream = quote! {};
 
-    if near_macro_args.contract_state.unwrap_or(false) {
-        if let Some(metadata) = near_macro_args.contract_metadata {
-            expanded = quote! {#[#near_sdk_crate::near_bindgen(#metadata)]}
-        } else {
+    let contract_state = near_macro_args.contract_state.unwrap_or(false);
+    if contract_state {
+        let metadata =
+            near_macro_args.contract_metadata.as_ref().map(|metadata| quote! {#metadata});
+        let version = near_macro_args.version.map(|version| quote! {version = #version});
+        let bindgen_args: Vec<_> = metadata.into_iter().chain(version).collect();
+        if bindgen_args.is_empty() {
             expanded = quote! {#[#near_sdk_crate::near_bindgen]}
+        } else {
+            expanded = quote! {#[#near_sdk_crate::near_bindgen(#(#bindgen_args),*)]}
         }
     };
 
@@ -186,21 +229,56 @@ pub fn near(attr: TokenStream, item: TokenStream) -> TokenStream {
         };
     }
 
+    let method_names = near_macro_args.method_names.unwrap_or(false);
+    if method_names && syn::parse::<ItemImpl>(item.clone()).is_err() {
+        return TokenStream::from(
+            syn::Error::new(Span::call_site(), "`method_names` can only be used on impl blocks.")
+                .to_compile_error(),
+        );
+    }
+
+    if near_macro_args.version.is_some() && syn::parse::<ItemImpl>(item.clone()).is_ok() {
+        return TokenStream::from(
+            syn::Error::new(
+                Span::call_site(),
+                "`version` can only be used on struct or enum definitions.",
+            )
+            .to_compile_error(),
+        );
+    }
+
+    // The contract state's version is implemented by `near_bindgen`.
+    let versioned_state = |ident: &Ident, generics: &syn::Generics| {
+        let version = if contract_state { None } else { near_macro_args.version };
+        generate_versioned_state(&near_sdk_crate, ident, generics, version)
+    };
+
     if let Ok(input) = syn::parse::<ItemStruct>(item.clone()) {
+        let versioned_state = versioned_state(&input.ident, &input.generics);
         expanded = quote! {
             #expanded
             #input
+            #versioned_state
         };
     } else if let Ok(input) = syn::parse::<ItemEnum>(item.clone()) {
+        let versioned_state = versioned_state(&input.ident, &input.generics);
         expanded = quote! {
             #expanded
             #input
+            #versioned_state
         };
     } else if let Ok(input) = syn::parse::<ItemImpl>(item) {
-        expanded = quote! {
-            #[#near_sdk_crate::near_bindgen]
-            #input
-        };
+        if method_names {
+            expanded = quote! {
+                #[#near_sdk_crate::near_bindgen(method_names)]
+                #input
+            };
+        } else {
+            expanded = quote! {
+                #[#near_sdk_crate::near_bindgen]
+                #input
+            };
+        }
     } else {
         return TokenStream::from(
             syn::Error::new(
@@ -227,10 +305,15 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
 
         let metadata_impl_gen = syn::parse::<ItemImpl>(metadata_impl_gen)
             .expect("failed to generate contract metadata");
-        process_impl_block(metadata_impl_gen)
+        process_impl_block(metadata_impl_gen, false)
     };
 
     if let Ok(input) = syn::parse::<ItemStruct>(item.clone()) {
+        let state_version = generate_contract_state_version(
+            &input.ident,
+            &input.generics,
+            core_impl::contract_state_version(attr.clone()),
+        );
         let metadata = core_impl::contract_source_metadata_const(attr);
 
         let metadata_impl_gen = generate_metadata(&input.ident, &input.generics);
@@ -251,8 +334,14 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
             #abi_embedded
             #metadata
             #metadata_impl_gen
+            #state_version
         })
     } else if let Ok(input) = syn::parse::<ItemEnum>(item.clone()) {
+        let state_version = generate_contract_state_version(
+            &input.ident,
+            &input.generics,
+            core_impl::contract_state_version(attr.clone()),
+        );
         let metadata = core_impl::contract_source_metadata_const(attr);
         let metadata_impl_gen = generate_metadata(&input.ident, &input.generics);
 
@@ -272,6 +361,7 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
             #abi_embedded
             #metadata
             #metadata_impl_gen
+            #state_version
         })
     } else if let Ok(input) = syn::parse::<ItemImpl>(item) {
         for method in &input.items {
@@ -288,7 +378,14 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
                 }
             }
         }
-        match process_impl_block(input) {
+        let args = match NestedMeta::parse_meta_list(attr.into())
+            .map_err(Error::from)
+            .and_then(|meta_list| ImplBlockArgs::from_list(&meta_list))
+        {
+            Ok(v) => v,
+            Err(e) => return TokenStream::from(e.write_errors()),
+        };
+        match process_impl_block(input, args.method_names.unwrap_or(false)) {
             Ok(output) => output,
             Err(output) => output,
         }
@@ -308,10 +405,12 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
 //
 // # Arguments
 // * input - impl block to process.
+// * method_names - whether to generate the `METHOD_NAMES` constant, see `#[near(method_names)]`.
 //
 // The Result has a TokenStream error type, because those need to be propagated to the compiler.
 fn process_impl_block(
     mut input: ItemImpl,
+    method_names: bool,
 ) -> Result<proc_macro2::TokenStream, proc_macro2::TokenStream> {
     let item_impl_info = match ItemImplInfo::new(&mut input) {
         Ok(x) => x,
@@ -328,11 +427,18 @@ fn process_impl_block(
     // Add wrapper methods for ext call API
     let ext_generated_code = item_impl_info.generate_ext_wrapper_code();
 
+    let method_names_const = if method_names {
+        item_impl_info.method_names_const()
+    } else {
+        quote! {}
+    };
+
     Ok(TokenStream::from(quote! {
         #ext_generated_code
         #input
         #generated_code
         #abi_generated
+        #method_names_const
     })
     .into())
 }
@@ -340,27 +446,23 @@ fn process_impl_block(
 #[proc_macro_attribute]
 pub fn ext_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     if let Ok(mut input) = syn::parse::<ItemTrait>(item) {
-        let mod_name: Option<proc_macro2::Ident> = if attr.is_empty() {
-            None
-        } else {
-            match syn::parse(attr) {
-                Ok(x) => x,
-                Err(err) => {
-                    return TokenStream::from(
-                        syn::Error::new(
-                            Span::call_site(),
-                            format!("Failed to parse mod name for ext_contract: {}", err),
-                        )
-                        .to_compile_error(),
+        let args: ExtContractArgs = match syn::parse(attr) {
+            Ok(x) => x,
+            Err(err) => {
+                return TokenStream::from(
+                    syn::Error::new(
+                        Span::call_site(),
+                        format!("Failed to parse mod name for ext_contract: {}", err),
                     )
-                }
+                    .to_compile_error(),
+                )
             }
         };
-        let item_trait_info = match ItemTraitInfo::new(&mut input, mod_name) {
+        let item_trait_info = match ItemTraitInfo::new(&mut input, args.mod_name) {
             Ok(x) => x,
             Err(err) => return TokenStream::from(err.to_compile_error()),
         };
-        let ext_api = item_trait_info.wrap_trait_ext();
+        let ext_api = item_trait_info.wrap_trait_ext(args.method_names);
 
         TokenStream::from(quote! {
             #input
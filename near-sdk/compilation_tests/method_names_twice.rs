@@ -0,0 +1,19 @@
+//! Only one impl block of a contract can generate `METHOD_NAMES`.
+
+use near_sdk::near;
+
+#[derive(Default)]
+#[near(contract_state)]
+struct Game {}
+
+#[near(method_names)]
+impl Game {
+    pub fn play(&mut self) {}
+}
+
+#[near(method_names)]
+impl Game {
+    pub fn resign(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,94 @@
+//! Checks made by the
+//! [`#[access_control(...)]` and `#[only(owner)]`](crate::near#access_control-and-onlyowner-annotate-methods-of-a-type-in-its-impl-block)
+//! method attributes.
+//!
+//! A contract implements [`AccessControllable`] to return the component storing its owner and
+//! roles, such as `near_contract_standards::access_control::AccessControl`, which implements
+//! [`AccessCheck`].
+
+use crate::{env, require, AccountIdRef};
+
+/// Owner and roles of a contract.
+pub trait AccessCheck {
+    /// Whether the account is the owner of the contract.
+    fn is_owner(&self, account_id: &AccountIdRef) -> bool;
+
+    /// Whether the account is a member of the role.
+    fn has_role(&self, role: &str, account_id: &AccountIdRef) -> bool;
+
+    /// Whether the account is a member of any of the roles.
+    fn has_any_role(&self, roles: &[&str], account_id: &AccountIdRef) -> bool {
+        roles.iter().any(|role| self.has_role(role, account_id))
+    }
+
+    /// Panics if the predecessor isn't the owner.
+    fn assert_owner(&self) {
+        require!(self.is_owner(&env::predecessor_account_id()), "Owner must be predecessor");
+    }
+
+    /// Panics if the predecessor doesn't have the role.
+    fn assert_role(&self, role: &str) {
+        self.assert_any_role(&[role])
+    }
+
+    /// Panics if the predecessor doesn't have any of the roles.
+    fn assert_any_role(&self, roles: &[&str]) {
+        if !self.has_any_role(roles, &env::predecessor_account_id()) {
+            env::panic_str(&format!("Predecessor must have role {}", roles.join(" or ")));
+        }
+    }
+}
+
+/// Contract whose methods are checked by `#[access_control(...)]` and `#[only(owner)]`.
+pub trait AccessControllable {
+    type Acl: AccessCheck;
+
+    fn acl(&self) -> &Self::Acl;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::VMContextBuilder;
+    use crate::testing_env;
+
+    struct Acl;
+
+    impl AccessCheck for Acl {
+        fn is_owner(&self, account_id: &AccountIdRef) -> bool {
+            account_id == "owner.near"
+        }
+
+        fn has_role(&self, role: &str, account_id: &AccountIdRef) -> bool {
+            role == "Admin" && account_id == "admin.near"
+        }
+    }
+
+    fn set_predecessor(account_id: &str) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account_id.parse().unwrap())
+            .build());
+    }
+
+    #[test]
+    fn assert_roles() {
+        set_predecessor("admin.near");
+        Acl.assert_role("Admin");
+        Acl.assert_any_role(&["Minter", "Admin"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Predecessor must have role Minter or Admin")]
+    fn assert_any_role_fails() {
+        set_predecessor("owner.near");
+        Acl.assert_owner();
+        Acl.assert_any_role(&["Minter", "Admin"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner must be predecessor")]
+    fn assert_owner_fails() {
+        set_predecessor("admin.near");
+        Acl.assert_owner();
+    }
+}
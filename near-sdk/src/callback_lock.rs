@@ -0,0 +1,126 @@
+use crate::{env, Timestamp};
+
+/// Prefix of the storage keys of the locks.
+const LOCK_PREFIX: &[u8] = b"__near_lock:";
+
+/// A named lock held in storage from a method which schedules a cross-contract call until its
+/// callback, so that other methods using the same lock fail fast instead of acting on the state
+/// the callback is about to change. It is usually taken with the
+/// [`#[lock("...")]`](crate::near#lock-annotates-methods-of-a-type-in-its-impl-block)
+/// attribute.
+///
+/// The lock is stored with the state of the contract, so it is not taken if the method panics,
+/// but it stays held if the callback panics or runs out of gas. In that case it can be released
+/// with [`release`](Self::release), for example by a method only the owner can call.
+///
+/// # Examples
+/// ```
+/// use near_sdk::CallbackLock;
+///
+/// let lock = CallbackLock::new("loan");
+/// lock.acquire();
+/// assert!(lock.is_held());
+/// assert!(!lock.try_acquire());
+/// assert!(lock.release());
+/// assert!(!lock.is_held());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallbackLock<'a> {
+    name: &'a str,
+}
+
+impl<'a> CallbackLock<'a> {
+    pub const fn new(name: &'a str) -> Self {
+        Self { name }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    fn storage_key(&self) -> Vec<u8> {
+        [LOCK_PREFIX, self.name.as_bytes()].concat()
+    }
+
+    pub fn is_held(&self) -> bool {
+        env::storage_has_key(&self.storage_key())
+    }
+
+    /// Block timestamp at which the lock was acquired, if it is held.
+    pub fn held_since(&self) -> Option<Timestamp> {
+        let value = env::storage_read(&self.storage_key())?;
+        Some(value.try_into().map(Timestamp::from_le_bytes).unwrap_or_else(|_| env::abort()))
+    }
+
+    /// Acquires the lock, returning `false` if it is already held.
+    pub fn try_acquire(&self) -> bool {
+        if self.is_held() {
+            return false;
+        }
+        env::storage_write(&self.storage_key(), &env::block_timestamp().to_le_bytes());
+        true
+    }
+
+    /// Acquires the lock, panicking if it is already held.
+    pub fn acquire(&self) {
+        if !self.try_acquire() {
+            env::panic_str(&format!("Lock {} is held", self.name));
+        }
+    }
+
+    /// Panics if the lock is held, without acquiring it.
+    pub fn assert_not_held(&self) {
+        if self.is_held() {
+            env::panic_str(&format!("Lock {} is held", self.name));
+        }
+    }
+
+    /// Releases the lock, returning `false` if it wasn't held.
+    pub fn release(&self) -> bool {
+        env::storage_remove(&self.storage_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::VMContextBuilder;
+    use crate::testing_env;
+
+    #[test]
+    fn acquire_and_release() {
+        testing_env!(VMContextBuilder::new().block_timestamp(42).build());
+        let loan = CallbackLock::new("loan");
+        let other = CallbackLock::new("other");
+        assert_eq!(loan.held_since(), None);
+        loan.acquire();
+        assert_eq!(loan.held_since(), Some(42));
+        assert!(!other.is_held());
+        assert!(other.try_acquire());
+
+        assert!(loan.release());
+        assert!(!loan.release());
+        assert!(other.is_held());
+    }
+
+    #[test]
+    #[should_panic(expected = "Lock loan is held")]
+    fn acquire_held() {
+        CallbackLock::new("loan").acquire();
+        CallbackLock::new("loan").acquire();
+    }
+
+    #[test]
+    fn assert_not_held_does_not_acquire() {
+        let loan = CallbackLock::new("loan");
+        loan.assert_not_held();
+        assert!(!loan.is_held());
+    }
+
+    #[test]
+    #[should_panic(expected = "Lock loan is held")]
+    fn assert_not_held_fails() {
+        CallbackLock::new("loan").acquire();
+        CallbackLock::new("loan").assert_not_held();
+    }
+}
@@ -0,0 +1,524 @@
+//! BLS12-381 points, hashing to the curve and BLS signatures.
+//!
+//! Points are stored in the uncompressed encoding used by the `bls12381_*` host functions of
+//! [`env`](crate::env): big-endian field elements, `x` before `y`, the `c1` component of an `Fp2`
+//! element before `c0`, and the flags of the [ZCash serialization] in the top bits of the first
+//! byte.
+//! Compressed points, as found in Ethereum, are converted with [`G1Point::decompress`] and
+//! [`G2Point::decompress`].
+//!
+//! Signatures follow the ciphersuite of Ethereum consensus, [`DST`]: public keys are points of G1
+//! and signatures points of G2. Public keys are expected to come with a proof of possession, e.g.
+//! to be registered validator keys, so aggregation does not protect against rogue keys.
+//!
+//! # Example
+//! ```
+//! use near_sdk::crypto::bls12381::{self, G1Point, G2Point};
+//! # fn from_hex<const N: usize>(hex: &str) -> [u8; N] {
+//! #     let mut bytes = [0; N];
+//! #     for (byte, i) in bytes.iter_mut().zip((0..hex.len()).step_by(2)) {
+//! #         *byte = u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+//! #     }
+//! #     bytes
+//! # }
+//!
+//! let public_key = G1Point::decompress(&from_hex(
+//!     "95a254501b7733239ed3cec4d56737977bd09ede881d8a234560e83e5525017add3b1dcc3eabfb85e12a4131b19c253b",
+//! ))
+//! .unwrap();
+//! let signature = G2Point::decompress(&from_hex(
+//!     "9806f18bee3c0a19db1e7b48d29db4a4a0aa160f1f9aecd0e005255fc704e9c9774b6456288767b25541d995414d7e29\
+//!      088ce71a708a0dec3fe5f3ef51945dab2cdaca4843e6cfa15aba5660336247fb3ab02716b76db44aad7c7de6e5d4b72e",
+//! ))
+//! .unwrap();
+//! assert!(bls12381::verify(&public_key, b"sync committee root", &signature));
+//! ```
+//!
+//! [ZCash serialization]: https://github.com/zkcrypto/pairing/blob/main/src/bls12_381/README.md#serialization
+
+use std::ops::{Add, Mul, Neg};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::env;
+
+/// Domain separation tag of the `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite, used
+/// by Ethereum consensus.
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+const FP_LEN: usize = 48;
+const G1_LEN: usize = 96;
+const G2_LEN: usize = 192;
+
+/// Modulus of the base field, as little-endian limbs.
+const P: [u64; 6] = [
+    0xb9fe_ffff_ffff_aaab,
+    0x1eab_fffe_b153_ffff,
+    0x6730_d2a0_f6b0_f624,
+    0x6477_4b84_f385_12bf,
+    0x4b1b_a7b6_434b_acd7,
+    0x1a01_11ea_397f_e69a,
+];
+
+const fn from_hex<const N: usize>(hex: &str) -> [u8; N] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex"),
+        }
+    }
+    let hex = hex.as_bytes();
+    let mut bytes = [0; N];
+    let mut i = 0;
+    while i < N {
+        bytes[i] = nibble(hex[2 * i]) << 4 | nibble(hex[2 * i + 1]);
+        i += 1;
+    }
+    bytes
+}
+
+macro_rules! impl_point {
+    (
+        $ty:ident,
+        $len:expr,
+        $compressed_len:expr,
+        $sum:ident,
+        $multiexp:ident,
+        $decompress:ident,
+        $name:literal
+    ) => {
+        impl $ty {
+            /// The point at infinity, i.e. the identity of the group.
+            pub const INFINITY: Self = {
+                let mut bytes = [0; $len];
+                bytes[0] = 0x40;
+                Self(bytes)
+            };
+
+            /// Decompresses a point from its compressed encoding. Returns `None` if the bytes do
+            /// not encode a point on the curve.
+            pub fn decompress(compressed: &[u8; $compressed_len]) -> Option<Self> {
+                env::$decompress(compressed).map(|point| Self::from_vec(&point))
+            }
+
+            /// Decompresses several points with a single host function call. Returns `None` if any
+            /// of them does not encode a point on the curve.
+            pub fn decompress_all(compressed: &[[u8; $compressed_len]]) -> Option<Vec<Self>> {
+                let points = env::$decompress(compressed.concat().as_slice())?;
+                Some(points.chunks_exact($len).map(Self::from_vec).collect())
+            }
+
+            /// Whether this is the point at infinity.
+            pub fn is_infinity(&self) -> bool {
+                self.0[0] & 0x40 != 0
+            }
+
+            /// Bytes of the uncompressed encoding.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            /// Computes `sum(scalar * point)` over all pairs with a single host function call.
+            ///
+            /// # Panics
+            ///
+            /// If any of the points is invalid or not in the prime order subgroup.
+            pub fn multiexp<'a>(pairs: impl IntoIterator<Item = (&'a Self, &'a Scalar)>) -> Self {
+                let mut input = Vec::new();
+                for (point, scalar) in pairs {
+                    input.extend_from_slice(&point.0);
+                    input.extend_from_slice(&scalar.0);
+                }
+                Self::from_vec(
+                    &env::$multiexp(&input)
+                        .unwrap_or_else(|| env::panic_str(concat!("Invalid ", $name, " point"))),
+                )
+            }
+
+            /// Sums the points, negating the ones paired with `true`. Returns `None` if any of the
+            /// points is invalid.
+            fn try_sum<'a>(points: impl IntoIterator<Item = (&'a Self, bool)>) -> Option<Self> {
+                let mut input = Vec::new();
+                for (point, negate) in points {
+                    input.push(negate as u8);
+                    input.extend_from_slice(&point.0);
+                }
+                env::$sum(&input).map(|sum| Self::from_vec(&sum))
+            }
+
+            fn sum_or_panic<'a>(points: impl IntoIterator<Item = (&'a Self, bool)>) -> Self {
+                Self::try_sum(points)
+                    .unwrap_or_else(|| env::panic_str(concat!("Invalid ", $name, " point")))
+            }
+
+            fn from_vec(bytes: &[u8]) -> Self {
+                Self(bytes.try_into().unwrap_or_else(|_| env::abort()))
+            }
+        }
+
+        impl From<[u8; $len]> for $ty {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl From<$ty> for [u8; $len] {
+            fn from(point: $ty) -> Self {
+                point.0
+            }
+        }
+
+        /// Adds points with a host function call. Panics if either point is invalid.
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self::sum_or_panic([(&self, false), (&rhs, false)])
+            }
+        }
+
+        /// Negates the point with a host function call. Panics if it is invalid.
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self::sum_or_panic([(&self, true)])
+            }
+        }
+
+        /// Multiplies the point by the scalar with a host function call. Panics if the point is
+        /// invalid or not in the prime order subgroup.
+        impl Mul<Scalar> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: Scalar) -> Self {
+                Self::multiexp([(&self, &rhs)])
+            }
+        }
+
+        /// Sums the points with a single host function call. Panics if any point is invalid.
+        impl<'a> std::iter::Sum<&'a $ty> for $ty {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                Self::sum_or_panic(iter.map(|point| (point, false)))
+            }
+        }
+    };
+}
+
+/// Point of the G1 group, in uncompressed encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct G1Point(pub [u8; 96]);
+
+impl G1Point {
+    /// The standard generator of G1.
+    pub const GENERATOR: Self = Self(from_hex(
+        "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+         08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1",
+    ));
+
+    /// Negation of [`Self::GENERATOR`].
+    const NEG_GENERATOR: Self = Self(from_hex(
+        "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+         114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca",
+    ));
+}
+
+impl_point!(
+    G1Point,
+    96,
+    48,
+    try_bls12381_p1_sum,
+    try_bls12381_g1_multiexp,
+    try_bls12381_p1_decompress,
+    "G1"
+);
+
+/// Point of the G2 group, in uncompressed encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct G2Point(pub [u8; 192]);
+
+impl G2Point {
+    /// The standard generator of G2.
+    pub const GENERATOR: Self = Self(from_hex(
+        "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+         024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8\
+         0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be\
+         0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+    ));
+}
+
+impl_point!(
+    G2Point,
+    192,
+    96,
+    try_bls12381_p2_sum,
+    try_bls12381_g2_multiexp,
+    try_bls12381_p2_decompress,
+    "G2"
+);
+
+/// Scalar to multiply points by, as 32 little-endian bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct Scalar(pub [u8; 32]);
+
+impl Scalar {
+    /// Scalar from its little-endian encoding.
+    pub const fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Scalar from its big-endian encoding, as used by Ethereum.
+    pub fn from_be_bytes(mut bytes: [u8; 32]) -> Self {
+        bytes.reverse();
+        Self(bytes)
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        let mut bytes = [0; 32];
+        bytes[..8].copy_from_slice(&value.to_le_bytes());
+        Self(bytes)
+    }
+}
+
+/// Checks that the product of the pairings of all pairs is the identity. Returns `false` if any
+/// of the points is invalid or not in the prime order subgroup.
+pub fn pairing_check<'a>(pairs: impl IntoIterator<Item = (&'a G1Point, &'a G2Point)>) -> bool {
+    let mut input = Vec::new();
+    for (g1, g2) in pairs {
+        input.extend_from_slice(&g1.0);
+        input.extend_from_slice(&g2.0);
+    }
+    env::bls12381_pairing_check(&input)
+}
+
+/// Hashes `message` to a point of G1 with the `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite of
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html), using the domain separation tag `dst`.
+pub fn hash_to_g1(message: &[u8], dst: &[u8]) -> G1Point {
+    let uniform = expand_message_xmd(message, dst, 2 * 64);
+    let mut input = Vec::with_capacity(2 * FP_LEN);
+    for chunk in uniform.chunks_exact(64) {
+        input.extend_from_slice(&reduce(chunk));
+    }
+    // The host function clears the cofactor of each mapped point, which commutes with their sum.
+    let points = env::try_bls12381_map_fp_to_g1(&input)
+        .unwrap_or_else(|| env::panic_str("Field element out of range"));
+    let (u0, u1) = points.split_at(G1_LEN);
+    G1Point::from_vec(u0) + G1Point::from_vec(u1)
+}
+
+/// Hashes `message` to a point of G2 with the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html), using the domain separation tag `dst`.
+pub fn hash_to_g2(message: &[u8], dst: &[u8]) -> G2Point {
+    let uniform = expand_message_xmd(message, dst, 4 * 64);
+    let mut input = Vec::with_capacity(4 * FP_LEN);
+    for element in uniform.chunks_exact(2 * 64) {
+        let (c0, c1) = element.split_at(64);
+        input.extend_from_slice(&reduce(c1));
+        input.extend_from_slice(&reduce(c0));
+    }
+    let points = env::try_bls12381_map_fp2_to_g2(&input)
+        .unwrap_or_else(|| env::panic_str("Field element out of range"));
+    let (u0, u1) = points.split_at(G2_LEN);
+    G2Point::from_vec(u0) + G2Point::from_vec(u1)
+}
+
+/// Verifies the signature of `message` by `public_key`.
+pub fn verify(public_key: &G1Point, message: &[u8], signature: &G2Point) -> bool {
+    !public_key.is_infinity()
+        && pairing_check([
+            (public_key, &hash_to_g2(message, DST)),
+            (&G1Point::NEG_GENERATOR, signature),
+        ])
+}
+
+/// Verifies an aggregate signature of a different message by each public key.
+pub fn aggregate_verify(public_keys: &[G1Point], messages: &[&[u8]], signature: &G2Point) -> bool {
+    if public_keys.is_empty()
+        || public_keys.len() != messages.len()
+        || public_keys.iter().any(G1Point::is_infinity)
+    {
+        return false;
+    }
+    let hashes: Vec<G2Point> = messages.iter().map(|message| hash_to_g2(message, DST)).collect();
+    pairing_check(public_keys.iter().zip(&hashes).chain([(&G1Point::NEG_GENERATOR, signature)]))
+}
+
+/// Verifies an aggregate signature of the same message by all public keys, e.g. by the members of
+/// a sync committee.
+pub fn fast_aggregate_verify(public_keys: &[G1Point], message: &[u8], signature: &G2Point) -> bool {
+    if public_keys.is_empty() || public_keys.iter().any(G1Point::is_infinity) {
+        return false;
+    }
+    match G1Point::try_sum(public_keys.iter().map(|public_key| (public_key, false))) {
+        Some(aggregate) => verify(&aggregate, message, signature),
+        None => false,
+    }
+}
+
+/// `expand_message_xmd` of RFC 9380 with SHA-256.
+fn expand_message_xmd(message: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let oversize_dst;
+    let dst = if dst.len() > 255 {
+        oversize_dst = env::sha256_array(&[b"H2C-OVERSIZE-DST-", dst].concat());
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let b0 = env::sha256_array(
+        &[&[0; 64], message, &(len as u16).to_be_bytes(), &[0], &dst_prime].concat(),
+    );
+    let mut b = env::sha256_array(&[&b0[..], &[1], &dst_prime].concat());
+    let mut uniform = b.to_vec();
+    for i in 2..=len.div_ceil(32) {
+        let mut xor = b0;
+        xor.iter_mut().zip(&b).for_each(|(x, b)| *x ^= b);
+        b = env::sha256_array(&[&xor[..], &[i as u8], &dst_prime].concat());
+        uniform.extend_from_slice(&b);
+    }
+    uniform.truncate(len);
+    uniform
+}
+
+/// Reduces a big-endian integer modulo the base field modulus.
+fn reduce(bytes: &[u8]) -> [u8; FP_LEN] {
+    let mut r = [0u64; 6];
+    for bit in bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1)) {
+        // r < p < 2^381, so doubling it does not overflow and at most one subtraction is needed.
+        let mut carry = bit as u64;
+        for limb in r.iter_mut() {
+            let next = *limb >> 63;
+            *limb = *limb << 1 | carry;
+            carry = next;
+        }
+        if r.iter().rev().cmp(P.iter().rev()).is_ge() {
+            let mut borrow = false;
+            for (limb, p) in r.iter_mut().zip(P) {
+                let (diff, b1) = limb.overflowing_sub(p);
+                let (diff, b2) = diff.overflowing_sub(borrow as u64);
+                *limb = diff;
+                borrow = b1 || b2;
+            }
+        }
+    }
+    let mut out = [0; FP_LEN];
+    for (chunk, limb) in out.chunks_exact_mut(8).zip(r.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEYS: [&str; 3] = [
+        "95a254501b7733239ed3cec4d56737977bd09ede881d8a234560e83e5525017add3b1dcc3eabfb85e12a4131b19c253b",
+        "ac80a5e08c712d5f08f0306ad743f7d8c215d982489b84a1d6ba805733d94c006e8938f9089a75db3ffa135af33bc69a",
+        "96df714a5cc9ddd2298546dce3d6d3827762a6d5b1c2a91e5ca93c9c898b1b4319cc105c493212a55b63080732ec2249",
+    ];
+    const MESSAGE: &[u8] = b"sync committee root";
+
+    fn public_keys() -> Vec<G1Point> {
+        let compressed: Vec<[u8; 48]> = PUBLIC_KEYS.iter().map(|key| from_hex(key)).collect();
+        G1Point::decompress_all(&compressed).unwrap()
+    }
+
+    fn signature(hex: &str) -> G2Point {
+        G2Point::decompress(&from_hex(hex)).unwrap()
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        let mut compressed = from_hex::<48>("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        compressed[0] |= 0x80;
+        assert_eq!(G1Point::decompress(&compressed), Some(G1Point::GENERATOR));
+        assert_eq!(-G1Point::GENERATOR, G1Point::NEG_GENERATOR);
+        assert_eq!(G1Point::GENERATOR + G1Point::NEG_GENERATOR, G1Point::INFINITY);
+
+        let five = G1Point(from_hex(
+            "10e7791fb972fe014159aa33a98622da3cdc98ff707965e536d8636b5fcc5ac7a91a8c46e59a00dca575af0f18fb13dc\
+             16ba437edcc6551e30c10512367494bfb6b01cc6681e8a4c3cd2501832ab5c4abc40b4578b85cbaffbf0bcd70d67c6e2",
+        ));
+        assert_eq!(G1Point::GENERATOR * Scalar::from(5), five);
+        assert_eq!([G1Point::GENERATOR; 5].iter().sum::<G1Point>(), five);
+        let mut three = [0; 32];
+        three[31] = 3;
+        assert_eq!(
+            G1Point::multiexp([
+                (&G1Point::GENERATOR, &Scalar::from(2)),
+                (&G1Point::GENERATOR, &Scalar::from_be_bytes(three)),
+            ]),
+            five
+        );
+
+        assert!(pairing_check([
+            (&five, &G2Point::GENERATOR),
+            (&G1Point::NEG_GENERATOR, &(G2Point::GENERATOR * Scalar::from(5))),
+        ]));
+        assert!(!pairing_check([(&five, &G2Point::GENERATOR)]));
+        assert!(G1Point::decompress(&[0xff; 48]).is_none());
+    }
+
+    #[test]
+    fn hash_to_curve() {
+        // Test vector of RFC 9380, appendix J.9.1.
+        assert_eq!(
+            hash_to_g1(b"abc", b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_"),
+            G1Point(from_hex(
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903\
+                 0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d"
+            ))
+        );
+        assert_eq!(
+            hash_to_g2(MESSAGE, DST),
+            G2Point(from_hex(
+                "06acd1740787fa689b82924ffd9ba646f9ca4f291d4de866399d375e94354382d9f9af35ad44528f1bb6059579442122\
+                 15e9b22a637e7ff68fa90380b6193dcf21ac2e6f1fa613b2467951155acb4a13980278913fdee83df604bd83b067ea88\
+                 0ecaf83a3e29c9c1ce76c75c86211151a1b657f88c49806a24949aa7e23cbf624faea069540c655f948768e345acaddc\
+                 19bd3678402adce6ece250861f7e82dfc7b6d410877ed493cca5f738667f912ca9f362d82ad2db4edb621ff03483173f"
+            ))
+        );
+    }
+
+    #[test]
+    fn verify_signatures() {
+        let public_keys = public_keys();
+        let signature = signature(
+            "9806f18bee3c0a19db1e7b48d29db4a4a0aa160f1f9aecd0e005255fc704e9c9774b6456288767b25541d995414d7e29\
+             088ce71a708a0dec3fe5f3ef51945dab2cdaca4843e6cfa15aba5660336247fb3ab02716b76db44aad7c7de6e5d4b72e",
+        );
+        assert!(verify(&public_keys[0], MESSAGE, &signature));
+        assert!(!verify(&public_keys[0], b"other message", &signature));
+        assert!(!verify(&public_keys[1], MESSAGE, &signature));
+        assert!(!verify(&G1Point::INFINITY, MESSAGE, &G2Point::INFINITY));
+    }
+
+    #[test]
+    fn verify_aggregate_signatures() {
+        let public_keys = public_keys();
+        let signature = signature(
+            "899b67aee42722d8feaa7da6d164a859bac489c7493f8a2280a685bccf8c3e19042bb06add9039a831bcd04a61fd0501\
+             01e0bee8ba325e37ff57d1429b3a6bb14cb26886358c170286b0ef85edb00146aabf4cd7464247664c1f3dd7976fddeb",
+        );
+        assert!(fast_aggregate_verify(&public_keys, MESSAGE, &signature));
+        assert!(!fast_aggregate_verify(&public_keys[..2], MESSAGE, &signature));
+        assert!(!fast_aggregate_verify(&[], MESSAGE, &signature));
+
+        let signature = super::tests::signature(
+            "955607b0b40f8995cdba05d68a67e632f2a15e31b803d5685d1cb37faf30b5819175393bcd4488a6ccab802d5314ec4b\
+             1288ca93487edb8d6ebb242a3ea16e5dc3e39846f39a1b92b12d3583c326db4d1a3e722501e9616c87be93f33f58c372",
+        );
+        let messages: [&[u8]; 3] = [b"one", b"two", b"three"];
+        assert!(aggregate_verify(&public_keys, &messages, &signature));
+        assert!(!aggregate_verify(&public_keys, &[b"two", b"one", b"three"], &signature));
+        assert!(!aggregate_verify(&public_keys, &messages[..2], &signature));
+    }
+}
@@ -0,0 +1,390 @@
+//! Hashing and verification of messages signed with Ethereum wallets: `personal_sign` messages of
+//! [EIP-191] and typed data of [EIP-712].
+//!
+//! Signatures are the 65 bytes `r || s || v` returned by wallets, as a [`Secp256k1Signature`](crate::Secp256k1Signature). `v`
+//! may be given as `0`/`1` or `27`/`28`, and signatures with `s` in the upper half of the curve
+//! order are rejected, so that a signature cannot be replayed in its malleated form. Recovering
+//! signers uses [`env::ecrecover`](crate::env::ecrecover) and requires the `unstable` feature.
+//!
+//! # Example
+//! ```
+//! # #[cfg(feature = "unstable")] {
+//! use near_sdk::crypto::ethereum::{self, Address};
+//! use near_sdk::Secp256k1Signature;
+//!
+//! let signer: Address = "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826".parse().unwrap();
+//! let signature: Secp256k1Signature =
+//!     "secp256k1:A2HvatmXQofr7cUcJnJufQbtvXqB1UVNZgLmfdvWirrrxfrY6kWpAokZg34D15JTK23tuzwXBCYTSG45vX3qZVCav"
+//!         .parse()
+//!         .unwrap();
+//! assert!(ethereum::verify_personal_message(b"Sign in to example.near", &signature, &signer));
+//! # }
+//! ```
+//!
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use std::fmt;
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk_macros::near;
+use serde::{de, ser, Deserialize};
+
+use crate::env;
+#[cfg(feature = "unstable")]
+use crate::Secp256k1Signature;
+
+/// 20-byte Ethereum address. Serialized to JSON as a `0x`-prefixed lowercase hex string.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, BorshSerialize, BorshDeserialize,
+)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    /// Address of an uncompressed secp256k1 public key, without its `0x04` prefix: the last 20
+    /// bytes of the keccak256 hash of the key.
+    pub fn from_public_key(public_key: &[u8; 64]) -> Self {
+        let hash = env::keccak256_array(public_key);
+        let mut address = [0; 20];
+        address.copy_from_slice(&hash[12..]);
+        Self(address)
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(address: [u8; 20]) -> Self {
+        Self(address)
+    }
+}
+
+impl From<Address> for [u8; 20] {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl FromStr for Address {
+    type Err = ParseAddressError;
+
+    /// Parses a `0x`-prefixed hex address. The case of the letters, i.e. the EIP-55 checksum, is
+    /// not checked.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let hex = value.strip_prefix("0x").ok_or(ParseAddressError { _private: () })?;
+        if hex.len() != 40 || !hex.is_ascii() {
+            return Err(ParseAddressError { _private: () });
+        }
+        let mut address = [0; 20];
+        for (byte, i) in address.iter_mut().zip((0..40).step_by(2)) {
+            *byte = u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| ParseAddressError { _private: () })?;
+        }
+        Ok(Self(address))
+    }
+}
+
+impl ser::Serialize for Address {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Address {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl schemars::JsonSchema for Address {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// Error when parsing an [`Address`] from a string.
+#[derive(Debug)]
+pub struct ParseAddressError {
+    _private: (),
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a 0x-prefixed address of 40 hex digits")
+    }
+}
+
+impl std::error::Error for ParseAddressError {}
+
+/// The `EIP712Domain` of typed data. Fields which are `None` are left out of the domain type.
+#[near(inside_nearsdk, serializers=[borsh, json])]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip712Domain {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<u64>,
+    pub verifying_contract: Option<Address>,
+    pub salt: Option<[u8; 32]>,
+}
+
+impl Eip712Domain {
+    /// The domain separator, i.e. the hash of the domain as an EIP-712 struct.
+    pub fn separator(&self) -> [u8; 32] {
+        let mut fields = Vec::new();
+        let mut values = Vec::new();
+        if let Some(name) = &self.name {
+            fields.push("string name");
+            values.push(Eip712Value::String(name));
+        }
+        if let Some(version) = &self.version {
+            fields.push("string version");
+            values.push(Eip712Value::String(version));
+        }
+        if let Some(chain_id) = self.chain_id {
+            fields.push("uint256 chainId");
+            values.push(Eip712Value::Uint(chain_id.into()));
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            fields.push("address verifyingContract");
+            values.push(Eip712Value::Address(verifying_contract));
+        }
+        if let Some(salt) = self.salt {
+            fields.push("bytes32 salt");
+            values.push(Eip712Value::Bytes32(salt));
+        }
+        hash_struct(&format!("EIP712Domain({})", fields.join(",")), &values)
+    }
+}
+
+/// Value of a member of an EIP-712 struct, encoded to 32 bytes by [`hash_struct`].
+///
+/// Members which are structs are given by their [`hash_struct`] and arrays by the keccak256 hash
+/// of the concatenated encoding of their elements, both as [`Eip712Value::Bytes32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eip712Value<'a> {
+    Address(Address),
+    Bool(bool),
+    /// Unsigned integer of up to 128 bits.
+    Uint(u128),
+    /// Signed integer of up to 128 bits.
+    Int(i128),
+    /// Unsigned integer of up to 256 bits, big-endian.
+    Uint256([u8; 32]),
+    /// `bytes32`, or the hash of a struct or array.
+    Bytes32([u8; 32]),
+    String(&'a str),
+    Bytes(&'a [u8]),
+}
+
+impl Eip712Value<'_> {
+    /// The 32-byte encoding of the value.
+    pub fn encode(&self) -> [u8; 32] {
+        let mut encoded = [0; 32];
+        match *self {
+            Self::Address(address) => encoded[12..].copy_from_slice(&address.0),
+            Self::Bool(value) => encoded[31] = value as u8,
+            Self::Uint(value) => encoded[16..].copy_from_slice(&value.to_be_bytes()),
+            Self::Int(value) => {
+                // Sign extension to 256 bits.
+                encoded = [if value < 0 { 0xff } else { 0 }; 32];
+                encoded[16..].copy_from_slice(&value.to_be_bytes());
+            }
+            Self::Uint256(value) | Self::Bytes32(value) => encoded = value,
+            Self::String(value) => encoded = env::keccak256_array(value.as_bytes()),
+            Self::Bytes(value) => encoded = env::keccak256_array(value),
+        }
+        encoded
+    }
+}
+
+/// `hashStruct` of EIP-712: the hash of the type hash of `encoded_type` followed by the encoded
+/// members. `encoded_type` is e.g. `Mail(Person from,Person to,string contents)Person(string
+/// name,address wallet)`, with the referenced struct types appended in alphabetical order.
+pub fn hash_struct(encoded_type: &str, values: &[Eip712Value]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(32 * (1 + values.len()));
+    data.extend_from_slice(&env::keccak256_array(encoded_type.as_bytes()));
+    for value in values {
+        data.extend_from_slice(&value.encode());
+    }
+    env::keccak256_array(&data)
+}
+
+/// Hash of typed data signed with `eth_signTypedData`, from the [`Eip712Domain::separator`] and
+/// the [`hash_struct`] of the message.
+pub fn hash_typed_data(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    env::keccak256_array(&[&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat())
+}
+
+/// Hash of a message signed with `personal_sign`: the message prefixed with
+/// `"\x19Ethereum Signed Message:\n"` and its length in decimal.
+pub fn hash_personal_message(message: &[u8]) -> [u8; 32] {
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    env::keccak256_array(&[prefix.as_bytes(), message].concat())
+}
+
+/// Recovers the address which signed `hash`. Returns `None` if the signature is invalid or
+/// malleable.
+#[cfg(feature = "unstable")]
+pub fn recover(hash: &[u8; 32], signature: &Secp256k1Signature) -> Option<Address> {
+    let v = signature.recovery_id();
+    if v > 1 {
+        return None;
+    }
+    env::ecrecover(hash, &signature.0[..64], v, true)
+        .map(|public_key| Address::from_public_key(&public_key))
+}
+
+/// Verifies that `hash` was signed by `signer`.
+#[cfg(feature = "unstable")]
+pub fn verify(hash: &[u8; 32], signature: &Secp256k1Signature, signer: &Address) -> bool {
+    recover(hash, signature).as_ref() == Some(signer)
+}
+
+/// Verifies that `message` was signed by `signer` with `personal_sign`.
+#[cfg(feature = "unstable")]
+pub fn verify_personal_message(
+    message: &[u8],
+    signature: &Secp256k1Signature,
+    signer: &Address,
+) -> bool {
+    verify(&hash_personal_message(message), signature, signer)
+}
+
+/// Verifies that the typed data was signed by `signer` with `eth_signTypedData`.
+#[cfg(feature = "unstable")]
+pub fn verify_typed_data(
+    domain_separator: &[u8; 32],
+    struct_hash: &[u8; 32],
+    signature: &Secp256k1Signature,
+    signer: &Address,
+) -> bool {
+    verify(&hash_typed_data(domain_separator, struct_hash), signature, signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    /// The `Mail` example of EIP-712, signed by `COW`.
+    fn mail() -> ([u8; 32], [u8; 32]) {
+        let domain = Eip712Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(1),
+            verifying_contract: Some("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".parse().unwrap()),
+            salt: None,
+        };
+        const PERSON: &str = "Person(string name,address wallet)";
+        let from = hash_struct(
+            PERSON,
+            &[Eip712Value::String("Cow"), Eip712Value::Address(COW.parse().unwrap())],
+        );
+        let to = hash_struct(
+            PERSON,
+            &[
+                Eip712Value::String("Bob"),
+                Eip712Value::Address("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB".parse().unwrap()),
+            ],
+        );
+        let mail = hash_struct(
+            &["Mail(Person from,Person to,string contents)", PERSON].concat(),
+            &[
+                Eip712Value::Bytes32(from),
+                Eip712Value::Bytes32(to),
+                Eip712Value::String("Hello, Bob!"),
+            ],
+        );
+        (domain.separator(), mail)
+    }
+
+    const COW: &str = "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826";
+
+    #[test]
+    fn typed_data_hash() {
+        let (domain_separator, mail) = mail();
+        assert_eq!(
+            domain_separator,
+            bytes("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+        assert_eq!(mail, bytes("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"));
+        assert_eq!(
+            hash_typed_data(&domain_separator, &mail),
+            bytes("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+
+        assert_eq!(Eip712Value::Int(-1).encode(), [0xff; 32]);
+        assert_eq!(Eip712Value::Bool(true).encode()[31], 1);
+    }
+
+    #[test]
+    fn personal_message_hash() {
+        assert_eq!(
+            hash_personal_message(b"Sign in to example.near"),
+            bytes("b0a1b583cd4960b171ad6df7909cfcfbc9befb64a5fd2627964d21e8b547b512")
+        );
+    }
+
+    #[test]
+    fn address_str() {
+        let address: Address = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826".parse().unwrap();
+        assert_eq!(address.to_string(), COW);
+        assert_eq!(serde_json::to_string(&address).unwrap(), format!("\"{}\"", COW));
+        assert!("cd2a3d9f938e13cd947ec05abc7fe734df8dd826".parse::<Address>().is_err());
+        assert!("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd8".parse::<Address>().is_err());
+        assert!("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd8zz".parse::<Address>().is_err());
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn verify_signatures() {
+        let cow: Address = COW.parse().unwrap();
+
+        let (domain_separator, mail) = mail();
+        let signature = Secp256k1Signature(bytes(
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+        ));
+        assert!(verify_typed_data(&domain_separator, &mail, &signature, &cow));
+        assert!(!verify_typed_data(&mail, &domain_separator, &signature, &cow));
+
+        let message = b"Sign in to example.near";
+        let mut signature = Secp256k1Signature(bytes(
+            "663d7c126d524f4da93bc07c1c151f18c27676acd655a3f86372dbf54767244b\
+             5298ef5a7432493ca93528632a4e2f6f58190ee0773f5c4787ddfbef088a4d7d1b",
+        ));
+        assert_eq!(recover(&hash_personal_message(message), &signature), Some(cow));
+        signature.0[64] = 0;
+        assert!(verify_personal_message(message, &signature, &cow));
+        signature.0[64] = 2;
+        assert!(!verify_personal_message(message, &signature, &cow));
+
+        // The same signature with `s` negated and `v` flipped is rejected as malleable.
+        signature.0[32..64].copy_from_slice(&bytes::<32>(
+            "ad6710a58bcdb6c356cad79cd5b1d08f6295ce06380943f437f4629dc7abf3c4",
+        ));
+        signature.0[64] = 1;
+        assert_eq!(recover(&hash_personal_message(message), &signature), None);
+    }
+}
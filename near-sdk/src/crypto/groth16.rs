@@ -0,0 +1,521 @@
+//! Verification of [Groth16] zk-SNARK proofs over the BN254 (`alt_bn128`) curve, using the
+//! `alt_bn128_*` host functions of [`env`](crate::env).
+//!
+//! [`VerifyingKey`], [`Proof`] and the public inputs deserialize from the JSON files written by
+//! [snarkjs] (`verification_key.json`, `proof.json` and `public.json`), with coordinates as
+//! decimal or `0x`-prefixed hex strings. The fields can also be given under the names used by
+//! [arkworks], e.g. `alpha_g1` instead of `vk_alpha_1`. With Borsh, points are stored in the
+//! encoding of the host functions, which is the uncompressed serialization of affine points of
+//! arkworks: little-endian coordinates, `x` before `y`, and `c0` before `c1` for G2.
+//!
+//! # Example
+//! ```
+//! use near_sdk::crypto::groth16::{Fr, Proof, VerifyingKey};
+//! use near_sdk::{near, require};
+//!
+//! #[near(contract_state)]
+//! pub struct Pool {
+//!     verifying_key: VerifyingKey,
+//! }
+//!
+//! #[near]
+//! impl Pool {
+//!     #[init]
+//!     pub fn new(verifying_key: VerifyingKey) -> Self {
+//!         Self { verifying_key }
+//!     }
+//!
+//!     pub fn withdraw(&mut self, proof: Proof, public_inputs: Vec<Fr>) {
+//!         require!(self.verifying_key.verify(&proof, &public_inputs), "Invalid proof");
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! [Groth16]: https://eprint.iacr.org/2016/260
+//! [snarkjs]: https://github.com/iden3/snarkjs
+//! [arkworks]: https://github.com/arkworks-rs/groth16
+
+use std::fmt;
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk_macros::near;
+use serde::{de, ser, Deserialize, Serialize};
+
+use crate::env;
+
+/// 256-bit integer as little-endian limbs.
+type U256 = [u64; 4];
+
+/// Modulus of the base field.
+const Q: U256 =
+    [0x3c20_8c16_d87c_fd47, 0x9781_6a91_6871_ca8d, 0xb850_45b6_8181_585d, 0x3064_4e72_e131_a029];
+
+/// Modulus of the scalar field, i.e. the order of the groups.
+const R: U256 =
+    [0x43e1_f593_f000_0001, 0x2833_e848_79b9_7091, 0xb850_45b6_8181_585d, 0x3064_4e72_e131_a029];
+
+/// Point of the G1 group: the little-endian `x` and `y` coordinates of the affine point, or zeros
+/// for the point at infinity.
+#[near(inside_nearsdk)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct G1Point(pub [u8; 64]);
+
+/// Point of the G2 group: the little-endian `x.c0`, `x.c1`, `y.c0` and `y.c1` coordinates of the
+/// affine point, or zeros for the point at infinity.
+#[near(inside_nearsdk)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct G2Point(pub [u8; 128]);
+
+/// Element of the scalar field, used for public inputs. Serialized to JSON as a decimal string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct Fr([u8; 32]);
+
+/// Verifying key of a circuit.
+#[near(inside_nearsdk, serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    #[serde(rename = "vk_alpha_1", alias = "alpha_g1")]
+    pub alpha_g1: G1Point,
+    #[serde(rename = "vk_beta_2", alias = "beta_g2")]
+    pub beta_g2: G2Point,
+    #[serde(rename = "vk_gamma_2", alias = "gamma_g2")]
+    pub gamma_g2: G2Point,
+    #[serde(rename = "vk_delta_2", alias = "delta_g2")]
+    pub delta_g2: G2Point,
+    /// Points the public inputs are multiplied by, starting with the one for the constant `1`.
+    #[serde(rename = "IC", alias = "gamma_abc_g1")]
+    pub gamma_abc_g1: Vec<G1Point>,
+}
+
+/// Groth16 proof.
+#[near(inside_nearsdk, serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proof {
+    #[serde(rename = "pi_a", alias = "a")]
+    pub a: G1Point,
+    #[serde(rename = "pi_b", alias = "b")]
+    pub b: G2Point,
+    #[serde(rename = "pi_c", alias = "c")]
+    pub c: G1Point,
+}
+
+impl VerifyingKey {
+    /// Number of public inputs of the circuit.
+    pub fn num_public_inputs(&self) -> usize {
+        self.gamma_abc_g1.len().saturating_sub(1)
+    }
+
+    /// Verifies `proof` for the given public inputs. Returns `false` if the number of inputs does
+    /// not match the verifying key.
+    ///
+    /// # Panics
+    ///
+    /// If any of the points of the key or proof is not on the curve or, for G2, not in the prime
+    /// order subgroup, which the host functions reject.
+    pub fn verify(&self, proof: &Proof, public_inputs: &[Fr]) -> bool {
+        if public_inputs.len() != self.num_public_inputs() {
+            return false;
+        }
+
+        // vk_x = IC[0] + sum(public_inputs[i] * IC[i + 1])
+        let mut multiexp = Vec::with_capacity(self.gamma_abc_g1.len() * (64 + 32));
+        for (point, scalar) in self.gamma_abc_g1.iter().zip([Fr::ONE].iter().chain(public_inputs)) {
+            multiexp.extend_from_slice(&point.0);
+            multiexp.extend_from_slice(&scalar.0);
+        }
+        let vk_x = env::alt_bn128_g1_multiexp(&multiexp);
+        let neg_a = env::alt_bn128_g1_sum(&[&[1], &proof.a.0[..]].concat());
+
+        // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+        let pairing = [
+            &neg_a[..],
+            &proof.b.0,
+            &self.alpha_g1.0,
+            &self.beta_g2.0,
+            &vk_x,
+            &self.gamma_g2.0,
+            &proof.c.0,
+            &self.delta_g2.0,
+        ]
+        .concat();
+        env::alt_bn128_pairing_check(&pairing)
+    }
+}
+
+impl Fr {
+    /// The scalar `1`.
+    pub const ONE: Self = {
+        let mut bytes = [0; 32];
+        bytes[0] = 1;
+        Self(bytes)
+    };
+
+    /// Scalar from its little-endian encoding. Returns `None` if it is not smaller than the
+    /// order of the field.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Option<Self> {
+        lt(&u256_from_le_bytes(&bytes), &R).then_some(Self(bytes))
+    }
+
+    /// Little-endian encoding of the scalar.
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl From<u64> for Fr {
+    fn from(value: u64) -> Self {
+        Self(u256_to_le_bytes(&[value, 0, 0, 0]))
+    }
+}
+
+impl FromStr for Fr {
+    type Err = ParseFieldError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_element(value, &R).map(Self)
+    }
+}
+
+impl fmt::Display for Fr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_decimal(u256_from_le_bytes(&self.0)))
+    }
+}
+
+impl BorshDeserialize for Fr {
+    fn deserialize_reader<Rd: std::io::Read>(reader: &mut Rd) -> std::io::Result<Self> {
+        Self::from_le_bytes(<[u8; 32]>::deserialize_reader(reader)?).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, ParseFieldError::out_of_range())
+        })
+    }
+}
+
+impl Serialize for Fr {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fr {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value: String = Deserialize::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Splits the coordinates of a point as written by snarkjs into `x`, `y` and whether it is the
+/// point at infinity, i.e. `z` is `zero`. `z` is optional and must be `one` otherwise.
+fn split_coordinates<T: PartialEq, E: de::Error>(
+    mut coordinates: Vec<T>,
+    zero: T,
+    one: T,
+) -> Result<(T, T, bool), E> {
+    let z = match coordinates.len() {
+        2 => None,
+        3 => coordinates.pop(),
+        len => return Err(E::invalid_length(len, &"2 or 3 coordinates")),
+    };
+    let infinity = match z {
+        Some(z) if z == zero => true,
+        Some(z) if z != one => {
+            return Err(E::custom("projective coordinates are not supported"));
+        }
+        _ => false,
+    };
+    let y = coordinates.pop().unwrap_or_else(|| env::abort());
+    let x = coordinates.pop().unwrap_or_else(|| env::abort());
+    Ok((x, y, infinity))
+}
+
+impl Serialize for G1Point {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [x, y] = [0, 1].map(|i| element_to_decimal(&self.0[i * 32..][..32]));
+        let z = if self.0 == [0; 64] { "0" } else { "1" };
+        Serialize::serialize(&[x, y, z.to_string()], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for G1Point {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let coordinates: Vec<String> = Deserialize::deserialize(deserializer)?;
+        let (x, y, infinity) = split_coordinates(coordinates, "0".to_string(), "1".to_string())?;
+        let mut point = [0; 64];
+        if !infinity {
+            for (chunk, element) in point.chunks_exact_mut(32).zip([x, y]) {
+                chunk.copy_from_slice(&parse_element(&element, &Q).map_err(de::Error::custom)?);
+            }
+        }
+        Ok(Self(point))
+    }
+}
+
+impl Serialize for G2Point {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [x0, x1, y0, y1] = [0, 1, 2, 3].map(|i| element_to_decimal(&self.0[i * 32..][..32]));
+        let z = if self.0 == [0; 128] { "0" } else { "1" };
+        Serialize::serialize(&[[x0, x1], [y0, y1], [z.to_string(), "0".to_string()]], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for G2Point {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let coordinates: Vec<[String; 2]> = Deserialize::deserialize(deserializer)?;
+        let zero = ["0".to_string(), "0".to_string()];
+        let one = ["1".to_string(), "0".to_string()];
+        let (x, y, infinity) = split_coordinates(coordinates, zero, one)?;
+        let mut point = [0; 128];
+        if !infinity {
+            for (chunk, element) in point.chunks_exact_mut(32).zip(x.iter().chain(&y)) {
+                chunk.copy_from_slice(&parse_element(element, &Q).map_err(de::Error::custom)?);
+            }
+        }
+        Ok(Self(point))
+    }
+}
+
+#[cfg(feature = "abi")]
+mod schema {
+    use super::{Fr, G1Point, G2Point};
+    use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+
+    macro_rules! impl_json_schema {
+        ($($ty:ty => $repr:ty),*) => {
+            $(
+                impl JsonSchema for $ty {
+                    fn is_referenceable() -> bool {
+                        false
+                    }
+
+                    fn schema_name() -> String {
+                        <$repr>::schema_name()
+                    }
+
+                    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                        <$repr>::json_schema(gen)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_json_schema!(Fr => String, G1Point => Vec<String>, G2Point => Vec<Vec<String>>);
+}
+
+/// Error when parsing a field element from a string.
+#[derive(Debug)]
+pub struct ParseFieldError {
+    kind: ParseFieldErrorKind,
+}
+
+#[derive(Debug)]
+enum ParseFieldErrorKind {
+    InvalidNumber,
+    OutOfRange,
+}
+
+impl ParseFieldError {
+    fn out_of_range() -> Self {
+        Self { kind: ParseFieldErrorKind::OutOfRange }
+    }
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseFieldErrorKind::InvalidNumber => {
+                write!(f, "expected a decimal or 0x-prefixed hex number")
+            }
+            ParseFieldErrorKind::OutOfRange => write!(f, "number is not smaller than the modulus"),
+        }
+    }
+}
+
+impl std::error::Error for ParseFieldError {}
+
+/// Parses a decimal or `0x`-prefixed hex number smaller than `modulus` into little-endian bytes.
+fn parse_element(value: &str, modulus: &U256) -> Result<[u8; 32], ParseFieldError> {
+    let (digits, radix) = match value.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (value, 10),
+    };
+    let invalid = || ParseFieldError { kind: ParseFieldErrorKind::InvalidNumber };
+    if digits.is_empty() {
+        return Err(invalid());
+    }
+    let mut n: U256 = [0; 4];
+    for c in digits.chars() {
+        let mut carry = c.to_digit(radix).ok_or_else(invalid)? as u128;
+        for limb in n.iter_mut() {
+            let v = *limb as u128 * radix as u128 + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 {
+            return Err(ParseFieldError::out_of_range());
+        }
+    }
+    if !lt(&n, modulus) {
+        return Err(ParseFieldError::out_of_range());
+    }
+    Ok(u256_to_le_bytes(&n))
+}
+
+/// Formats a little-endian 32-byte element as a decimal number.
+fn element_to_decimal(bytes: &[u8]) -> String {
+    to_decimal(u256_from_le_bytes(bytes.try_into().unwrap_or_else(|_| env::abort())))
+}
+
+fn to_decimal(mut n: U256) -> String {
+    let mut digits = Vec::new();
+    loop {
+        let mut rem = 0u128;
+        for limb in n.iter_mut().rev() {
+            let v = rem << 64 | *limb as u128;
+            *limb = (v / 10) as u64;
+            rem = v % 10;
+        }
+        digits.push(b'0' + rem as u8);
+        if n == [0; 4] {
+            break;
+        }
+    }
+    digits.iter().rev().map(|&d| d as char).collect()
+}
+
+fn lt(a: &U256, b: &U256) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+fn u256_from_le_bytes(bytes: &[u8; 32]) -> U256 {
+    let mut n = [0; 4];
+    for (limb, chunk) in n.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap_or_else(|_| env::abort()));
+    }
+    n
+}
+
+fn u256_to_le_bytes(n: &U256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(n) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Key and proof of a circuit with two public inputs, set up with known toxic waste.
+    const VERIFYING_KEY: &str = r#"{
+        "protocol": "groth16",
+        "curve": "bn128",
+        "nPublic": 2,
+        "vk_alpha_1": [
+            "19033251874843656108471242320417533909414939332036131356573128480367742634479",
+            "20792135454608030201903199625673964159744755218442260092768620403349374102584",
+            "1"
+        ],
+        "vk_beta_2": [
+            ["16137324789686743234629608741537369181251990815455155257427276976918350071287", "280672898440571232725436467950720547829638241593507531241322547969961007057"],
+            ["12136420650226457477690750437223209427924916790606163705631661913973995426040", "17641806683785498955878869918183868440783188556637975525088932771694068429840"],
+            ["1", "0"]
+        ],
+        "vk_gamma_2": [
+            ["5571996575954125260736435753480252954196528247617148060558631406349160775832", "15577308679414974642168536368096450326086203870944559758314800234684337462316"],
+            ["11302850696403459405052467769487663388868168369318255751101607320138145101673", "3949072583587836530885517791345259776526014207612010591436388615095276192789"],
+            ["1", "0"]
+        ],
+        "vk_delta_2": [
+            ["9858527670347636692234166401928174269791741769432234490836150038270445961293", "16849508654450081119304017172227396057124361478955927014163046732185922553166"],
+            ["20108569381576808061469857349769609506804248011311707108758562062556705125393", "13963340053412710066602628493986245254268869857782169725667227673717164818367"],
+            ["1", "0"]
+        ],
+        "IC": [
+            ["13640322012419910779160519747081036978280854528525356142388876682012724302321", "18538714940515721848968265449014632110570653454278528879450713650630487487382", "1"],
+            ["9961482077405933653703920413004101065199760487639777914203301284159532567165", "5862436715964027487145075334372980905100234227901145792980374837265196864691", "1"],
+            ["9366015879375004571250438303432407971238053874512316318402267084951246439740", "18456548560916331602912926306132216314029103442570467520030714287463663922742", "1"]
+        ]
+    }"#;
+
+    const PROOF: &str = r#"{
+        "pi_a": [
+            "16711416843522114883433203178480474873102412521895441414645328675899158417507",
+            "8853086173564136849925865683598036960975837022999254197357454379453354604209",
+            "1"
+        ],
+        "pi_b": [
+            ["9196454279045833558964584763522054629402264714430462349585327397513246589059", "7753820733870010007002567610296293542789734490422667563726262060646954483828"],
+            ["16884217140014367286894274128397983223598541959667992086512433551579010917438", "20541522916252121878823226344242781501445438469839352658759729302896506657102"],
+            ["1", "0"]
+        ],
+        "pi_c": [
+            "20098896197060219379038709625196147035038451138730398720782773652072597512045",
+            "13229531900823967390918171264274330653260758215049011519976648343402975049116",
+            "1"
+        ],
+        "protocol": "groth16",
+        "curve": "bn128"
+    }"#;
+
+    const PUBLIC: &str = r#"["33", "12345678901234567890"]"#;
+
+    fn vectors() -> (VerifyingKey, Proof, Vec<Fr>) {
+        (
+            serde_json::from_str(VERIFYING_KEY).unwrap(),
+            serde_json::from_str(PROOF).unwrap(),
+            serde_json::from_str(PUBLIC).unwrap(),
+        )
+    }
+
+    #[test]
+    fn verify_proof() {
+        let (vk, proof, public_inputs) = vectors();
+        assert_eq!(vk.num_public_inputs(), 2);
+        assert!(vk.verify(&proof, &public_inputs));
+
+        assert!(!vk.verify(&proof, &[public_inputs[0], Fr::from(1)]));
+        assert!(!vk.verify(&proof, &public_inputs[..1]));
+        assert!(!vk.verify(&Proof { c: proof.a, ..proof }, &public_inputs));
+    }
+
+    #[test]
+    fn serialization() {
+        let (vk, proof, public_inputs) = vectors();
+
+        let borsh = borsh::to_vec(&vk).unwrap();
+        assert_eq!(VerifyingKey::try_from_slice(&borsh).unwrap(), vk);
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(serde_json::from_str::<Proof>(&json).unwrap(), proof);
+        assert_eq!(serde_json::to_string(&public_inputs).unwrap(), PUBLIC.replace(", ", ","));
+
+        // G1 generator, with arkworks names and hex coordinates.
+        let point: G1Point = serde_json::from_str(r#"["0x1", "0x2"]"#).unwrap();
+        assert_eq!(point.0[0], 1);
+        assert_eq!(point.0[32], 2);
+        assert_eq!(serde_json::to_string(&point).unwrap(), r#"["1","2","1"]"#);
+        let infinity: G1Point = serde_json::from_str(r#"["0", "1", "0"]"#).unwrap();
+        assert_eq!(infinity, G1Point([0; 64]));
+        let proof = serde_json::json!({ "a": proof.a, "b": proof.b, "c": proof.c });
+        assert!(serde_json::from_value::<Proof>(proof).is_ok());
+    }
+
+    #[test]
+    fn field_elements() {
+        assert_eq!("0".parse::<Fr>().unwrap(), Fr::from(0));
+        assert_eq!("0xff".parse::<Fr>().unwrap(), Fr::from(255));
+        let r_minus_one =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+        assert_eq!(r_minus_one.parse::<Fr>().unwrap().to_string(), r_minus_one);
+        let r = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        assert!(r.parse::<Fr>().is_err());
+        assert!(format!("{}0", r).parse::<Fr>().is_err());
+        assert!("".parse::<Fr>().is_err());
+        assert!("12a".parse::<Fr>().is_err());
+        assert!(Fr::try_from_slice(&[0xff; 32]).is_err());
+        assert!(serde_json::from_str::<G1Point>(r#"["1", "2", "3"]"#).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+//! Cryptographic schemes built on top of the host functions of [`env`](crate::env).
+
+pub mod bls12381;
+pub mod ethereum;
+pub mod groth16;
@@ -10,15 +10,27 @@
 use std::convert::TryInto;
 use std::mem::{size_of, size_of_val};
 use std::panic as std_panic;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{convert::TryFrom, mem::MaybeUninit};
 
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(feature = "unit-testing"),
+    feature = "non-contract-usage"
+))]
+use super::offchain as sys;
 #[cfg(all(not(target_arch = "wasm32"), feature = "unit-testing"))]
 use crate::mock::MockedBlockchain;
 use crate::promise::Allowance;
 use crate::types::{
     AccountId, BlockHeight, Gas, NearToken, PromiseIndex, PromiseResult, PublicKey, StorageUsage,
 };
-use crate::{CryptoHash, GasWeight, PromiseError};
+use crate::{CryptoHash, DeserializationError, GasWeight, PromiseError};
+#[cfg(any(
+    target_arch = "wasm32",
+    feature = "unit-testing",
+    not(feature = "non-contract-usage")
+))]
 use near_sys as sys;
 
 const REGISTER_EXPECTED_ERR: &str =
@@ -34,6 +46,9 @@ const EVICTED_REGISTER: u64 = u64::MAX - 1;
 /// Key used to store the state of the contract.
 const STATE_KEY: &[u8] = b"STATE";
 
+/// Key used to store the version of the state of the contract.
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
 /// The minimum length of a valid account ID.
 const MIN_ACCOUNT_ID_LEN: u64 = 2;
 /// The maximum length of a valid account ID.
@@ -102,6 +117,8 @@ pub(crate) unsafe fn read_register_fixed_64(register_id: u64) -> [u8; 64] {
 /// ```
 #[cfg(all(not(target_arch = "wasm32"), feature = "unit-testing"))]
 pub fn set_blockchain_interface(blockchain_interface: MockedBlockchain) {
+    crate::logging::reset();
+    crate::gas_profile::reset();
     crate::mock::with_mocked_blockchain(|b| {
         *b = blockchain_interface;
     })
@@ -152,6 +169,118 @@ pub fn register_len(register_id: u64) -> Option<u64> {
     }
 }
 
+/// Writes `data` into the `register_id`, replacing its previous content.
+pub fn write_register(register_id: u64, data: &[u8]) {
+    unsafe { sys::write_register(register_id, data.len() as _, data.as_ptr() as _) }
+}
+
+/// Registers handed out by [`Register::unused`] start here, away from the small ids contracts
+/// usually pick and from the registers used internally by this module.
+const FIRST_ALLOCATED_REGISTER: u64 = u64::MAX / 2;
+
+static NEXT_ALLOCATED_REGISTER: AtomicU64 = AtomicU64::new(FIRST_ALLOCATED_REGISTER);
+
+/// Handle to a register of the runtime, which host functions can write their output into.
+///
+/// Registers live for the duration of a single function call. Of the functions of this module,
+/// only [`promise_yield_create_in_register`] hands out its output as a register; the others read
+/// the registers they use and return the data. Other registers are mostly useful when calling
+/// host functions directly, or to keep data around without holding it in Wasm memory.
+///
+/// # Examples
+/// ```
+/// use near_sdk::env::Register;
+///
+/// let register = Register::unused();
+/// assert_eq!(register.len(), None);
+///
+/// register.write(b"some data");
+/// let mut buf = Vec::with_capacity(64);
+/// assert!(register.read_into(&mut buf));
+/// assert_eq!(buf, b"some data");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Register(u64);
+
+impl Register {
+    /// Handle to the register with the given id.
+    pub const fn new(register_id: u64) -> Self {
+        Self(register_id)
+    }
+
+    /// Returns a register which is not used and was not handed out by this function before in the
+    /// current function call.
+    pub fn unused() -> Self {
+        loop {
+            let register_id = NEXT_ALLOCATED_REGISTER.fetch_add(1, Ordering::Relaxed);
+            if register_len(register_id).is_none() {
+                return Self(register_id);
+            }
+        }
+    }
+
+    /// Id of the register, to be passed to host functions.
+    pub const fn id(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the size of the register, or `None` if it is not used.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(self) -> Option<u64> {
+        register_len(self.0)
+    }
+
+    /// Writes `data` into the register, replacing its previous content.
+    pub fn write(self, data: &[u8]) {
+        write_register(self.0, data)
+    }
+
+    /// Reads the content of the register, or `None` if it is not used.
+    pub fn read(self) -> Option<Vec<u8>> {
+        read_register(self.0)
+    }
+
+    /// Reads the content of the register into `buf`, replacing its content and only reallocating
+    /// if it lacks the capacity. Returns `false`, leaving `buf` untouched, if the register is not
+    /// used.
+    pub fn read_into(self, buf: &mut Vec<u8>) -> bool {
+        let len: usize = match register_len(self.0) {
+            Some(len) => len.try_into().unwrap_or_else(|_| abort()),
+            None => return false,
+        };
+        buf.clear();
+        buf.reserve(len);
+        //* SAFETY: `buf` has the capacity for the whole register, which the read initializes.
+        unsafe {
+            sys::read_register(self.0, buf.as_mut_ptr() as u64);
+            buf.set_len(len);
+        }
+        true
+    }
+
+    /// Reads a [`CryptoHash`], such as a data id, from the register. Returns `None` if the register
+    /// is not used and panics if it does not hold exactly 32 bytes.
+    pub fn read_crypto_hash(self) -> Option<CryptoHash> {
+        match register_len(self.0)? {
+            //* SAFETY: The register was just checked to hold 32 bytes.
+            32 => Some(unsafe { read_register_fixed_32(self.0) }),
+            _ => panic_str("Register does not hold a 32 byte hash"),
+        }
+    }
+}
+
+impl From<u64> for Register {
+    fn from(register_id: u64) -> Self {
+        Self(register_id)
+    }
+}
+
+impl From<Register> for u64 {
+    fn from(register: Register) -> Self {
+        register.0
+    }
+}
+
 // ###############
 // # Context API #
 // ###############
@@ -737,72 +866,81 @@ pub fn alt_bn128_pairing_check(value: &[u8]) -> bool {
 // # BLS12-381 #
 // #############
 
+/// Calls a BLS12-381 host function, which writes its result into `ATOMIC_OP_REGISTER` and returns
+/// `0` on success. Evaluates to `None` if the host function rejects `value`.
+macro_rules! bls12381_call {
+    ( $method:ident, $value:expr ) => {{
+        let value: &[u8] = $value;
+        match unsafe { sys::$method(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER) } {
+            0 => Some(
+                read_register(ATOMIC_OP_REGISTER)
+                    .unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR)),
+            ),
+            _ => None,
+        }
+    }};
+}
+
 /// Compute BLS12-381 G1 sum.
 ///
 /// See also: [IETF draft-irtf-cfrg-pairing-friendly-curves](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-pairing-friendly-curves)
 pub fn bls12381_p1_sum(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_p1_sum(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_p1_sum(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_p1_sum`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_p1_sum(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_p1_sum, value)
 }
 
 /// Compute BLS12-381 G2 sum.
 pub fn bls12381_p2_sum(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_p2_sum(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_p2_sum(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_p2_sum`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_p2_sum(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_p2_sum, value)
 }
 
 /// Compute BLS12-381 G1 multiexponentiation.
 pub fn bls12381_g1_multiexp(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_g1_multiexp(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_g1_multiexp(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_g1_multiexp`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_g1_multiexp(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_g1_multiexp, value)
 }
 
 /// Compute BLS12-381 G2 multiexponentiation.
 pub fn bls12381_g2_multiexp(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_g2_multiexp(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_g2_multiexp(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_g2_multiexp`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_g2_multiexp(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_g2_multiexp, value)
 }
 
 /// Map an Fp element to a BLS12-381 G1 point.
 pub fn bls12381_map_fp_to_g1(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_map_fp_to_g1(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_map_fp_to_g1(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_map_fp_to_g1`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_map_fp_to_g1(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_map_fp_to_g1, value)
 }
 
 /// Map an Fp2 element to a BLS12-381 G2 point.
 pub fn bls12381_map_fp2_to_g2(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_map_fp2_to_g2(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_map_fp2_to_g2(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_map_fp2_to_g2`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_map_fp2_to_g2(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_map_fp2_to_g2, value)
 }
 
 /// Perform BLS12-381 pairing check. Returns true if the pairing check passes.
@@ -812,24 +950,22 @@ pub fn bls12381_pairing_check(value: &[u8]) -> bool {
 
 /// Decompress a BLS12-381 G1 point.
 pub fn bls12381_p1_decompress(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_p1_decompress(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_p1_decompress(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_p1_decompress`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_p1_decompress(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_p1_decompress, value)
 }
 
 /// Decompress a BLS12-381 G2 point.
 pub fn bls12381_p2_decompress(value: &[u8]) -> Vec<u8> {
-    unsafe {
-        sys::bls12381_p2_decompress(value.len() as _, value.as_ptr() as _, ATOMIC_OP_REGISTER);
-    };
-    match read_register(ATOMIC_OP_REGISTER) {
-        Some(result) => result,
-        None => panic_str(REGISTER_EXPECTED_ERR),
-    }
+    try_bls12381_p2_decompress(value).unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
+}
+
+/// Same as [`bls12381_p2_decompress`], but returns `None` if the host function rejects `value`.
+pub(crate) fn try_bls12381_p2_decompress(value: &[u8]) -> Option<Vec<u8>> {
+    bls12381_call!(bls12381_p2_decompress, value)
 }
 
 // ################
@@ -1111,6 +1247,133 @@ pub fn promise_batch_action_deploy_contract(promise_index: PromiseIndex, code: &
     }
 }
 
+/// Attach a deploy global contract promise action to the NEAR promise index with the provided
+/// promise index. Other accounts can then use the code by its hash, see
+/// [`promise_batch_action_use_global_contract`].
+///
+/// More info about batching [here](crate::env::promise_batch_create)
+/// # Examples
+/// ```
+/// use near_sdk::env::{promise_batch_action_deploy_global_contract, promise_batch_create};
+/// use near_sdk::AccountId;
+/// use std::str::FromStr;
+///
+/// let promise = promise_batch_create(
+///     &AccountId::from_str("contract.near").unwrap()
+/// );
+///
+/// let code = [0; 1487];
+/// promise_batch_action_deploy_global_contract(promise, &code);
+/// ```
+pub fn promise_batch_action_deploy_global_contract(promise_index: PromiseIndex, code: &[u8]) {
+    unsafe {
+        sys::promise_batch_action_deploy_global_contract(
+            promise_index.0,
+            code.len() as _,
+            code.as_ptr() as _,
+        )
+    }
+}
+
+/// Attach a deploy global contract promise action to the NEAR promise index with the provided
+/// promise index. Other accounts can then use the code by the id of the account it is deployed
+/// by, which can later update it, see [`promise_batch_action_use_global_contract_by_account_id`].
+///
+/// More info about batching [here](crate::env::promise_batch_create)
+/// # Examples
+/// ```
+/// use near_sdk::env::{
+///     promise_batch_action_deploy_global_contract_by_account_id, promise_batch_create,
+/// };
+/// use near_sdk::AccountId;
+/// use std::str::FromStr;
+///
+/// let promise = promise_batch_create(
+///     &AccountId::from_str("contract.near").unwrap()
+/// );
+///
+/// let code = [0; 1487];
+/// promise_batch_action_deploy_global_contract_by_account_id(promise, &code);
+/// ```
+pub fn promise_batch_action_deploy_global_contract_by_account_id(
+    promise_index: PromiseIndex,
+    code: &[u8],
+) {
+    unsafe {
+        sys::promise_batch_action_deploy_global_contract_by_account_id(
+            promise_index.0,
+            code.len() as _,
+            code.as_ptr() as _,
+        )
+    }
+}
+
+/// Attach a use global contract promise action to the NEAR promise index with the provided
+/// promise index. The account starts using the global contract code with the given hash.
+///
+/// More info about batching [here](crate::env::promise_batch_create)
+/// # Examples
+/// ```
+/// use near_sdk::env::{promise_batch_action_use_global_contract, promise_batch_create};
+/// use near_sdk::AccountId;
+/// use std::str::FromStr;
+///
+/// let promise = promise_batch_create(
+///     &AccountId::from_str("child.contract.near").unwrap()
+/// );
+///
+/// let code_hash = [0; 32];
+/// promise_batch_action_use_global_contract(promise, &code_hash);
+/// ```
+pub fn promise_batch_action_use_global_contract(
+    promise_index: PromiseIndex,
+    code_hash: &CryptoHash,
+) {
+    unsafe {
+        sys::promise_batch_action_use_global_contract(
+            promise_index.0,
+            code_hash.len() as _,
+            code_hash.as_ptr() as _,
+        )
+    }
+}
+
+/// Attach a use global contract promise action to the NEAR promise index with the provided
+/// promise index. The account starts using the global contract deployed by `account_id`, and
+/// follows its updates.
+///
+/// More info about batching [here](crate::env::promise_batch_create)
+/// # Examples
+/// ```
+/// use near_sdk::env::{
+///     promise_batch_action_use_global_contract_by_account_id, promise_batch_create,
+/// };
+/// use near_sdk::AccountId;
+/// use std::str::FromStr;
+///
+/// let promise = promise_batch_create(
+///     &AccountId::from_str("child.contract.near").unwrap()
+/// );
+///
+/// promise_batch_action_use_global_contract_by_account_id(
+///     promise,
+///     &AccountId::from_str("contract.near").unwrap(),
+/// );
+/// ```
+pub fn promise_batch_action_use_global_contract_by_account_id(
+    promise_index: PromiseIndex,
+    account_id: &AccountId,
+) {
+    let account_id = account_id.as_bytes();
+    unsafe {
+        sys::promise_batch_action_use_global_contract_by_account_id(
+            promise_index.0,
+            account_id.len() as _,
+            account_id.as_ptr() as _,
+        )
+    }
+}
+
 /// Attach a function call promise action to the NEAR promise index with the provided promise index.
 ///
 /// More info about batching [here](crate::env::promise_batch_create)
@@ -1653,6 +1916,29 @@ pub fn promise_yield_create(
     }
 }
 
+/// Same as [`promise_yield_create`], but writes the resumption token `data_id` into a
+/// [`Register::unused`] register which is returned along with the promise index.
+///
+/// # Examples
+/// ```no_run
+/// use near_sdk::env::promise_yield_create_in_register;
+/// use near_sdk::{Gas, GasWeight};
+///
+/// let (promise, data_id_register) =
+///     promise_yield_create_in_register("on_resume", b"{}", Gas::from_tgas(10), GasWeight(0));
+/// let data_id = data_id_register.read_crypto_hash().unwrap();
+/// ```
+pub fn promise_yield_create_in_register(
+    function_name: &str,
+    arguments: &[u8],
+    gas: Gas,
+    weight: GasWeight,
+) -> (PromiseIndex, Register) {
+    let register = Register::unused();
+    let promise = promise_yield_create(function_name, arguments, gas, weight, register.id());
+    (promise, register)
+}
+
 /// Accepts a resumption token `data_id` created by promise_yield_create on the local account.
 /// `data` is a payload to be passed to the callback method as a promise input. Returns false if
 /// no promise yield with the specified `data_id` is found. Returns true otherwise, guaranteeing
@@ -1856,9 +2142,29 @@ pub fn log_str(message: &str) {
     #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
     eprintln!("{}", message);
 
+    crate::logging::record(message.len());
     unsafe { sys::log_utf8(message.len() as _, message.as_ptr() as _) }
 }
 
+/// Logs the UTF-16 encoded message, e.g. a string coming from JavaScript. This message is stored
+/// on chain, decoded to UTF-8.
+///
+/// # Examples
+/// ```
+/// use near_sdk::env::log_utf16;
+///
+/// let message: Vec<u16> = "Some text".encode_utf16().collect();
+/// log_utf16(&message);
+/// # assert_eq!(near_sdk::test_utils::get_logs(), ["Some text"]);
+/// ```
+pub fn log_utf16(message: &[u16]) {
+    let decoded_len = char::decode_utf16(message.iter().copied())
+        .map(|c| c.map_or(char::REPLACEMENT_CHARACTER.len_utf8(), char::len_utf8))
+        .sum();
+    crate::logging::record(decoded_len);
+    unsafe { sys::log_utf16(size_of_val(message) as _, message.as_ptr() as _) }
+}
+
 /// Log the UTF-8 encodable message.
 ///
 /// # Examples
@@ -1872,6 +2178,7 @@ pub fn log(message: &[u8]) {
     #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
     eprintln!("{}", String::from_utf8_lossy(message));
 
+    crate::logging::record(message.len());
     unsafe { sys::log_utf8(message.len() as _, message.as_ptr() as _) }
 }
 
@@ -2000,10 +2307,25 @@ pub fn storage_has_key(key: &[u8]) -> bool {
 // ############################################
 /// Load the state of the given object.
 pub fn state_read<T: borsh::BorshDeserialize>() -> Option<T> {
-    storage_read(STATE_KEY).map(|data| {
-        T::try_from_slice(&data)
-            .unwrap_or_else(|_| panic_str("Cannot deserialize the contract state."))
-    })
+    try_state_read().unwrap_or_else(|_| panic_str("Cannot deserialize the contract state."))
+}
+
+/// Load the state of the given object, returning an error rather than panicking if it can't be
+/// deserialized, e.g. to migrate from the state of a previous version of the contract.
+///
+/// # Examples
+/// ```
+/// use near_sdk::env::{state_write, try_state_read};
+///
+/// assert_eq!(try_state_read::<u64>(), Ok(None));
+/// state_write(&1u8);
+/// assert!(try_state_read::<u64>().is_err());
+/// assert_eq!(try_state_read::<u8>(), Ok(Some(1)));
+/// ```
+pub fn try_state_read<T: borsh::BorshDeserialize>() -> Result<Option<T>, DeserializationError> {
+    storage_read(STATE_KEY)
+        .map(|data| T::try_from_slice(&data).map_err(DeserializationError::new))
+        .transpose()
 }
 
 /// Writes the specified state to storage.
@@ -2019,6 +2341,27 @@ pub fn state_exists() -> bool {
     storage_has_key(STATE_KEY)
 }
 
+/// Version of the contract state, written by the init methods of contracts declared with
+/// `#[near(contract_state, version = N)]`, see [`VersionedState`](crate::VersionedState).
+///
+/// # Examples
+/// ```
+/// use near_sdk::env::{state_version, state_version_write};
+///
+/// assert_eq!(state_version(), None);
+/// state_version_write(2);
+/// assert_eq!(state_version(), Some(2));
+/// ```
+pub fn state_version() -> Option<u32> {
+    let data = storage_read(STATE_VERSION_KEY)?;
+    Some(data.try_into().map(u32::from_le_bytes).unwrap_or_else(|_| abort()))
+}
+
+/// Writes the version of the contract state, see [`state_version`].
+pub fn state_version_write(version: u32) {
+    storage_write(STATE_VERSION_KEY, &version.to_le_bytes());
+}
+
 // #####################################
 // # Parameters exposed by the runtime #
 // #####################################
@@ -2169,6 +2512,43 @@ mod tests {
         assert!(is_valid_account_id(b"near"));
     }
 
+    #[test]
+    fn registers() {
+        let first = Register::unused();
+        let second = Register::unused();
+        assert_ne!(first, second);
+        assert_eq!(first.read(), None);
+
+        let mut buf = vec![1, 2, 3];
+        assert!(!first.read_into(&mut buf));
+        assert_eq!(buf, [1, 2, 3]);
+
+        first.write(&[7; 32]);
+        assert_ne!(Register::unused(), first);
+        assert_eq!(first.len(), Some(32));
+        assert!(first.read_into(&mut buf));
+        assert_eq!(buf, [7; 32]);
+        assert_eq!(first.read_crypto_hash(), Some([7; 32]));
+
+        second.write(b"");
+        assert_eq!(read_register(second.id()), Some(vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Register does not hold a 32 byte hash")]
+    fn register_crypto_hash_length() {
+        let register = Register::unused();
+        register.write(&[0; 31]);
+        register.read_crypto_hash();
+    }
+
+    #[test]
+    fn yield_create_in_register() {
+        let (_, register) =
+            promise_yield_create_in_register("callback", b"{}", Gas::from_tgas(10), GasWeight(0));
+        assert!(register.read_crypto_hash().is_some());
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[test]
     fn hash_smoke_tests() {
@@ -4,12 +4,12 @@ use crate::mock::MockAction;
 use crate::mock::mocked_memory::MockedMemory;
 use crate::test_utils::VMContextBuilder;
 use crate::types::{NearToken, PromiseResult};
-use crate::VMContext;
+use crate::{VMContext, YieldId};
 use near_parameters::{RuntimeConfigStore, RuntimeFeesConfig};
 use near_primitives_core::version::PROTOCOL_VERSION;
-use near_vm_runner::logic::mocks::mock_external::MockedExternal;
+use near_vm_runner::logic::mocks::mock_external::{MockAction as LogicMockAction, MockedExternal};
 use near_vm_runner::logic::types::{PromiseResult as VmPromiseResult, ReceiptIndex};
-use near_vm_runner::logic::{ExecutionResultState, External, MemoryLike, VMLogic};
+use near_vm_runner::logic::{ExecutionResultState, External, MemSlice, MemoryLike, VMLogic};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::marker::PhantomData;
@@ -28,6 +28,9 @@ where
     // We keep ownership over logic fixture so that references in `VMLogic` are valid.
     #[allow(dead_code)]
     logic_fixture: LogicFixture,
+    // `VMLogic` doesn't implement the global contract actions, so they are recorded here along
+    // with the length of its action log at the time they were added.
+    global_contract_actions: Vec<(usize, MockAction)>,
     _memory: PhantomData<Memory>,
 }
 
@@ -104,7 +107,7 @@ where
         };
 
         let logic = RefCell::new(logic);
-        Self { logic, logic_fixture, _memory: PhantomData }
+        Self { logic, logic_fixture, global_contract_actions: Vec::new(), _memory: PhantomData }
     }
 
     pub fn take_storage(&mut self) -> HashMap<Vec<u8>, Vec<u8>> {
@@ -113,12 +116,21 @@ where
 
     /// Returns metadata about the receipts created
     pub fn created_receipts(&self) -> Vec<Receipt> {
-        let action_log = &self.logic_fixture.ext.action_log;
-        let action_log: Vec<MockAction> =
-            action_log.clone().into_iter().map(<MockAction as From<_>>::from).collect();
-        let create_receipts: Vec<(usize, MockAction)> = action_log
-            .clone()
-            .into_iter()
+        let logic_action_log = &self.logic_fixture.ext.action_log;
+        let mut global_contract_actions = self.global_contract_actions.iter().peekable();
+        let mut action_log: Vec<MockAction> = Vec::new();
+        for (position, action) in logic_action_log.iter().enumerate() {
+            while let Some((_, global)) = global_contract_actions.next_if(|(p, _)| *p == position) {
+                action_log.push(global.clone());
+            }
+            action_log.push(action.clone().into());
+        }
+        action_log.extend(global_contract_actions.map(|(_, global)| global.clone()));
+        // Receipt indices are positions in the action log of `VMLogic`.
+        let create_receipts: Vec<(usize, MockAction)> = logic_action_log
+            .iter()
+            .cloned()
+            .map(MockAction::from)
             .enumerate()
             .filter(|(_receipt_idx, action)| matches!(action, MockAction::CreateReceipt { .. }))
             .collect();
@@ -148,6 +160,44 @@ where
         result
     }
 
+    /// Returns the ids and payloads of the yielded promises resumed so far.
+    pub fn resumed_yields(&self) -> Vec<(YieldId, Vec<u8>)> {
+        self.logic_fixture
+            .ext
+            .action_log
+            .iter()
+            .filter_map(|action| match action {
+                LogicMockAction::YieldResume { data_id, data } => {
+                    Some((YieldId::from(data_id.0), data.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reads `len` bytes of the guest memory at `ptr`.
+    fn read_memory(&self, len: u64, ptr: u64) -> Vec<u8> {
+        let slice = MemSlice { ptr, len };
+        self.logic_fixture.memory.view_memory(slice).expect("invalid memory access").into_owned()
+    }
+
+    /// Records a global contract action on the receipt of the given promise.
+    fn add_global_contract_action(
+        &mut self,
+        promise_index: u64,
+        action: impl FnOnce(ReceiptIndex) -> MockAction,
+    ) {
+        // Adding an account creation checks the promise index and resolves its receipt the same
+        // way `VMLogic` would for a global contract action, which then takes its place.
+        self.logic.borrow_mut().promise_batch_action_create_account(promise_index).unwrap();
+        let receipt_index = match self.logic_fixture.ext.action_log.pop() {
+            Some(LogicMockAction::CreateAccount { receipt_index }) => receipt_index,
+            _ => unreachable!("an account creation was just added"),
+        };
+        let position = self.logic_fixture.ext.action_log.len();
+        self.global_contract_actions.push((position, action(receipt_index)));
+    }
+
     pub fn gas(&mut self, gas_amount: u32) {
         self.logic.borrow_mut().gas(gas_amount.into()).unwrap()
     }
@@ -189,6 +239,7 @@ fn sdk_context_to_vm_context(
 
 #[cfg(not(target_arch = "wasm32"))]
 mod mock_chain {
+    use crate::mock::MockAction;
     use near_vm_runner::logic::{errors::VMLogicError, VMLogic};
 
     fn with_mock_interface<F, R>(f: F) -> R
@@ -207,6 +258,10 @@ mod mock_chain {
         with_mock_interface(|b| b.register_len(register_id))
     }
     #[no_mangle]
+    extern "C-unwind" fn write_register(register_id: u64, data_len: u64, data_ptr: u64) {
+        with_mock_interface(|b| b.write_register(register_id, data_len, data_ptr))
+    }
+    #[no_mangle]
     extern "C-unwind" fn current_account_id(register_id: u64) {
         with_mock_interface(|b| b.current_account_id(register_id))
     }
@@ -419,6 +474,64 @@ mod mock_chain {
         })
     }
     #[no_mangle]
+    extern "C-unwind" fn promise_batch_action_deploy_global_contract(
+        promise_index: u64,
+        code_len: u64,
+        code_ptr: u64,
+    ) {
+        crate::mock::with_mocked_blockchain(|b| {
+            let code = b.read_memory(code_len, code_ptr);
+            b.add_global_contract_action(promise_index, |receipt_index| {
+                MockAction::DeployGlobalContract { receipt_index, code }
+            })
+        })
+    }
+    #[no_mangle]
+    extern "C-unwind" fn promise_batch_action_deploy_global_contract_by_account_id(
+        promise_index: u64,
+        code_len: u64,
+        code_ptr: u64,
+    ) {
+        crate::mock::with_mocked_blockchain(|b| {
+            let code = b.read_memory(code_len, code_ptr);
+            b.add_global_contract_action(promise_index, |receipt_index| {
+                MockAction::DeployGlobalContractByAccountId { receipt_index, code }
+            })
+        })
+    }
+    #[no_mangle]
+    extern "C-unwind" fn promise_batch_action_use_global_contract(
+        promise_index: u64,
+        code_hash_len: u64,
+        code_hash_ptr: u64,
+    ) {
+        crate::mock::with_mocked_blockchain(|b| {
+            let code_hash = b
+                .read_memory(code_hash_len, code_hash_ptr)
+                .try_into()
+                .expect("code hash must be 32 bytes");
+            b.add_global_contract_action(promise_index, |receipt_index| {
+                MockAction::UseGlobalContract { receipt_index, code_hash }
+            })
+        })
+    }
+    #[no_mangle]
+    extern "C-unwind" fn promise_batch_action_use_global_contract_by_account_id(
+        promise_index: u64,
+        account_id_len: u64,
+        account_id_ptr: u64,
+    ) {
+        crate::mock::with_mocked_blockchain(|b| {
+            let account_id = String::from_utf8(b.read_memory(account_id_len, account_id_ptr))
+                .ok()
+                .and_then(|account_id| account_id.parse().ok())
+                .expect("invalid account id");
+            b.add_global_contract_action(promise_index, |receipt_index| {
+                MockAction::UseGlobalContractByAccountId { receipt_index, account_id }
+            })
+        })
+    }
+    #[no_mangle]
     extern "C-unwind" fn promise_batch_action_function_call(
         promise_index: u64,
         function_name_len: u64,
@@ -2,7 +2,7 @@ use near_primitives_core::types::GasWeight;
 use near_vm_runner::logic::mocks::mock_external::MockAction as LogicMockAction;
 use near_vm_runner::logic::types::ReceiptIndex;
 
-use crate::{AccountId, Gas, NearToken};
+use crate::{AccountId, CryptoHash, Gas, NearToken};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -29,6 +29,22 @@ pub enum MockAction {
         receipt_index: ReceiptIndex,
         code: Vec<u8>,
     },
+    DeployGlobalContract {
+        receipt_index: ReceiptIndex,
+        code: Vec<u8>,
+    },
+    DeployGlobalContractByAccountId {
+        receipt_index: ReceiptIndex,
+        code: Vec<u8>,
+    },
+    UseGlobalContract {
+        receipt_index: ReceiptIndex,
+        code_hash: CryptoHash,
+    },
+    UseGlobalContractByAccountId {
+        receipt_index: ReceiptIndex,
+        account_id: AccountId,
+    },
     FunctionCallWeight {
         receipt_index: ReceiptIndex,
         method_name: Vec<u8>,
@@ -84,6 +100,12 @@ impl MockAction {
             MockAction::CreateReceipt { .. } => None,
             MockAction::CreateAccount { receipt_index } => Some(*receipt_index),
             MockAction::DeployContract { receipt_index, .. } => Some(*receipt_index),
+            MockAction::DeployGlobalContract { receipt_index, .. } => Some(*receipt_index),
+            MockAction::DeployGlobalContractByAccountId { receipt_index, .. } => {
+                Some(*receipt_index)
+            }
+            MockAction::UseGlobalContract { receipt_index, .. } => Some(*receipt_index),
+            MockAction::UseGlobalContractByAccountId { receipt_index, .. } => Some(*receipt_index),
             MockAction::FunctionCallWeight { receipt_index, .. } => Some(*receipt_index),
             MockAction::Transfer { receipt_index, .. } => Some(*receipt_index),
             MockAction::Stake { receipt_index, .. } => Some(*receipt_index),
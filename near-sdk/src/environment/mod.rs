@@ -4,3 +4,10 @@ pub mod env;
 /// Mock blockchain utilities. These can only be used inside tests and are not available for
 /// a wasm32 target.
 pub mod mock;
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(feature = "unit-testing"),
+    feature = "non-contract-usage"
+))]
+mod offchain;
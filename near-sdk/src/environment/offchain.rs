@@ -0,0 +1,169 @@
+//! Host functions for builds that run neither on the NEAR runtime nor on the
+//! [`MockedBlockchain`](crate::mock::MockedBlockchain), such as off-chain tools using `near-sdk`
+//! with the `non-contract-usage` feature.
+//!
+//! [`env`](crate::env) calls these instead of [`near_sys`] in such builds. They are plain Rust
+//! functions rather than exported symbols, so they never replace functions of the same name
+//! (such as libc `abort`) in the binary.
+//!
+//! Registers, hashing, logging and panics are implemented natively, so that collections from
+//! [`store`](crate::store) can be used against a
+//! [`StateDump`](crate::store::offchain::StateDump). All other host functions panic when called.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use sha2::Digest;
+
+thread_local! {
+    static REGISTERS: RefCell<HashMap<u64, Vec<u8>>> = RefCell::new(HashMap::new());
+}
+
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes.
+unsafe fn slice<'a>(len: u64, ptr: u64) -> &'a [u8] {
+    std::slice::from_raw_parts(ptr as *const u8, len as usize)
+}
+
+fn set_register(register_id: u64, data: Vec<u8>) {
+    REGISTERS.with(|registers| registers.borrow_mut().insert(register_id, data));
+}
+
+pub(super) unsafe fn read_register(register_id: u64, ptr: u64) {
+    REGISTERS.with(|registers| {
+        let registers = registers.borrow();
+        let data = registers
+            .get(&register_id)
+            .unwrap_or_else(|| std::panic!("register {} is not used", register_id));
+        //* SAFETY: the caller provides a buffer of at least `register_len` bytes.
+        std::ptr::copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len())
+    })
+}
+
+pub(super) unsafe fn register_len(register_id: u64) -> u64 {
+    REGISTERS.with(|registers| {
+        registers.borrow().get(&register_id).map_or(u64::MAX, |data| data.len() as u64)
+    })
+}
+
+pub(super) unsafe fn write_register(register_id: u64, data_len: u64, data_ptr: u64) {
+    set_register(register_id, slice(data_len, data_ptr).to_vec())
+}
+
+pub(super) unsafe fn sha256(value_len: u64, value_ptr: u64, register_id: u64) {
+    set_register(register_id, sha2::Sha256::digest(slice(value_len, value_ptr)).to_vec())
+}
+
+pub(super) unsafe fn keccak256(value_len: u64, value_ptr: u64, register_id: u64) {
+    set_register(register_id, sha3::Keccak256::digest(slice(value_len, value_ptr)).to_vec())
+}
+
+pub(super) unsafe fn keccak512(value_len: u64, value_ptr: u64, register_id: u64) {
+    set_register(register_id, sha3::Keccak512::digest(slice(value_len, value_ptr)).to_vec())
+}
+
+pub(super) unsafe fn panic() -> ! {
+    std::panic!("explicit guest panic")
+}
+
+pub(super) unsafe fn panic_utf8(len: u64, ptr: u64) -> ! {
+    std::panic!("{}", String::from_utf8_lossy(slice(len, ptr)))
+}
+
+pub(super) unsafe fn log_utf8(len: u64, ptr: u64) {
+    // Debug builds already print the message in `env::log_str`.
+    if !cfg!(debug_assertions) {
+        eprintln!("{}", String::from_utf8_lossy(slice(len, ptr)));
+    }
+}
+
+pub(super) unsafe fn log_utf16(len: u64, ptr: u64) {
+    let message = std::slice::from_raw_parts(ptr as *const u16, len as usize / 2);
+    eprintln!("{}", String::from_utf16_lossy(message));
+}
+
+pub(super) unsafe fn block_height() -> u64 {
+    block_index()
+}
+
+macro_rules! unavailable {
+    ($($(#[$attr:meta])* $name:ident($($arg:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$attr])*
+            #[allow(clippy::too_many_arguments)]
+            pub(super) unsafe fn $name($(_: $arg),*) $(-> $ret)? {
+                std::panic!(concat!(
+                    "host function `",
+                    stringify!($name),
+                    "` is not available outside of the NEAR runtime"
+                ))
+            }
+        )*
+    };
+}
+
+unavailable! {
+    current_account_id(u64);
+    signer_account_id(u64);
+    signer_account_pk(u64);
+    predecessor_account_id(u64);
+    input(u64);
+    block_index() -> u64;
+    block_timestamp() -> u64;
+    epoch_height() -> u64;
+    storage_usage() -> u64;
+    account_balance(u64);
+    account_locked_balance(u64);
+    attached_deposit(u64);
+    prepaid_gas() -> u64;
+    used_gas() -> u64;
+    random_seed(u64);
+    ripemd160(u64, u64, u64);
+    #[cfg(feature = "unstable")]
+    ecrecover(u64, u64, u64, u64, u64, u64, u64) -> u64;
+    ed25519_verify(u64, u64, u64, u64, u64, u64) -> u64;
+    value_return(u64, u64);
+    promise_create(u64, u64, u64, u64, u64, u64, u64, u64) -> u64;
+    promise_then(u64, u64, u64, u64, u64, u64, u64, u64, u64) -> u64;
+    promise_and(u64, u64) -> u64;
+    promise_batch_create(u64, u64) -> u64;
+    promise_batch_then(u64, u64, u64) -> u64;
+    promise_batch_action_create_account(u64);
+    promise_batch_action_deploy_contract(u64, u64, u64);
+    promise_batch_action_deploy_global_contract(u64, u64, u64);
+    promise_batch_action_deploy_global_contract_by_account_id(u64, u64, u64);
+    promise_batch_action_use_global_contract(u64, u64, u64);
+    promise_batch_action_use_global_contract_by_account_id(u64, u64, u64);
+    promise_batch_action_function_call(u64, u64, u64, u64, u64, u64, u64);
+    promise_batch_action_function_call_weight(u64, u64, u64, u64, u64, u64, u64, u64);
+    promise_batch_action_transfer(u64, u64);
+    promise_batch_action_stake(u64, u64, u64, u64);
+    promise_batch_action_add_key_with_full_access(u64, u64, u64, u64);
+    promise_batch_action_add_key_with_function_call(u64, u64, u64, u64, u64, u64, u64, u64, u64);
+    promise_batch_action_delete_key(u64, u64, u64);
+    promise_batch_action_delete_account(u64, u64, u64);
+    promise_yield_create(u64, u64, u64, u64, u64, u64, u64) -> u64;
+    promise_yield_resume(u64, u64, u64, u64) -> u32;
+    promise_results_count() -> u64;
+    promise_result(u64, u64) -> u64;
+    promise_return(u64);
+    storage_write(u64, u64, u64, u64, u64) -> u64;
+    storage_read(u64, u64, u64) -> u64;
+    storage_remove(u64, u64, u64) -> u64;
+    storage_has_key(u64, u64) -> u64;
+    validator_stake(u64, u64, u64);
+    validator_total_stake(u64);
+    alt_bn128_g1_multiexp(u64, u64, u64);
+    alt_bn128_g1_sum(u64, u64, u64);
+    alt_bn128_pairing_check(u64, u64) -> u64;
+    bls12381_p1_sum(u64, u64, u64) -> u64;
+    bls12381_p2_sum(u64, u64, u64) -> u64;
+    bls12381_g1_multiexp(u64, u64, u64) -> u64;
+    bls12381_g2_multiexp(u64, u64, u64) -> u64;
+    bls12381_map_fp_to_g1(u64, u64, u64) -> u64;
+    bls12381_map_fp2_to_g2(u64, u64, u64) -> u64;
+    bls12381_pairing_check(u64, u64) -> u64;
+    bls12381_p1_decompress(u64, u64, u64) -> u64;
+    bls12381_p2_decompress(u64, u64, u64) -> u64;
+}
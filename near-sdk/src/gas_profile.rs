@@ -0,0 +1,320 @@
+//! Gas profiling of contract code with named spans.
+//!
+//! ```
+//! use near_sdk::{env, gas_span};
+//! # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+//!
+//! let total = gas_span!("sum", {
+//!     let _write = gas_span!("write");
+//!     env::storage_write(b"key", b"value");
+//!     2 + 2
+//! });
+//! assert_eq!(total, 4);
+//!
+//! let report = near_sdk::gas_profile::report();
+//! assert_eq!(report.spans().len(), 2);
+//! assert!(report.get("sum").unwrap().gas >= report.get("write").unwrap().gas);
+//! ```
+//!
+//! The gas of a span includes the gas of the spans nested in it. Spans entered several times in
+//! the same parent span are merged, so a span in a loop is reported once with the number of calls.
+//! Methods annotated with [`#[gas_profile]`](crate::near#gas_profile-annotates-methods-of-a-type-in-its-impl-block)
+//! are profiled as a whole.
+//!
+//! Spans are only recorded with the `gas-profiling` feature, with which a summary is logged by
+//! [`flush`] at the end of each profiled method, and in unit tests, where the [`report`] is
+//! available instead. The summary is logged with [`env::log_str`], so the
+//! `logging-max-level-*` features of [`logging`](crate::logging) don't disable it. The gas of unit
+//! tests only counts host function calls, as the Rust code is not metered off-chain.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+
+use crate::{env, Gas};
+
+/// Whether spans are recorded.
+const ENABLED: bool = cfg!(any(
+    feature = "gas-profiling",
+    all(feature = "unit-testing", not(target_arch = "wasm32"))
+));
+
+/// Gas used by a span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanReport {
+    /// Name of the span.
+    pub name: &'static str,
+    /// Index of the span it is nested in, in [`GasReport::spans`].
+    pub parent: Option<usize>,
+    /// Number of spans it is nested in.
+    pub depth: usize,
+    /// Number of times the span was entered.
+    pub calls: u32,
+    /// Total gas used, including nested spans.
+    pub gas: Gas,
+}
+
+/// Gas used by the spans of the current execution, in the order they were first entered.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasReport {
+    spans: Vec<SpanReport>,
+    /// Indices of the spans which are entered and not ended yet, innermost last.
+    open: Vec<usize>,
+}
+
+impl GasReport {
+    pub fn spans(&self) -> &[SpanReport] {
+        &self.spans
+    }
+
+    /// The first span with the given name.
+    pub fn get(&self, name: &str) -> Option<&SpanReport> {
+        self.spans.iter().find(|span| span.name == name)
+    }
+
+    fn enter(&mut self, name: &'static str) -> usize {
+        let parent = self.open.last().copied();
+        let index =
+            match self.spans.iter().position(|span| span.name == name && span.parent == parent) {
+                Some(index) => index,
+                None => {
+                    let depth = self.open.len();
+                    self.spans.push(SpanReport {
+                        name,
+                        parent,
+                        depth,
+                        calls: 0,
+                        gas: Gas::from_gas(0),
+                    });
+                    self.spans.len() - 1
+                }
+            };
+        self.open.push(index);
+        index
+    }
+
+    fn write_children(&self, f: &mut fmt::Formatter<'_>, parent: Option<usize>) -> fmt::Result {
+        for (index, span) in self.spans.iter().enumerate().filter(|(_, span)| span.parent == parent)
+        {
+            write!(f, "\n{:indent$}{}: {}", "", span.name, span.gas, indent = 2 * span.depth + 2)?;
+            if span.calls != 1 {
+                write!(f, " ({} calls)", span.calls)?;
+            }
+            self.write_children(f, Some(index))?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the report as `gas profile:` followed by a line per span, below its parent and
+/// indented by its depth.
+impl Display for GasReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("gas profile:")?;
+        self.write_children(f, None)
+    }
+}
+
+thread_local! {
+    static PROFILE: RefCell<GasReport> = RefCell::default();
+}
+
+/// Guard which records the gas used until it is dropped, created by [`gas_span!`](crate::gas_span).
+#[must_use = "the span ends when the guard is dropped"]
+pub struct GasSpan {
+    index: Option<usize>,
+    start: Gas,
+    method: bool,
+}
+
+impl GasSpan {
+    /// Enters a span with the given name.
+    pub fn enter(name: &'static str) -> Self {
+        if !ENABLED {
+            return Self { index: None, start: Gas::from_gas(0), method: false };
+        }
+        let index = PROFILE.with(|profile| profile.borrow_mut().enter(name));
+        Self { index: Some(index), start: env::used_gas(), method: false }
+    }
+
+    /// Enters the span of a method annotated with `#[gas_profile]`, which calls [`flush`] when it
+    /// ends.
+    #[doc(hidden)]
+    pub fn method(name: &'static str) -> Self {
+        let mut span = Self::enter(name);
+        span.method = true;
+        span
+    }
+}
+
+impl Drop for GasSpan {
+    fn drop(&mut self) {
+        let Some(index) = self.index else {
+            return;
+        };
+        let used = env::used_gas().saturating_sub(self.start);
+        PROFILE.with(|profile| {
+            let mut profile = profile.borrow_mut();
+            profile.open.retain(|&open| open != index);
+            let span = &mut profile.spans[index];
+            span.calls += 1;
+            span.gas = span.gas.saturating_add(used);
+        });
+        if self.method {
+            flush();
+        }
+    }
+}
+
+/// Logs the [`GasReport`] with the `gas-profiling` feature, which should be done at the end of the
+/// method. It is logged with [`env::log_str`], so it counts towards the limits on logs but isn't
+/// dropped or compiled out like the messages of [`logging`](crate::logging).
+pub fn flush() {
+    if cfg!(feature = "gas-profiling") {
+        let report = PROFILE.with(|profile| profile.borrow().to_string());
+        env::log_str(&report);
+    }
+}
+
+/// Gas used by the spans recorded since the current mocked blockchain was set up.
+#[cfg(all(not(target_arch = "wasm32"), feature = "unit-testing"))]
+pub fn report() -> GasReport {
+    PROFILE.with(|profile| profile.borrow().clone())
+}
+
+/// Clears the spans, for a new mocked blockchain.
+#[cfg(all(not(target_arch = "wasm32"), feature = "unit-testing"))]
+pub(crate) fn reset() {
+    PROFILE.with(|profile| *profile.borrow_mut() = GasReport::default());
+}
+
+/// Records the gas used by a block as a named span, see the [module documentation](crate::gas_profile).
+///
+/// `gas_span!("name", { ... })` evaluates to the value of the block, while `gas_span!("name")`
+/// returns a guard which ends the span when dropped.
+///
+/// ```
+/// use near_sdk::{env, gas_span};
+/// # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+///
+/// let value = gas_span!("read", { env::storage_read(b"key") });
+/// assert_eq!(value, None);
+/// assert_eq!(near_sdk::gas_profile::report().get("read").unwrap().calls, 1);
+/// ```
+#[macro_export]
+macro_rules! gas_span {
+    ($name:expr) => {
+        $crate::gas_profile::GasSpan::enter($name)
+    };
+    ($name:expr, $body:block) => {{
+        let _span = $crate::gas_profile::GasSpan::enter($name);
+        $body
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::VMContextBuilder;
+    use crate::testing_env;
+
+    #[test]
+    fn nested_spans() {
+        testing_env!(VMContextBuilder::new().build());
+        gas_span!("outer", {
+            for _ in 0..3 {
+                gas_span!("write", { env::storage_write(b"key", b"value") });
+            }
+            let _read = gas_span!("read");
+            env::storage_read(b"key");
+        });
+
+        let report = report();
+        let spans: Vec<_> =
+            report.spans().iter().map(|span| (span.name, span.parent, span.calls)).collect();
+        assert_eq!(spans, [("outer", None, 1), ("write", Some(0), 3), ("read", Some(0), 1)]);
+        let outer = report.get("outer").unwrap().gas;
+        let inner =
+            report.get("write").unwrap().gas.saturating_add(report.get("read").unwrap().gas);
+        assert!(inner > Gas::from_gas(0));
+        assert!(outer >= inner);
+    }
+
+    #[test]
+    fn same_name_in_other_parents() {
+        testing_env!(VMContextBuilder::new().build());
+        gas_span!("deposit", {
+            gas_span!("write", {});
+        });
+        gas_span!("withdraw", {
+            gas_span!("write", {});
+        });
+        gas_span!("deposit", {
+            gas_span!("read", {});
+        });
+
+        let report = report();
+        let spans: Vec<_> =
+            report.spans().iter().map(|span| (span.name, span.parent, span.calls)).collect();
+        assert_eq!(
+            spans,
+            [
+                ("deposit", None, 2),
+                ("write", Some(0), 1),
+                ("withdraw", None, 1),
+                ("write", Some(2), 1),
+                ("read", Some(0), 1)
+            ]
+        );
+        // Spans are rendered below their parent.
+        let rendered = report.to_string();
+        let names: Vec<_> =
+            rendered.lines().skip(1).map(|line| line.split(':').next().unwrap()).collect();
+        assert_eq!(names, ["  deposit", "    write", "    read", "  withdraw", "    write"]);
+    }
+
+    #[test]
+    #[cfg(feature = "gas-profiling")]
+    fn flush_logs_report() {
+        testing_env!(VMContextBuilder::new().build());
+        gas_span!("span", {});
+        flush();
+        let logs = crate::test_utils::get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].starts_with("gas profile:\n  span: "));
+    }
+
+    #[test]
+    fn reset_by_testing_env() {
+        testing_env!(VMContextBuilder::new().build());
+        gas_span!("span", {});
+        testing_env!(VMContextBuilder::new().build());
+        assert!(report().spans().is_empty());
+    }
+
+    #[test]
+    fn render() {
+        let report = GasReport {
+            spans: vec![
+                SpanReport {
+                    name: "deposit",
+                    parent: None,
+                    depth: 0,
+                    calls: 1,
+                    gas: Gas::from_tgas(3),
+                },
+                SpanReport {
+                    name: "transfer",
+                    parent: Some(0),
+                    depth: 1,
+                    calls: 2,
+                    gas: Gas::from_ggas(200),
+                },
+            ],
+            open: vec![],
+        };
+        assert_eq!(
+            report.to_string(),
+            "gas profile:\n  deposit: 3.0 Tgas\n    transfer: 0.200 Tgas (2 calls)"
+        );
+    }
+}
@@ -231,6 +231,44 @@ compile_error!(
 ///
 /// ### Reference to [Implementation of `#[near]` macro](near#implementation-of-near-macro-and-host-functions-calls-used) (How does it work?)
 ///
+/// ## `#[near(method_names)]` (annotates impl blocks)
+///
+/// Generates a `METHOD_NAMES` constant on the contract type with the names of the public change
+/// methods of the impl block, i.e. without views, initializers and `#[private]` methods. It is
+/// meant for the function call access keys scoped to the contract, see
+/// [`FunctionCallKeyPermission`]. As the constant is defined on the contract type, only one impl
+/// block of a type can be annotated. [`ext_contract`] generates the same constant for the traits
+/// of other contracts with `#[ext_contract(mod_name, method_names)]`.
+///
+/// ### Basic example
+/// ```rust
+/// use near_sdk::{env, near, FunctionCallKeyPermission};
+///
+/// # #[near(contract_state)]
+/// # #[derive(Default)]
+/// # pub struct Contract {
+/// #     moves: u32,
+/// # }
+/// #[near(method_names)]
+/// impl Contract {
+///     pub fn play(&mut self) {
+///         self.moves += 1;
+///     }
+///
+///     pub fn moves(&self) -> u32 {
+///         self.moves
+///     }
+/// }
+///
+/// assert_eq!(Contract::METHOD_NAMES, ["play"]);
+///
+/// fn session_key_permission() -> FunctionCallKeyPermission {
+///     FunctionCallKeyPermission::new(env::current_account_id())
+///         .with_method_names(Contract::METHOD_NAMES)
+///         .unwrap()
+/// }
+/// ```
+///
 /// ## `#[near(serializers=[...])` (annotates structs/enums)
 ///
 /// The attribute makes the struct or enum serializable with either json or borsh. By default, borsh is used.
@@ -590,6 +628,208 @@ compile_error!(
 /// }
 /// ```
 ///
+/// ## `#[access_control(...)]` and `#[only(owner)]` (annotate methods of a type in its `impl` block)
+///
+/// `#[access_control(role = "Admin")]` panics unless the predecessor is a member of the role, and
+/// `#[only(owner)]` unless it is the owner of the contract. Several roles can be listed, of which
+/// the predecessor needs any. The contract returns its owner and roles by implementing
+/// [`AccessControllable`](crate::access_control::AccessControllable), usually with the
+/// [`near_contract_standards::access_control::AccessControl`](https://docs.rs/near-contract-standards/latest/near_contract_standards/access_control/struct.AccessControl.html)
+/// component. Only methods which take `&mut self` can be annotated. The ABI has no field for the
+/// required roles, so they are appended to the documentation of the method as
+/// `Required roles: ...`.
+///
+/// ```rust,ignore
+/// #[near]
+/// impl Contract {
+///     #[access_control(role = "Admin", role = "Minter")]
+///     pub fn mint(&mut self, amount: U128) {
+///         // ...
+///     }
+///
+///     #[only(owner)]
+///     pub fn add_minter(&mut self, account_id: AccountId) {
+///         self.acl.grant_role("Minter", account_id);
+///     }
+/// }
+/// ```
+///
+/// ## `#[pause(...)]` (annotates methods of a type in its `impl` block)
+///
+/// Panics if the feature of the method is paused. The contract returns its paused features by
+/// implementing [`PausableContract`](crate::pausable::PausableContract), usually with the
+/// [`near_contract_standards::pausable::Pausable`](https://docs.rs/near-contract-standards/latest/near_contract_standards/pausable/struct.Pausable.html)
+/// component, whose `impl_pausable!` macro also exports methods to pause, unpause and list the
+/// paused features. The feature is named
+/// after the method unless set with `feature = "..."`, and `except(roles = [...])` lets members of
+/// the roles call the method while it is paused. The check is done by the exported function, before
+/// the arguments are deserialized, and only methods which take `&mut self` can be annotated.
+///
+/// ```rust,ignore
+/// #[near]
+/// impl Contract {
+///     #[payable]
+///     #[pause(feature = "deposits", except(roles = ["Admin"]))]
+///     pub fn deposit(&mut self) {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// ## `#[lock("...")]` (annotates methods of a type in its `impl` block)
+///
+/// Takes the named [`CallbackLock`] before the body of the method runs, panicking if it is already
+/// held, so that methods using the same lock fail fast between the scheduling of a cross-contract
+/// call and its callback. On a `#[private]` method, which is how callbacks are usually marked, the
+/// attribute releases the lock instead. Only call methods can be annotated.
+///
+/// The lock can only be taken by methods returning a `Promise` or a `TypedPromise`, since other
+/// methods schedule no callback to release it. Methods which should only fail while the lock is
+/// held are annotated with `#[lock("...", check)]`, which panics if the lock is held without
+/// taking it.
+///
+/// ```rust
+/// use near_sdk::{env, near, CallbackLock, Gas, NearToken, Promise, PromiseError};
+///
+/// # #[near(contract_state)]
+/// # #[derive(Default)]
+/// # pub struct Contract { borrowed: u128 }
+/// #[near]
+/// impl Contract {
+///     #[lock("loan")]
+///     pub fn borrow(&mut self, amount: u128) -> Promise {
+///         Promise::new("oracle.near".parse().unwrap())
+///             .function_call("price".to_string(), vec![], NearToken::from_near(0), Gas::from_tgas(5))
+///             .then(Self::ext(env::current_account_id()).on_borrow(amount))
+///     }
+///
+///     #[private]
+///     #[lock("loan")]
+///     pub fn on_borrow(&mut self, amount: u128, #[callback_result] price: Result<u128, PromiseError>) {
+///         if price.is_ok() {
+///             self.borrowed += amount;
+///         }
+///     }
+///
+///     #[lock("loan", check)]
+///     pub fn repay(&mut self, amount: u128) {
+///         self.borrowed -= amount;
+///     }
+/// }
+///
+/// # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+/// let mut contract = Contract::default();
+/// contract.borrow(10);
+/// assert!(CallbackLock::new("loan").is_held());
+/// contract.on_borrow(10, Ok(1));
+/// assert!(!CallbackLock::new("loan").is_held());
+/// contract.repay(10);
+/// assert!(!CallbackLock::new("loan").is_held());
+/// ```
+///
+/// ## `#[near(contract_state, version = N)]` and `#[migrate(...)]` (annotate structs/enums and methods)
+///
+/// `version = N` implements [`VersionedState`] for the state. Previous versions of the state are
+/// declared with `#[near(version = N)]` alone, and state written before the contract was
+/// versioned counts as version `0`.
+///
+/// The methods of the contract use the version in every impl block, including those generated by
+/// other macros: init methods store it alongside the state, see [`env::state_version`], and the
+/// other methods panic before reading a state of another version, which must be migrated first.
+/// Methods of contracts declared without `version = N` don't read or write a version.
+///
+/// Functions annotated with `#[migrate(from = "ContractV1")]` take the previous state and return
+/// the next one, which is either the contract or the `from` of another migration. The macro chains
+/// them into a private `migrate` init method, which reads the state of the stored version, runs the
+/// migrations up to the contract and stores the new version. It panics if the stored version is
+/// not the version of any `from` state, e.g. when the state is already migrated. The migrations
+/// must be in a single inherent `impl` block and aren't exported themselves.
+///
+/// ```rust
+/// use near_sdk::{env, near, PanicOnDefault};
+///
+/// #[near(version = 1)]
+/// pub struct ContractV1 {
+///     count: u32,
+/// }
+///
+/// #[near(version = 2)]
+/// pub struct ContractV2 {
+///     count: u64,
+/// }
+///
+/// #[near(contract_state, version = 3)]
+/// #[derive(PanicOnDefault)]
+/// pub struct Contract {
+///     count: u64,
+///     owner: String,
+/// }
+///
+/// #[near]
+/// impl Contract {
+///     #[init]
+///     pub fn new(owner: String) -> Self {
+///         Self { count: 0, owner }
+///     }
+///
+///     pub fn increment(&mut self) {
+///         self.count += 1;
+///     }
+///
+///     #[migrate(from = "ContractV1")]
+///     fn from_v1(old: ContractV1) -> ContractV2 {
+///         ContractV2 { count: old.count.into() }
+///     }
+///
+///     #[migrate(from = "ContractV2")]
+///     fn from_v2(old: ContractV2) -> Self {
+///         Self { count: old.count, owner: "alice.near".to_string() }
+///     }
+/// }
+///
+/// # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new()
+/// #     .predecessor_account_id(near_sdk::test_utils::test_env::alice())
+/// #     .build());
+/// env::state_write(&ContractV1 { count: 3 });
+/// env::state_version_write(1);
+/// let contract = Contract::migrate();
+/// assert_eq!(contract.count, 3);
+///
+/// env::state_version_write(3);
+/// let result = std::panic::catch_unwind(|| Contract::migrate());
+/// assert!(result.is_err());
+/// ```
+///
+/// ## `#[gas_profile]` (annotates methods of a type in its `impl` block)
+///
+/// Records the gas used by the whole method as a span named after it, see
+/// [`gas_profile`](crate::gas_profile). Spans entered with [`gas_span!`](crate::gas_span) within
+/// the method are nested in it. With the `gas-profiling` feature, the report is logged when the
+/// method returns.
+///
+/// ```rust
+/// use near_sdk::{env, gas_span, near};
+///
+/// # #[near(contract_state)]
+/// # #[derive(Default)]
+/// # pub struct Contract {}
+/// #[near]
+/// impl Contract {
+///     #[gas_profile]
+///     pub fn store(&mut self, values: Vec<String>) {
+///         for value in values {
+///             gas_span!("write", { env::storage_write(value.as_bytes(), b"") });
+///         }
+///     }
+/// }
+///
+/// # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+/// Contract::default().store(vec!["a".to_string(), "b".to_string()]);
+/// let report = near_sdk::gas_profile::report();
+/// assert_eq!(report.get("store").unwrap().calls, 1);
+/// assert_eq!(report.get("write").unwrap().calls, 2);
+/// ```
+///
 /// ## `#[callback_unwrap]` (annotates function arguments)
 ///
 /// Automatically unwraps the successful result of a callback from a cross-contract call.
@@ -678,6 +918,38 @@ compile_error!(
 ///
 /// ### Reference to  [Implementation of `#[callback_unwrap]` attribute](near#implementation-of-callback_unwrap-attribute-and-host-functions-calls-used)
 ///
+/// ## `#[yield_result]` (annotates function arguments)
+///
+/// Reads the payload of a promise created with [`Promise::yield_on`] in its callback. The argument
+/// should have type `Result<T, PromiseError>`: it is `Ok` with the payload passed to
+/// [`Promise::resume`] or `Err` if the promise timed out. It can't be combined with other
+/// callback arguments, and the method panics if it isn't called with exactly one promise result.
+///
+/// ```rust
+/// use near_sdk::{near, PromiseError};
+///
+/// # #[near(contract_state)]
+/// # #[derive(Default)]
+/// # pub struct Contract {}
+/// #[near]
+/// impl Contract {
+///     #[private]
+///     pub fn on_signature(
+///         &mut self,
+///         request_id: u64,
+///         #[yield_result] signature: Result<String, PromiseError>,
+///     ) -> String {
+///         match signature {
+///             Ok(signature) => signature,
+///             Err(_) => format!("Request {request_id} timed out"),
+///         }
+///     }
+/// }
+/// ```
+///
+/// The callback can be tested by passing `test_utils::yield_resumed` or
+/// `test_utils::yield_timed_out` as the promise results of `testing_env!`.
+///
 /// ## `#[near(event_json(...))]` (annotates enums)
 ///
 /// By passing `event_json` as an argument `near` will generate the relevant code to format events
@@ -920,12 +1192,13 @@ pub use near_sdk_macros::near_bindgen;
 
 /// `ext_contract` takes a Rust Trait and converts it to a module with static methods.
 /// Each of these static methods takes positional arguments defined by the Trait,
-/// then the receiver_id, the attached deposit and the amount of gas and returns a new Promise.
+/// then the receiver_id, the attached deposit and the amount of gas and returns a new
+/// [`TypedPromise`] of the value returned by the method.
 ///
 /// ## Examples
 ///
 /// ```rust
-/// use near_sdk::{AccountId,ext_contract, near, Promise, Gas};
+/// use near_sdk::{AccountId,ext_contract, near, Gas, TypedPromise};
 ///
 /// #[near(contract_state)]
 /// struct Contract {
@@ -940,7 +1213,7 @@ pub use near_sdk_macros::near_bindgen;
 ///
 /// #[near]
 /// impl Contract {
-///    pub fn multiply_by_five(&mut self, number: u64) -> Promise {
+///    pub fn multiply_by_five(&mut self, number: u64) -> TypedPromise<u128> {
 ///        ext_calculator::ext(self.calculator_account.clone())
 ///            .with_static_gas(Gas::from_tgas(5))
 ///            .mult(number, 5)
@@ -949,6 +1222,57 @@ pub use near_sdk_macros::near_bindgen;
 ///
 /// ```
 ///
+/// ### Default gas
+///
+/// Methods can declare the gas attached to their calls by default with
+/// `#[gas(static = "5 Tgas", weight = 1)]`, where `static` is an amount like `"5 Tgas"`,
+/// `"100 Ggas"` or `"1000 gas"` and `weight` is the weight of the unused gas, see
+/// [`Promise::function_call_weight`]. Either can be omitted, in which case no static gas and a
+/// weight of 1 are used. `with_static_gas` and `with_unused_gas_weight` still override the
+/// defaults. The same attribute can be used on the methods of a `#[near]` impl block.
+///
+/// ```rust
+/// use near_sdk::{ext_contract, near, AccountId, Gas, Promise, TypedPromise};
+///
+/// #[ext_contract(ext_calculator)]
+/// trait Calculator {
+///     #[gas(static = "5 Tgas", weight = 0)]
+///     fn mult(&self, a: u64, b: u64) -> u128;
+/// }
+///
+/// const CALLBACK_GAS: Gas = Gas::from_tgas(10);
+/// const SCHEDULING_GAS: Gas = Gas::from_tgas(10);
+///
+/// #[near(contract_state)]
+/// struct Contract {
+///     calculator_account: AccountId,
+/// }
+///
+/// #[near]
+/// impl Contract {
+///     pub fn multiply_by_five(&mut self, number: u64) -> TypedPromise<u128> {
+///         ext_calculator::ext(self.calculator_account.clone())
+///             // All of the gas left after reserving enough for the callback.
+///             .with_static_gas(Promise::remaining_gas(CALLBACK_GAS.saturating_add(SCHEDULING_GAS)))
+///             .mult(number, 5)
+///             .then(Self::ext(near_sdk::env::current_account_id()).on_mult())
+///     }
+///
+///     #[private]
+///     #[gas(static = "10 Tgas")]
+///     pub fn on_mult(&self, #[callback_unwrap] product: u128) -> u128 {
+///         product
+///     }
+/// }
+/// ```
+///
+/// ### Method names
+///
+/// `#[ext_contract(mod_name, method_names)]` also generates a `METHOD_NAMES` constant in the
+/// module with the names of the public change methods of the trait, i.e. those taking `&mut self`
+/// and not `#[private]`, for the function call access keys scoped to the contract, see
+/// [`FunctionCallKeyPermission`].
+///
 /// See more information about role of ext_contract in [NEAR documentation](https://docs.near.org/build/smart-contracts/anatomy/crosscontract)
 pub use near_sdk_macros::ext_contract;
 
@@ -1069,8 +1393,26 @@ pub use environment::env;
 #[cfg(feature = "unstable")]
 pub use near_sys as sys;
 
+pub mod logging;
+
+pub mod gas_profile;
+
+mod callback_lock;
+pub use callback_lock::CallbackLock;
+
+pub mod access_control;
+
+pub mod pausable;
+
 mod promise;
-pub use promise::{Allowance, Promise, PromiseOrValue};
+pub use promise::{
+    join_all, serializer, Allowance, FunctionCallKeyPermission, KeyPermissionError, Promise,
+    PromiseActionView, PromiseCallback, PromiseJoin, PromiseJoin2, PromiseJoinAll, PromiseOrValue,
+    PromiseView, TypedCallback, TypedPromise, YieldId, YieldedPromise,
+};
+
+#[cfg(feature = "refund-on-failure")]
+pub mod refund;
 
 // Private types just used within macro generation, not stable to be used.
 #[doc(hidden)]
@@ -1079,6 +1421,8 @@ pub mod __private;
 
 pub mod json_types;
 
+pub mod crypto;
+
 mod types;
 pub use crate::types::*;
 
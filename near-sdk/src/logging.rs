@@ -0,0 +1,324 @@
+//! Leveled logging with key/value fields, which keeps within the protocol limits on logs.
+//!
+//! ```
+//! use near_sdk::logging::{self, debug, info, warn};
+//! # near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+//!
+//! let amount = 10;
+//! info!("transfer"; amount = amount, receiver_id = "bob.near");
+//! debug!("balance is {}", 90);
+//! warn!("low balance"; note = "top up soon");
+//! logging::flush();
+//!
+//! assert_eq!(
+//!     near_sdk::test_utils::get_logs(),
+//!     [
+//!         "INFO transfer amount=10 receiver_id=bob.near",
+//!         "DEBUG balance is 90",
+//!         "WARN low balance note=\"top up soon\"",
+//!     ]
+//! );
+//! ```
+//!
+//! A receipt can emit at most [`MAX_LOGS`] logs of [`MAX_TOTAL_LOG_LEN`] bytes in total, and
+//! exceeding either fails the receipt. Instead, messages are truncated to the bytes left and then
+//! dropped, as are messages which would leave less than the [gas reserve](set_gas_reserve)
+//! unused. The number of dropped messages is logged by [`flush`], for which a log and
+//! [`SUMMARY_RESERVE`] bytes are kept, and which the `#[near]` method wrappers call when the method
+//! returns. Logs emitted with [`env::log_str`](crate::env::log_str) count towards the limits, but
+//! are never dropped.
+//!
+//! Levels above [`MAX_LEVEL`] are compiled out. It is set with the `logging-max-level-off`,
+//! `logging-max-level-error`, `logging-max-level-warn`, `logging-max-level-info` and
+//! `logging-max-level-debug` features, of which the lowest one applies. All levels are enabled
+//! without them.
+
+use std::cell::Cell;
+use std::fmt::{self, Display, Write};
+
+use crate::{env, Gas};
+
+/// Maximum number of logs of a receipt.
+pub const MAX_LOGS: usize = 100;
+/// Maximum total length of the logs of a receipt, in bytes.
+pub const MAX_TOTAL_LOG_LEN: usize = 16 * 1024;
+/// Bytes kept for the summary logged by [`flush`].
+pub const SUMMARY_RESERVE: usize = 32;
+
+/// Gas cost of logging a message, apart from its bytes.
+const LOG_BASE_GAS: u64 = 3_111_779_061 + 3_543_313_050;
+/// Gas cost of logging a byte of a message.
+const LOG_BYTE_GAS: u64 = 291_580_479 + 13_198_791;
+/// Messages shorter than this are dropped rather than truncated.
+const MIN_TRUNCATED_LEN: usize = 16;
+
+/// Level of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Most verbose level which is not compiled out, `None` if logging is disabled.
+pub const MAX_LEVEL: Option<Level> = if cfg!(feature = "logging-max-level-off") {
+    None
+} else if cfg!(feature = "logging-max-level-error") {
+    Some(Level::Error)
+} else if cfg!(feature = "logging-max-level-warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "logging-max-level-info") {
+    Some(Level::Info)
+} else if cfg!(feature = "logging-max-level-debug") {
+    Some(Level::Debug)
+} else {
+    Some(Level::Trace)
+};
+
+impl Level {
+    /// Whether messages of the level are logged, see [`MAX_LEVEL`].
+    pub const fn enabled(self) -> bool {
+        match MAX_LEVEL {
+            Some(max) => self as u8 <= max as u8,
+            None => false,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct LogState {
+    logs: usize,
+    len: usize,
+    dropped: usize,
+    gas_reserve: Gas,
+}
+
+const INITIAL_STATE: LogState =
+    LogState { logs: 0, len: 0, dropped: 0, gas_reserve: Gas::from_tgas(5) };
+
+thread_local! {
+    static STATE: Cell<LogState> = const { Cell::new(INITIAL_STATE) };
+}
+
+fn update<R>(f: impl FnOnce(&mut LogState) -> R) -> R {
+    STATE.with(|state| {
+        let mut value = state.get();
+        let result = f(&mut value);
+        state.set(value);
+        result
+    })
+}
+
+/// Records a log of `len` bytes emitted through `env`.
+pub(crate) fn record(len: usize) {
+    update(|state| {
+        state.logs += 1;
+        state.len += len;
+    })
+}
+
+/// Resets the counts, for a new mocked blockchain.
+#[cfg(all(not(target_arch = "wasm32"), feature = "unit-testing"))]
+pub(crate) fn reset() {
+    STATE.with(|state| state.set(INITIAL_STATE));
+}
+
+/// Sets the amount of gas which messages are dropped rather than use, 5 Tgas by default.
+pub fn set_gas_reserve(gas: Gas) {
+    update(|state| state.gas_reserve = gas)
+}
+
+/// Number of messages dropped so far.
+pub fn dropped() -> usize {
+    STATE.with(|state| state.get().dropped)
+}
+
+/// Logs a message of the given level with the limits applied. Prefer the macros, which skip
+/// formatting the message if the level is compiled out.
+pub fn log(level: Level, message: &str) {
+    log_fields(level, format_args!("{message}"), &[]);
+}
+
+/// Logs a message with key/value fields, rendered as `LEVEL message key=value`. Values which
+/// contain spaces, `=` or quotes are quoted.
+pub fn log_fields(level: Level, message: fmt::Arguments<'_>, fields: &[(&str, &dyn Display)]) {
+    if !level.enabled() {
+        return;
+    }
+    let mut line = format!("{level} {message}");
+    let mut value = String::new();
+    for (key, field) in fields {
+        value.clear();
+        let _ = write!(value, "{field}");
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+            let _ = write!(line, " {key}={value:?}");
+        } else {
+            let _ = write!(line, " {key}={value}");
+        }
+    }
+    emit(line);
+}
+
+fn emit(mut line: String) {
+    let state = STATE.with(Cell::get);
+    let left = MAX_TOTAL_LOG_LEN.saturating_sub(state.len + SUMMARY_RESERVE);
+    if state.logs + 1 >= MAX_LOGS || left < MIN_TRUNCATED_LEN {
+        return update(|state| state.dropped += 1);
+    }
+    if line.len() > left {
+        let mut end = left - "...".len();
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        line.truncate(end);
+        line.push_str("...");
+    }
+    let cost = Gas::from_gas(LOG_BASE_GAS + LOG_BYTE_GAS * line.len() as u64);
+    let gas_left = env::prepaid_gas().saturating_sub(env::used_gas());
+    if gas_left < state.gas_reserve.saturating_add(cost) {
+        return update(|state| state.dropped += 1);
+    }
+    env::log_str(&line);
+}
+
+/// Logs `WARN N logs dropped` if messages were dropped, and does nothing otherwise. It is called
+/// by the `#[near]` method wrappers when the method returns.
+pub fn flush() {
+    let dropped = update(|state| std::mem::take(&mut state.dropped));
+    if dropped > 0 {
+        env::log_str(&format!("{} {dropped} logs dropped", Level::Warn));
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_log {
+    ($level:expr, $fmt:literal $(, $arg:expr)* $(; $($key:ident = $value:expr),+ $(,)?)?) => {
+        if $level.enabled() {
+            $crate::logging::log_fields(
+                $level,
+                ::std::format_args!($fmt $(, $arg)*),
+                &[$($((::std::stringify!($key), &$value as &dyn ::std::fmt::Display)),+)?],
+            )
+        }
+    };
+}
+
+/// Logs an error, see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_error {
+    ($($args:tt)+) => { $crate::__logging_log!($crate::logging::Level::Error, $($args)+) };
+}
+
+/// Logs a warning, see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_warn {
+    ($($args:tt)+) => { $crate::__logging_log!($crate::logging::Level::Warn, $($args)+) };
+}
+
+/// Logs an informational message, see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_info {
+    ($($args:tt)+) => { $crate::__logging_log!($crate::logging::Level::Info, $($args)+) };
+}
+
+/// Logs a debug message, see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_debug {
+    ($($args:tt)+) => { $crate::__logging_log!($crate::logging::Level::Debug, $($args)+) };
+}
+
+/// Logs a trace message, see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __logging_trace {
+    ($($args:tt)+) => { $crate::__logging_log!($crate::logging::Level::Trace, $($args)+) };
+}
+
+#[doc(inline)]
+pub use crate::{
+    __logging_debug as debug, __logging_error as error, __logging_info as info,
+    __logging_log as log, __logging_trace as trace, __logging_warn as warn,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_logs, VMContextBuilder};
+    use crate::testing_env;
+
+    #[test]
+    fn fields() {
+        testing_env!(VMContextBuilder::new().build());
+        let account_id: crate::AccountId = "alice.near".parse().unwrap();
+        info!("transfer of {} NEAR", 5; from = account_id, memo = "", note = "a=b");
+        error!("failed");
+        assert_eq!(
+            get_logs(),
+            ["INFO transfer of 5 NEAR from=alice.near memo=\"\" note=\"a=b\"", "ERROR failed"]
+        );
+        // Nothing was dropped.
+        flush();
+        assert_eq!(get_logs().len(), 2);
+    }
+
+    #[test]
+    fn count_limit() {
+        testing_env!(VMContextBuilder::new().build());
+        for i in 0..MAX_LOGS + 10 {
+            info!("message {}", i);
+        }
+        assert_eq!(dropped(), 11);
+        flush();
+        let logs = get_logs();
+        assert_eq!(logs.len(), MAX_LOGS);
+        assert_eq!(logs.last().unwrap(), "WARN 11 logs dropped");
+        assert_eq!(dropped(), 0);
+    }
+
+    #[test]
+    fn length_limit() {
+        testing_env!(VMContextBuilder::new().build());
+        env::log_str(&"a".repeat(MAX_TOTAL_LOG_LEN - SUMMARY_RESERVE - 100));
+        debug!("{}", "é".repeat(100));
+        debug!("dropped");
+        flush();
+        let logs = get_logs();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[1].len(), 99);
+        assert!(logs[1].starts_with("DEBUG éé") && logs[1].ends_with("é..."));
+        assert_eq!(logs[2], "WARN 1 logs dropped");
+    }
+
+    #[test]
+    fn gas_reserve() {
+        testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(5)).build());
+        warn!("dropped");
+        set_gas_reserve(Gas::from_gas(0));
+        warn!("logged");
+        assert_eq!(get_logs(), ["WARN logged"]);
+        assert_eq!(dropped(), 1);
+    }
+}
@@ -9,6 +9,12 @@
 //! * `private`
 //! * `deny_unknown_arguments`
 //! * `handle_result`
+//! * `gas_profile`
+//! * `access_control`
+//! * `only`
+//! * `pause`
+//! * `lock`
+//! * `migrate`
 //! * `callback_unwrap`
 //! * `event_json`
 //! * `contract_metadata`
@@ -18,6 +24,8 @@
 //! Following attributes are only part of the `near` macro:
 //! * `serializers`
 //! * `contract_state`
+//! * `method_names`
+//! * `version`
 
 /// See [`near_sdk::near #[init]`](crate::near#init-annotates-methods-of-a-type-in-its-impl-block)
 pub fn init() {}
@@ -37,6 +45,24 @@ pub fn result_serializer() {}
 /// See [`near_sdk::near #[handle_result]`](crate::near#handle_result-annotates-methods-of-a-type-in-its-impl-block)
 pub fn handle_result() {}
 
+/// See [`near_sdk::near #[gas_profile]`](crate::near#gas_profile-annotates-methods-of-a-type-in-its-impl-block)
+pub fn gas_profile() {}
+
+/// See [`near_sdk::near #[access_control(...)]`](crate::near#access_control-and-onlyowner-annotate-methods-of-a-type-in-its-impl-block)
+pub fn access_control() {}
+
+/// See [`near_sdk::near #[only(owner)]`](crate::near#access_control-and-onlyowner-annotate-methods-of-a-type-in-its-impl-block)
+pub fn only() {}
+
+/// See [`near_sdk::near #[pause(...)]`](crate::near#pause-annotates-methods-of-a-type-in-its-impl-block)
+pub fn pause() {}
+
+/// See [`near_sdk::near #[lock(...)]`](crate::near#lock-annotates-methods-of-a-type-in-its-impl-block)
+pub fn lock() {}
+
+/// See [`near_sdk::near #[migrate(...)]`](crate::near#nearcontract_state-version--n-and-migrate-annotate-structsenums-and-methods)
+pub fn migrate() {}
+
 /// See [`near_sdk::near #[callback_unwrap]`](crate::near#callback_unwrap-annotates-function-arguments)
 pub fn callback_unwrap() {}
 
@@ -54,3 +80,6 @@ pub fn serializers() {}
 
 /// See [`near_sdk::near #[near(contract_state)]`](crate::near#nearcontract_state-annotates-structsenums)
 pub fn contract_state() {}
+
+/// See [`near_sdk::near #[near(method_names)]`](crate::near#nearmethod_names-annotates-impl-blocks)
+pub fn method_names() {}
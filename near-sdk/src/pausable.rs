@@ -0,0 +1,37 @@
+//! Checks made by the
+//! [`#[pause(...)]`](crate::near#pause-annotates-methods-of-a-type-in-its-impl-block) method
+//! attribute.
+//!
+//! A contract implements [`PausableContract`] to return the component storing its paused
+//! features, such as `near_contract_standards::pausable::Pausable`, which implements
+//! [`PauseCheck`].
+
+use crate::access_control::AccessCheck;
+use crate::env;
+
+/// Paused features of a contract.
+pub trait PauseCheck {
+    /// Whether the feature is paused.
+    fn is_paused(&self, feature: &str) -> bool;
+
+    /// Panics if the feature is paused.
+    fn assert_not_paused(&self, feature: &str) {
+        if self.is_paused(feature) {
+            env::panic_str(&format!("Feature {feature} is paused"));
+        }
+    }
+
+    /// Panics if the feature is paused, unless the predecessor has any of the roles.
+    fn assert_not_paused_except(&self, feature: &str, acl: &impl AccessCheck, roles: &[&str]) {
+        if self.is_paused(feature) && !acl.has_any_role(roles, &env::predecessor_account_id()) {
+            env::panic_str(&format!("Feature {feature} is paused"));
+        }
+    }
+}
+
+/// Contract whose methods are checked by `#[pause]`.
+pub trait PausableContract {
+    type Pausable: PauseCheck;
+
+    fn pausable(&self) -> &Self::Pausable;
+}
@@ -5,12 +5,46 @@ pub use near_abi::{
     AbiBorshParameter, AbiFunction, AbiFunctionKind, AbiFunctionModifier, AbiJsonParameter,
     AbiParameters, AbiType,
 };
-#[cfg(feature = "abi")]
 mod result_type_ext;
 
-#[cfg(feature = "abi")]
 pub use result_type_ext::ResultTypeExt;
 
+/// Version of the contract state, implemented by `#[near(contract_state)]` and `#[near_bindgen]`
+/// for every contract: `Some(N)` for a state declared with `version = N`, `None` otherwise.
+pub trait ContractStateVersion {
+    const STATE_VERSION: Option<u32>;
+}
+
+/// Panics if the stored state of the contract `T` has another version, checked by the method
+/// wrappers before reading the state. Does nothing for unversioned contracts. A state which
+/// doesn't exist yet is created with `Default`, so call methods pass `write_missing` to store its
+/// version.
+pub fn check_state_version<T: ContractStateVersion>(write_missing: bool) {
+    let Some(expected) = T::STATE_VERSION else {
+        return;
+    };
+    match crate::env::state_version() {
+        Some(version) if version == expected => {}
+        None if !crate::env::state_exists() => {
+            if write_missing {
+                crate::env::state_version_write(expected);
+            }
+        }
+        version => crate::env::panic_str(&format!(
+            "The contract state has version {}, expected {}",
+            version.unwrap_or(0),
+            expected
+        )),
+    }
+}
+
+/// Stores the version of the contract `T` alongside the state written by an init method.
+pub fn write_state_version<T: ContractStateVersion>() {
+    if let Some(version) = T::STATE_VERSION {
+        crate::env::state_version_write(version);
+    }
+}
+
 use crate::IntoStorageKey;
 use borsh::{to_vec, BorshSerialize};
 
@@ -43,3 +77,54 @@ where
         to_vec(&self).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_state_version, write_state_version, ContractStateVersion};
+    use crate::env;
+
+    struct Contract;
+
+    impl ContractStateVersion for Contract {
+        const STATE_VERSION: Option<u32> = Some(2);
+    }
+
+    struct UnversionedContract;
+
+    impl ContractStateVersion for UnversionedContract {
+        const STATE_VERSION: Option<u32> = None;
+    }
+
+    #[test]
+    fn check_state_version_writes_missing() {
+        check_state_version::<Contract>(false);
+        assert_eq!(env::state_version(), None);
+        check_state_version::<Contract>(true);
+        assert_eq!(env::state_version(), Some(2));
+        check_state_version::<Contract>(false);
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract state has version 0, expected 2")]
+    fn check_state_version_of_unversioned_state() {
+        env::state_write(&0u8);
+        check_state_version::<Contract>(true);
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract state has version 1, expected 2")]
+    fn check_state_version_of_previous_version() {
+        env::state_version_write(1);
+        check_state_version::<Contract>(false);
+    }
+
+    #[test]
+    fn unversioned_contract() {
+        env::state_write(&0u8);
+        check_state_version::<UnversionedContract>(true);
+        write_state_version::<UnversionedContract>();
+        assert_eq!(env::state_version(), None);
+        write_state_version::<Contract>();
+        assert_eq!(env::state_version(), Some(2));
+    }
+}
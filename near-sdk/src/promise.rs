@@ -4,11 +4,15 @@ use std::cell::RefCell;
 #[cfg(feature = "abi")]
 use std::collections::BTreeMap;
 use std::io::{Error, Write};
+use std::marker::PhantomData;
 use std::num::NonZeroU128;
 use std::rc::Rc;
 
 use crate::env::migrate_to_allowance;
-use crate::{AccountId, Gas, GasWeight, NearToken, PromiseIndex, PublicKey};
+use crate::json_types::{Base58CryptoHash, Base64VecU8};
+use crate::{
+    near, serde_json, AccountId, CryptoHash, Gas, GasWeight, NearToken, PromiseIndex, PublicKey,
+};
 
 /// Allow an access key to spend either an unlimited or limited amount of gas
 // This wrapper prevents incorrect construction
@@ -29,11 +33,153 @@ impl Allowance {
     }
 }
 
+/// Permission of a function call access key: the contract the key can call, the methods it can
+/// call on it and the amount of NEAR it can spend on gas. It is checked against the protocol
+/// limits when it is built, instead of failing the receipt which adds the key.
+///
+/// The method names can be taken from the `METHOD_NAMES` constant generated by
+/// [`#[ext_contract(mod_name, method_names)]`](crate::ext_contract) and `#[near(method_names)]`,
+/// which lists the change methods only.
+///
+/// ```
+/// # use near_sdk::{ext_contract, FunctionCallKeyPermission, NearToken, Promise, PublicKey};
+/// #[ext_contract(ext_game, method_names)]
+/// trait Game {
+///     fn play(&mut self, position: u8);
+///     fn resign(&mut self);
+///     fn board(&self) -> Vec<u8>;
+/// }
+///
+/// assert_eq!(ext_game::METHOD_NAMES, ["play", "resign"]);
+///
+/// # fn add_session_key(public_key: PublicKey) -> Result<(), near_sdk::KeyPermissionError> {
+/// let permission = FunctionCallKeyPermission::new("game.near".parse().unwrap())
+///     .with_method_names(ext_game::METHOD_NAMES)?
+///     .with_allowance(NearToken::from_millinear(250))?;
+/// Promise::new("alice.near".parse().unwrap()).add_function_call_key(public_key, permission);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCallKeyPermission {
+    receiver_id: AccountId,
+    method_names: Vec<String>,
+    allowance: Option<NearToken>,
+}
+
+impl FunctionCallKeyPermission {
+    /// Maximum length of a method name, in bytes.
+    pub const MAX_METHOD_NAME_LEN: usize = 256;
+    /// Maximum total length of the method names, in bytes, counting a terminating byte per name.
+    pub const MAX_METHOD_NAMES_LEN: usize = 2000;
+
+    /// Permission to call any method of `receiver_id`, with an unlimited allowance.
+    pub fn new(receiver_id: AccountId) -> Self {
+        Self { receiver_id, method_names: Vec::new(), allowance: None }
+    }
+
+    /// Restricts the key to the given methods. An empty list allows all methods.
+    pub fn with_method_names<I>(mut self, method_names: I) -> Result<Self, KeyPermissionError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let method_names: Vec<String> =
+            method_names.into_iter().map(|name| name.as_ref().to_owned()).collect();
+        for name in &method_names {
+            if name.is_empty() || name.contains(',') {
+                return Err(KeyPermissionError::InvalidMethodName(name.clone()));
+            }
+            if name.len() > Self::MAX_METHOD_NAME_LEN {
+                return Err(KeyPermissionError::MethodNameTooLong(name.clone()));
+            }
+        }
+        let len = method_names.iter().map(|name| name.len() + 1).sum::<usize>();
+        if len > Self::MAX_METHOD_NAMES_LEN {
+            return Err(KeyPermissionError::MethodNamesTooLong(len));
+        }
+        self.method_names = method_names;
+        Ok(self)
+    }
+
+    /// Limits the amount of NEAR the key can spend on gas, which has to be more than zero.
+    pub fn with_allowance(mut self, allowance: NearToken) -> Result<Self, KeyPermissionError> {
+        if allowance.is_zero() {
+            return Err(KeyPermissionError::ZeroAllowance);
+        }
+        self.allowance = Some(allowance);
+        Ok(self)
+    }
+
+    /// The contract the key can call.
+    pub fn receiver_id(&self) -> &AccountId {
+        &self.receiver_id
+    }
+
+    /// The methods the key can call, all of them if empty.
+    pub fn method_names(&self) -> &[String] {
+        &self.method_names
+    }
+
+    /// The amount of NEAR the key can spend on gas.
+    pub fn allowance(&self) -> Allowance {
+        self.allowance.and_then(Allowance::limited).unwrap_or(Allowance::Unlimited)
+    }
+}
+
+/// Error of building a [`FunctionCallKeyPermission`] which exceeds the protocol limits.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyPermissionError {
+    /// The method name is empty or contains a comma.
+    InvalidMethodName(String),
+    /// The method name is longer than [`FunctionCallKeyPermission::MAX_METHOD_NAME_LEN`].
+    MethodNameTooLong(String),
+    /// The method names, with a terminating byte each, are longer than
+    /// [`FunctionCallKeyPermission::MAX_METHOD_NAMES_LEN`].
+    MethodNamesTooLong(usize),
+    /// The allowance is zero, which is not a valid limit.
+    ZeroAllowance,
+}
+
+impl std::fmt::Display for KeyPermissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMethodName(name) => write!(f, "invalid method name {name:?}"),
+            Self::MethodNameTooLong(name) => write!(
+                f,
+                "method name {name:?} is longer than {} bytes",
+                FunctionCallKeyPermission::MAX_METHOD_NAME_LEN
+            ),
+            Self::MethodNamesTooLong(len) => write!(
+                f,
+                "method names take {len} bytes, more than {}",
+                FunctionCallKeyPermission::MAX_METHOD_NAMES_LEN
+            ),
+            Self::ZeroAllowance => write!(f, "allowance must be more than zero"),
+        }
+    }
+}
+
+impl std::error::Error for KeyPermissionError {}
+
 enum PromiseAction {
     CreateAccount,
     DeployContract {
         code: Vec<u8>,
     },
+    DeployGlobalContract {
+        code: Vec<u8>,
+    },
+    DeployGlobalContractByAccountId {
+        code: Vec<u8>,
+    },
+    UseGlobalContract {
+        code_hash: CryptoHash,
+    },
+    UseGlobalContractByAccountId {
+        account_id: AccountId,
+    },
     FunctionCall {
         function_name: String,
         arguments: Vec<u8>,
@@ -81,6 +227,24 @@ impl PromiseAction {
             DeployContract { code } => {
                 crate::env::promise_batch_action_deploy_contract(promise_index, code)
             }
+            DeployGlobalContract { code } => {
+                crate::env::promise_batch_action_deploy_global_contract(promise_index, code)
+            }
+            DeployGlobalContractByAccountId { code } => {
+                crate::env::promise_batch_action_deploy_global_contract_by_account_id(
+                    promise_index,
+                    code,
+                )
+            }
+            UseGlobalContract { code_hash } => {
+                crate::env::promise_batch_action_use_global_contract(promise_index, code_hash)
+            }
+            UseGlobalContractByAccountId { account_id } => {
+                crate::env::promise_batch_action_use_global_contract_by_account_id(
+                    promise_index,
+                    account_id,
+                )
+            }
             FunctionCall { function_name, arguments, amount, gas } => {
                 crate::env::promise_batch_action_function_call(
                     promise_index,
@@ -183,6 +347,15 @@ impl PromiseJoint {
     }
 }
 
+/// Yielded promise, which is created right away by [`Promise::yield_on`].
+struct PromiseYield {
+    function_name: String,
+    arguments: Vec<u8>,
+    gas: Gas,
+    yield_id: YieldId,
+    promise_index: PromiseIndex,
+}
+
 /// A structure representing a result of the scheduled execution on another contract.
 ///
 /// Smart contract developers will explicitly use `Promise` in two situations:
@@ -205,7 +378,7 @@ impl PromiseJoint {
 /// #[near]
 /// impl ContractA {
 ///     pub fn a(&self) -> Promise {
-///         contract_b::ext("bob_near".parse().unwrap()).b()
+///         contract_b::ext("bob_near".parse().unwrap()).b().into()
 ///     }
 /// }
 /// ```
@@ -247,6 +420,7 @@ impl BorshSchema for Promise {
 enum PromiseSubtype {
     Single(Rc<PromiseSingle>),
     Joint(Rc<PromiseJoint>),
+    Yield(Rc<PromiseYield>),
 }
 
 impl Promise {
@@ -264,12 +438,128 @@ impl Promise {
         }
     }
 
+    /// Creates a promise calling `function_name` of the current account with `arguments` and
+    /// `gas` once it is resumed with [`Promise::resume`], or with a [`PromiseError`] after a
+    /// timeout of 200 blocks. The callback receives no unused gas and can read the payload with a
+    /// `#[yield_result]` argument.
+    ///
+    /// Unlike other promises, the yielded promise is created right away as its [`YieldId`] is
+    /// needed to resume it. It can't be scheduled after another promise.
+    /// Uses low-level [`crate::env::promise_yield_create`].
+    ///
+    /// # Example
+    /// ```
+    /// # use near_sdk::{test_utils::VMContextBuilder, testing_env};
+    /// use near_sdk::{near, serde_json, store::LookupMap, Gas, Promise, PromiseError, YieldId};
+    /// # testing_env!(VMContextBuilder::new().build());
+    ///
+    /// #[near(contract_state)]
+    /// pub struct Contract {
+    ///     requests: LookupMap<u64, YieldId>,
+    /// }
+    ///
+    /// #[near]
+    /// impl Contract {
+    ///     pub fn request(&mut self, id: u64) -> Promise {
+    ///         let args = serde_json::to_vec(&serde_json::json!({ "id": id })).unwrap();
+    ///         let yielded = Promise::yield_on("on_response".to_owned(), args, Gas::from_tgas(5));
+    ///         self.requests.insert(id, yielded.yield_id());
+    ///         yielded.into_promise()
+    ///     }
+    ///
+    ///     pub fn respond(&mut self, id: u64, response: String) {
+    ///         let yield_id = self.requests.get(&id).expect("Unknown request");
+    ///         require!(Promise::resume(yield_id, &response), "The request timed out");
+    ///     }
+    ///
+    ///     #[private]
+    ///     pub fn on_response(
+    ///         &mut self,
+    ///         id: u64,
+    ///         #[yield_result] response: Result<String, PromiseError>,
+    ///     ) -> Option<String> {
+    ///         self.requests.remove(&id);
+    ///         // `Err` if the request timed out.
+    ///         response.ok()
+    ///     }
+    /// }
+    /// # use near_sdk::require;
+    /// # let mut contract = Contract { requests: LookupMap::new(b"r") };
+    /// # let _ = contract.request(1);
+    /// # contract.respond(1, "response".to_owned());
+    /// ```
+    ///
+    /// [`PromiseError`]: crate::PromiseError
+    pub fn yield_on(function_name: String, arguments: Vec<u8>, gas: Gas) -> YieldedPromise {
+        let (promise_index, register) = crate::env::promise_yield_create_in_register(
+            &function_name,
+            &arguments,
+            gas,
+            GasWeight(0),
+        );
+        let yield_id =
+            YieldId(register.read_crypto_hash().unwrap_or_else(|| crate::env::abort()).into());
+        let promise = Promise {
+            subtype: PromiseSubtype::Yield(Rc::new(PromiseYield {
+                function_name,
+                arguments,
+                gas,
+                yield_id,
+                promise_index,
+            })),
+            should_return: RefCell::new(false),
+        };
+        YieldedPromise { promise, yield_id }
+    }
+
+    /// Resumes the yielded promise with the JSON serialized `payload`, see [`Promise::yield_on`].
+    /// Returns `false` if there is no yielded promise with this id on the current account, e.g.
+    /// if it timed out. For other serializations use [`crate::env::promise_yield_resume`].
+    pub fn resume<T: serde::Serialize>(yield_id: &YieldId, payload: &T) -> bool {
+        let payload = serde_json::to_vec(payload).unwrap_or_else(|_| crate::env::abort());
+        crate::env::promise_yield_resume(&yield_id.0.into(), &payload)
+    }
+
+    /// Returns the gas left for the calls scheduled by the current method after reserving
+    /// `reserved` for the rest of the call chain, computed from [`crate::env::prepaid_gas`] and
+    /// [`crate::env::used_gas`]. The reserved amount should cover the static gas of the callbacks
+    /// and the gas still burnt by the current method, including the creation of the promises.
+    ///
+    /// # Panics
+    /// If less than `reserved` gas is left, so that the method fails before scheduling a chain
+    /// which would run out of gas halfway.
+    ///
+    /// # Examples
+    /// ```
+    /// # use near_sdk::{test_utils::VMContextBuilder, testing_env, Gas, Promise};
+    /// # testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(100)).build());
+    /// const CALLBACK_GAS: Gas = Gas::from_tgas(10);
+    /// const SCHEDULING_GAS: Gas = Gas::from_tgas(10);
+    ///
+    /// // At most 80 Tgas are left for the call scheduled before the callback.
+    /// let gas = Promise::remaining_gas(CALLBACK_GAS.saturating_add(SCHEDULING_GAS));
+    /// assert!(gas <= Gas::from_tgas(80));
+    /// ```
+    pub fn remaining_gas(reserved: Gas) -> Gas {
+        let prepaid = crate::env::prepaid_gas();
+        let left = prepaid.saturating_sub(crate::env::used_gas());
+        left.checked_sub(reserved).unwrap_or_else(|| {
+            crate::env::panic_str(&format!(
+                "Not enough gas for the call chain: {reserved} is reserved, but only {left} of \
+                 the prepaid {prepaid} is left"
+            ))
+        })
+    }
+
     fn add_action(self, action: PromiseAction) -> Self {
         match &self.subtype {
             PromiseSubtype::Single(x) => x.actions.borrow_mut().push(action),
             PromiseSubtype::Joint(_) => {
                 crate::env::panic_str("Cannot add action to a joint promise.")
             }
+            PromiseSubtype::Yield(_) => {
+                crate::env::panic_str("Cannot add action to a yielded promise.")
+            }
         }
         self
     }
@@ -286,6 +576,46 @@ impl Promise {
         self.add_action(PromiseAction::DeployContract { code })
     }
 
+    /// Deploy a global contract, which other accounts can use by the hash of its code, see
+    /// [`Promise::use_global_contract`]. The code is stored once, paid for by the account on
+    /// which this promise acts.
+    /// Uses low-level [`crate::env::promise_batch_action_deploy_global_contract`]
+    pub fn deploy_global_contract(self, code: Vec<u8>) -> Self {
+        self.add_action(PromiseAction::DeployGlobalContract { code })
+    }
+
+    /// Deploy a global contract, which other accounts can use by the id of the account on which
+    /// this promise acts, see [`Promise::use_global_contract_by_account_id`]. Deploying again
+    /// updates the code of all those accounts.
+    /// Uses low-level [`crate::env::promise_batch_action_deploy_global_contract_by_account_id`]
+    pub fn deploy_global_contract_by_account_id(self, code: Vec<u8>) -> Self {
+        self.add_action(PromiseAction::DeployGlobalContractByAccountId { code })
+    }
+
+    /// Use the global contract with the given code hash as the contract of the account on which
+    /// this promise acts, instead of deploying the code again.
+    ///
+    /// ```no_run
+    /// # use near_sdk::{env, Promise, NearToken};
+    /// const CHILD_CODE_HASH: [u8; 32] = [0; 32];
+    ///
+    /// Promise::new("child.factory.near".parse().unwrap())
+    ///     .create_account()
+    ///     .transfer(NearToken::from_millinear(100))
+    ///     .use_global_contract(CHILD_CODE_HASH);
+    /// ```
+    /// Uses low-level [`crate::env::promise_batch_action_use_global_contract`]
+    pub fn use_global_contract(self, code_hash: impl Into<CryptoHash>) -> Self {
+        self.add_action(PromiseAction::UseGlobalContract { code_hash: code_hash.into() })
+    }
+
+    /// Use the global contract deployed by `account_id` as the contract of the account on which
+    /// this promise acts. The account follows the updates of the global contract.
+    /// Uses low-level [`crate::env::promise_batch_action_use_global_contract_by_account_id`]
+    pub fn use_global_contract_by_account_id(self, account_id: AccountId) -> Self {
+        self.add_action(PromiseAction::UseGlobalContractByAccountId { account_id })
+    }
+
     /// A low-level interface for making a function call to the account that this promise acts on.
     /// Uses low-level [`crate::env::promise_batch_action_function_call`]
     pub fn function_call(
@@ -363,6 +693,34 @@ impl Promise {
         )
     }
 
+    /// Add a function call access key with the given [`FunctionCallKeyPermission`].
+    /// Uses low-level [`crate::env::promise_batch_action_add_key_with_function_call`]
+    pub fn add_function_call_key(
+        self,
+        public_key: PublicKey,
+        permission: FunctionCallKeyPermission,
+    ) -> Self {
+        self.add_function_call_key_with_nonce(public_key, permission, 0)
+    }
+
+    /// Add a function call access key with the given [`FunctionCallKeyPermission`] and nonce.
+    /// Uses low-level [`crate::env::promise_batch_action_add_key_with_function_call`]
+    pub fn add_function_call_key_with_nonce(
+        self,
+        public_key: PublicKey,
+        permission: FunctionCallKeyPermission,
+        nonce: u64,
+    ) -> Self {
+        let allowance = permission.allowance();
+        self.add_access_key_allowance_with_nonce(
+            public_key,
+            allowance,
+            permission.receiver_id,
+            permission.method_names.join(","),
+            nonce,
+        )
+    }
+
     #[deprecated(since = "5.0.0", note = "Use add_access_key_allowance instead")]
     pub fn add_access_key(
         self,
@@ -439,7 +797,8 @@ impl Promise {
     /// // p3.create_account();
     /// ```
     /// Uses low-level [`crate::env::promise_and`]
-    pub fn and(self, other: Promise) -> Promise {
+    pub fn and(self, other: impl Into<Promise>) -> Promise {
+        let other = other.into();
         Promise {
             subtype: PromiseSubtype::Joint(Rc::new(PromiseJoint {
                 promise_a: self,
@@ -464,7 +823,8 @@ impl Promise {
     /// p1.then(p2).and(p3).then(p4);
     /// ```
     /// Uses low-level [`crate::env::promise_batch_then`]
-    pub fn then(self, mut other: Promise) -> Promise {
+    pub fn then(self, other: impl Into<Promise>) -> Promise {
+        let mut other = other.into();
         match &mut other.subtype {
             PromiseSubtype::Single(x) => {
                 let mut after = x.after.borrow_mut();
@@ -476,10 +836,54 @@ impl Promise {
                 *after = Some(self)
             }
             PromiseSubtype::Joint(_) => crate::env::panic_str("Cannot callback joint promise."),
+            PromiseSubtype::Yield(_) => crate::env::panic_str("Cannot callback yielded promise."),
         }
         other
     }
 
+    /// Schedules a callback on the current contract which transfers `amount` to `refund_to` if
+    /// this promise fails, and logs a NEP-297 `deposit_refund` event, see [`crate::refund`].
+    /// If the promise succeeds, the callback returns its result unchanged.
+    ///
+    /// Use it to give back the deposit forwarded with a call, without writing the callback:
+    /// ```
+    /// # use near_sdk::{env, ext_contract, near, AccountId, Promise};
+    /// #[ext_contract(ext_market)]
+    /// trait Market {
+    ///     fn buy(&mut self, token_id: String);
+    /// }
+    ///
+    /// #[near(contract_state)]
+    /// #[derive(Default)]
+    /// pub struct Broker {}
+    ///
+    /// #[near]
+    /// impl Broker {
+    ///     #[payable]
+    ///     pub fn buy(&mut self, market: AccountId, token_id: String) -> Promise {
+    ///         let deposit = env::attached_deposit();
+    ///         Promise::from(ext_market::ext(market).with_attached_deposit(deposit).buy(token_id))
+    ///             .then_refund_on_failure(env::predecessor_account_id(), deposit)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The callback is exported by `near-sdk` and requires the `refund-on-failure` feature.
+    #[cfg(feature = "refund-on-failure")]
+    pub fn then_refund_on_failure(self, refund_to: AccountId, amount: NearToken) -> Promise {
+        use crate::refund::{RefundArgs, REFUND_ON_FAILURE_GAS, REFUND_ON_FAILURE_METHOD};
+
+        let arguments = serde_json::to_vec(&RefundArgs { refund_to, amount })
+            .unwrap_or_else(|_| crate::env::abort());
+        self.then(Promise::new(crate::env::current_account_id()).function_call_weight(
+            REFUND_ON_FAILURE_METHOD.to_string(),
+            arguments,
+            NearToken::from_yoctonear(0),
+            REFUND_ON_FAILURE_GAS,
+            GasWeight(0),
+        ))
+    }
+
     /// A specialized, relatively low-level API method. Allows to mark the given promise as the one
     /// that should be considered as a return value.
     ///
@@ -502,7 +906,7 @@ impl Promise {
     ///     }
     ///
     ///     pub fn a2(&self) -> Promise {
-    ///        contract_b::ext("bob_near".parse().unwrap()).b()
+    ///        contract_b::ext("bob_near".parse().unwrap()).b().into()
     ///     }
     /// }
     /// ```
@@ -513,10 +917,44 @@ impl Promise {
         self
     }
 
+    /// Returns a read-only view of the promise and of the promises it is scheduled after, see
+    /// [`PromiseView`].
+    pub fn inspect(&self) -> PromiseView {
+        match &self.subtype {
+            PromiseSubtype::Single(x) => PromiseView::Batch {
+                receiver_id: x.account_id.clone(),
+                actions: x.actions.borrow().iter().map(PromiseActionView::from).collect(),
+                after: x.after.borrow().as_ref().map(|after| Box::new(after.inspect())),
+            },
+            PromiseSubtype::Joint(x) => {
+                // Joints of joints are flattened by `promise_and`, so are they here.
+                let promises = [&x.promise_a, &x.promise_b]
+                    .into_iter()
+                    .flat_map(|promise| match promise.inspect() {
+                        PromiseView::Join { promises } => promises,
+                        view => vec![view],
+                    })
+                    .collect();
+                PromiseView::Join { promises }
+            }
+            PromiseSubtype::Yield(x) => PromiseView::Yield {
+                yield_id: x.yield_id,
+                function_call: PromiseActionView::FunctionCall {
+                    function_name: x.function_name.clone(),
+                    arguments: x.arguments.clone().into(),
+                    deposit: NearToken::from_near(0),
+                    static_gas: x.gas,
+                    gas_weight: 0,
+                },
+            },
+        }
+    }
+
     fn construct_recursively(&self) -> PromiseIndex {
         let res = match &self.subtype {
             PromiseSubtype::Single(x) => x.construct_recursively(),
             PromiseSubtype::Joint(x) => x.construct_recursively(),
+            PromiseSubtype::Yield(x) => x.promise_index,
         };
         if *self.should_return.borrow() {
             crate::env::promise_return(res);
@@ -564,138 +1002,919 @@ impl schemars::JsonSchema for Promise {
     }
 }
 
-/// When the method can return either a promise or a value, it can be called with `PromiseOrValue::Promise`
-/// or `PromiseOrValue::Value` to specify which one should be returned.
-/// # Example
-/// ```no_run
-/// # use near_sdk::{ext_contract, near, Gas, PromiseOrValue};
-/// #[ext_contract]
-/// pub trait ContractA {
-///     fn a(&mut self);
-/// }
-///
-/// let value = Some(true);
-/// let val: PromiseOrValue<bool> = if let Some(value) = value {
-///     PromiseOrValue::Value(value)
-/// } else {
-///     contract_a::ext("bob_near".parse().unwrap()).a().into()
-/// };
-/// ```
-#[derive(serde::Serialize)]
-#[serde(untagged)]
-pub enum PromiseOrValue<T> {
-    Promise(Promise),
-    Value(T),
-}
+/// Resumption token of a yielded promise, see [`Promise::yield_on`]. It is serialized as a base58
+/// string in JSON.
+#[near(inside_nearsdk, serializers=[borsh, json])]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct YieldId(Base58CryptoHash);
 
-#[cfg(feature = "abi")]
-impl<T> BorshSchema for PromiseOrValue<T>
-where
-    T: BorshSchema,
-{
-    fn add_definitions_recursively(
-        definitions: &mut BTreeMap<borsh::schema::Declaration, borsh::schema::Definition>,
-    ) {
-        T::add_definitions_recursively(definitions);
+impl From<CryptoHash> for YieldId {
+    fn from(data_id: CryptoHash) -> Self {
+        Self(data_id.into())
     }
+}
 
-    fn declaration() -> borsh::schema::Declaration {
-        T::declaration()
+impl From<YieldId> for CryptoHash {
+    fn from(yield_id: YieldId) -> Self {
+        yield_id.0.into()
     }
 }
 
-impl<T> From<Promise> for PromiseOrValue<T> {
-    fn from(promise: Promise) -> Self {
-        PromiseOrValue::Promise(promise)
+impl std::fmt::Display for YieldId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(&self.0))
     }
 }
 
-impl<T: borsh::BorshSerialize> borsh::BorshSerialize for PromiseOrValue<T> {
-    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
-        match self {
-            // Only actual value is serialized.
-            PromiseOrValue::Value(x) => x.serialize(writer),
-            // The promise is dropped to cause env::promise calls.
-            PromiseOrValue::Promise(p) => p.serialize(writer),
-        }
-    }
+/// A promise created with [`Promise::yield_on`], which waits to be resumed with
+/// [`Promise::resume`].
+pub struct YieldedPromise {
+    promise: Promise,
+    yield_id: YieldId,
 }
 
-#[cfg(feature = "abi")]
-impl<T: schemars::JsonSchema> schemars::JsonSchema for PromiseOrValue<T> {
-    fn schema_name() -> String {
-        format!("PromiseOrValue{}", T::schema_name())
+impl YieldedPromise {
+    /// The id needed to resume the promise.
+    pub fn yield_id(&self) -> YieldId {
+        self.yield_id
     }
 
-    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
-        T::json_schema(gen)
+    pub fn into_promise(self) -> Promise {
+        self.promise
     }
-}
-
-#[cfg(not(target_arch = "wasm32"))]
-#[cfg(test)]
-mod tests {
-    use crate::mock::MockAction;
-    use crate::test_utils::get_created_receipts;
-    use crate::test_utils::test_env::{alice, bob};
-    use crate::{
-        test_utils::VMContextBuilder, testing_env, AccountId, Allowance, NearToken, Promise,
-        PublicKey,
-    };
 
-    fn pk() -> PublicKey {
-        "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse().unwrap()
+    /// Schedules `other` after the yielded promise, see [`Promise::then`]. `other` receives the
+    /// result of the yielded callback.
+    ///
+    /// The mocked blockchain used in unit tests can't schedule promises after yielded ones, so
+    /// such chains can only be tested on a sandbox network.
+    pub fn then(self, other: impl Into<Promise>) -> Promise {
+        self.promise.then(other)
     }
 
-    fn get_actions() -> std::vec::IntoIter<MockAction> {
-        let receipts = get_created_receipts();
-        let first_receipt = receipts.into_iter().next().unwrap();
-        first_receipt.actions.into_iter()
+    /// Marks the promise as the return value of the current method, see [`Promise::as_return`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_return(self) -> Self {
+        Self { promise: self.promise.as_return(), yield_id: self.yield_id }
     }
+}
 
-    fn has_add_key_with_full_access(public_key: PublicKey, nonce: Option<u64>) -> bool {
-        let public_key = near_crypto::PublicKey::try_from(public_key).unwrap();
-        get_actions().any(|el| {
-            matches!(
-                el,
-                MockAction::AddKeyWithFullAccess { public_key: p, nonce: n, receipt_index: _, }
-                if p == public_key
-                    && (nonce.is_none() || Some(n) == nonce)
-            )
-        })
+impl From<YieldedPromise> for Promise {
+    fn from(promise: YieldedPromise) -> Self {
+        promise.promise
     }
+}
 
-    fn has_add_key_with_function_call(
-        public_key: PublicKey,
-        allowance: u128,
+/// Read-only snapshot of a [`Promise`] and of the promises it is scheduled after, returned by
+/// [`Promise::inspect`].
+///
+/// It can be used to check invariants before returning the promise, e.g. the total attached
+/// deposit, or to assert on the scheduled calls in tests. It renders as a text tree with
+/// [`Display`](std::fmt::Display) and serializes to JSON.
+///
+/// # Example
+/// ```
+/// # use near_sdk::{test_utils::VMContextBuilder, testing_env};
+/// use near_sdk::{require, Gas, NearToken, Promise};
+/// # testing_env!(VMContextBuilder::new().build());
+///
+/// let promise = Promise::new("alice.near".parse().unwrap())
+///     .transfer(NearToken::from_near(1))
+///     .and(Promise::new("bob.near".parse().unwrap()).transfer(NearToken::from_near(2)))
+///     .then(Promise::new("contract.near".parse().unwrap()).function_call(
+///         "on_transfer".to_owned(),
+///         vec![],
+///         NearToken::from_near(0),
+///         Gas::from_tgas(5),
+///     ));
+///
+/// let view = promise.inspect();
+/// require!(view.total_deposit() <= NearToken::from_near(5), "Too much NEAR attached");
+/// assert_eq!(
+///     view.to_string(),
+///     "join
+///   - alice.near
+///       transfer 1.00 NEAR
+///   - bob.near
+///       transfer 2.00 NEAR
+/// then contract.near
+///   function_call on_transfer(0 bytes), deposit 0 NEAR, gas 5.0 Tgas, weight 0"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromiseView {
+    /// Actions executed in a single receipt on `receiver_id`, after the promise `after` if any.
+    Batch {
         receiver_id: AccountId,
-        function_names: String,
-        nonce: Option<u64>,
-    ) -> bool {
-        let public_key = near_crypto::PublicKey::try_from(public_key).unwrap();
-        get_actions().any(|el| {
-            matches!(
-                el,
-                MockAction::AddKeyWithFunctionCall {
-                    public_key: p,
-                    allowance: a,
-                    receiver_id: r,
-                    method_names,
-                    nonce: n,
-                    receipt_index: _,
-                }
-                if p == public_key
-                    && a.unwrap() == NearToken::from_yoctonear(allowance)
-                    && r == receiver_id
-                    && method_names.clone() == function_names.split(',').collect::<Vec<_>>()
-                    && (nonce.is_none() || Some(n) == nonce)
-            )
+        actions: Vec<PromiseActionView>,
+        after: Option<Box<PromiseView>>,
+    },
+    /// Promises executed independently, joined with [`Promise::and`].
+    Join { promises: Vec<PromiseView> },
+    /// Promise created with [`Promise::yield_on`], calling `function_call` on the current account
+    /// once resumed.
+    Yield { yield_id: YieldId, function_call: PromiseActionView },
+}
+
+impl PromiseView {
+    /// Total deposit attached to the transfers and function calls of the promise and of the
+    /// promises it is scheduled after.
+    pub fn total_deposit(&self) -> NearToken {
+        self.fold(NearToken::from_yoctonear(0), |total, action| {
+            total.saturating_add(action.deposit())
         })
     }
 
-    #[test]
-    fn test_add_full_access_key() {
-        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+    /// Total static gas attached to the function calls of the promise and of the promises it is
+    /// scheduled after, excluding the unused gas distributed by weight.
+    pub fn total_static_gas(&self) -> Gas {
+        self.fold(Gas::from_gas(0), |total, action| total.saturating_add(action.static_gas()))
+    }
+
+    fn fold<B>(&self, init: B, f: impl Fn(B, &PromiseActionView) -> B + Copy) -> B {
+        match self {
+            PromiseView::Batch { actions, after, .. } => {
+                let init = match after {
+                    Some(after) => after.fold(init, f),
+                    None => init,
+                };
+                actions.iter().fold(init, f)
+            }
+            PromiseView::Join { promises } => {
+                promises.iter().fold(init, |acc, promise| promise.fold(acc, f))
+            }
+            PromiseView::Yield { function_call, .. } => f(init, function_call),
+        }
+    }
+
+    fn render(&self, lines: &mut Vec<String>) {
+        match self {
+            PromiseView::Batch { receiver_id, actions, after } => {
+                match after {
+                    Some(after) => {
+                        after.render(lines);
+                        lines.push(format!("then {receiver_id}"));
+                    }
+                    None => lines.push(receiver_id.to_string()),
+                }
+                lines.extend(actions.iter().map(|action| format!("  {action}")));
+            }
+            PromiseView::Join { promises } => {
+                lines.push("join".to_string());
+                for promise in promises {
+                    let mut promise_lines = vec![];
+                    promise.render(&mut promise_lines);
+                    for (i, line) in promise_lines.into_iter().enumerate() {
+                        let bullet = if i == 0 { "- " } else { "  " };
+                        lines.push(format!("  {bullet}{line}"));
+                    }
+                }
+            }
+            PromiseView::Yield { yield_id, function_call } => {
+                lines.push(format!("yield {yield_id}"));
+                lines.push(format!("  {function_call}"));
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PromiseView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        self.render(&mut lines);
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+/// Read-only view of an action of a [`PromiseView`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromiseActionView {
+    CreateAccount,
+    DeployContract {
+        code_size: usize,
+    },
+    DeployGlobalContract {
+        code_size: usize,
+    },
+    DeployGlobalContractByAccountId {
+        code_size: usize,
+    },
+    UseGlobalContract {
+        code_hash: Base58CryptoHash,
+    },
+    UseGlobalContractByAccountId {
+        account_id: AccountId,
+    },
+    /// Function call, with a `gas_weight` of 0 if it was created with
+    /// [`Promise::function_call`].
+    FunctionCall {
+        function_name: String,
+        arguments: Base64VecU8,
+        deposit: NearToken,
+        static_gas: Gas,
+        gas_weight: u64,
+    },
+    Transfer {
+        deposit: NearToken,
+    },
+    Stake {
+        stake: NearToken,
+        public_key: PublicKey,
+    },
+    AddFullAccessKey {
+        public_key: PublicKey,
+        nonce: u64,
+    },
+    /// Function call access key, with an unlimited allowance if `allowance` is `None`.
+    AddAccessKey {
+        public_key: PublicKey,
+        allowance: Option<NearToken>,
+        receiver_id: AccountId,
+        function_names: Vec<String>,
+        nonce: u64,
+    },
+    DeleteKey {
+        public_key: PublicKey,
+    },
+    DeleteAccount {
+        beneficiary_id: AccountId,
+    },
+}
+
+impl PromiseActionView {
+    /// Deposit attached to the transfer or function call, zero for other actions.
+    pub fn deposit(&self) -> NearToken {
+        match self {
+            PromiseActionView::FunctionCall { deposit, .. }
+            | PromiseActionView::Transfer { deposit } => *deposit,
+            _ => NearToken::from_yoctonear(0),
+        }
+    }
+
+    /// Static gas attached to the function call, zero for other actions.
+    pub fn static_gas(&self) -> Gas {
+        match self {
+            PromiseActionView::FunctionCall { static_gas, .. } => *static_gas,
+            _ => Gas::from_gas(0),
+        }
+    }
+}
+
+impl From<&PromiseAction> for PromiseActionView {
+    fn from(action: &PromiseAction) -> Self {
+        use PromiseAction::*;
+        match action {
+            CreateAccount => Self::CreateAccount,
+            DeployContract { code } => Self::DeployContract { code_size: code.len() },
+            DeployGlobalContract { code } => Self::DeployGlobalContract { code_size: code.len() },
+            DeployGlobalContractByAccountId { code } => {
+                Self::DeployGlobalContractByAccountId { code_size: code.len() }
+            }
+            UseGlobalContract { code_hash } => {
+                Self::UseGlobalContract { code_hash: (*code_hash).into() }
+            }
+            UseGlobalContractByAccountId { account_id } => {
+                Self::UseGlobalContractByAccountId { account_id: account_id.clone() }
+            }
+            FunctionCall { function_name, arguments, amount, gas } => Self::FunctionCall {
+                function_name: function_name.clone(),
+                arguments: arguments.clone().into(),
+                deposit: *amount,
+                static_gas: *gas,
+                gas_weight: 0,
+            },
+            FunctionCallWeight { function_name, arguments, amount, gas, weight } => {
+                Self::FunctionCall {
+                    function_name: function_name.clone(),
+                    arguments: arguments.clone().into(),
+                    deposit: *amount,
+                    static_gas: *gas,
+                    gas_weight: weight.0,
+                }
+            }
+            Transfer { amount } => Self::Transfer { deposit: *amount },
+            Stake { amount, public_key } => {
+                Self::Stake { stake: *amount, public_key: public_key.clone() }
+            }
+            AddFullAccessKey { public_key, nonce } => {
+                Self::AddFullAccessKey { public_key: public_key.clone(), nonce: *nonce }
+            }
+            AddAccessKey { public_key, allowance, receiver_id, function_names, nonce } => {
+                Self::AddAccessKey {
+                    public_key: public_key.clone(),
+                    allowance: match allowance {
+                        Allowance::Unlimited => None,
+                        Allowance::Limited(amount) => Some(NearToken::from_yoctonear(amount.get())),
+                    },
+                    receiver_id: receiver_id.clone(),
+                    function_names: function_names
+                        .split(',')
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_owned)
+                        .collect(),
+                    nonce: *nonce,
+                }
+            }
+            DeleteKey { public_key } => Self::DeleteKey { public_key: public_key.clone() },
+            DeleteAccount { beneficiary_id } => {
+                Self::DeleteAccount { beneficiary_id: beneficiary_id.clone() }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PromiseActionView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PromiseActionView::CreateAccount => write!(f, "create_account"),
+            PromiseActionView::DeployContract { code_size } => {
+                write!(f, "deploy_contract {code_size} bytes")
+            }
+            PromiseActionView::DeployGlobalContract { code_size } => {
+                write!(f, "deploy_global_contract {code_size} bytes")
+            }
+            PromiseActionView::DeployGlobalContractByAccountId { code_size } => {
+                write!(f, "deploy_global_contract_by_account_id {code_size} bytes")
+            }
+            PromiseActionView::UseGlobalContract { code_hash } => {
+                write!(f, "use_global_contract {}", String::from(code_hash))
+            }
+            PromiseActionView::UseGlobalContractByAccountId { account_id } => {
+                write!(f, "use_global_contract_by_account_id {account_id}")
+            }
+            PromiseActionView::FunctionCall {
+                function_name,
+                arguments,
+                deposit,
+                static_gas,
+                gas_weight,
+            } => write!(
+                f,
+                "function_call {function_name}({} bytes), deposit {deposit}, gas {static_gas}, \
+                 weight {gas_weight}",
+                arguments.0.len()
+            ),
+            PromiseActionView::Transfer { deposit } => write!(f, "transfer {deposit}"),
+            PromiseActionView::Stake { stake, public_key } => {
+                write!(f, "stake {stake} with {}", String::from(public_key))
+            }
+            PromiseActionView::AddFullAccessKey { public_key, nonce } => {
+                write!(f, "add_full_access_key {}, nonce {nonce}", String::from(public_key))
+            }
+            PromiseActionView::AddAccessKey {
+                public_key,
+                allowance,
+                receiver_id,
+                function_names,
+                nonce,
+            } => {
+                write!(
+                    f,
+                    "add_access_key {} for {receiver_id} [{}], allowance ",
+                    String::from(public_key),
+                    function_names.join(", ")
+                )?;
+                match allowance {
+                    Some(allowance) => write!(f, "{allowance}")?,
+                    None => write!(f, "unlimited")?,
+                }
+                write!(f, ", nonce {nonce}")
+            }
+            PromiseActionView::DeleteKey { public_key } => {
+                write!(f, "delete_key {}", String::from(public_key))
+            }
+            PromiseActionView::DeleteAccount { beneficiary_id } => {
+                write!(f, "delete_account, beneficiary {beneficiary_id}")
+            }
+        }
+    }
+}
+
+/// When the method can return either a promise or a value, it can be called with `PromiseOrValue::Promise`
+/// or `PromiseOrValue::Value` to specify which one should be returned.
+/// # Example
+/// ```no_run
+/// # use near_sdk::{ext_contract, near, Gas, PromiseOrValue};
+/// #[ext_contract]
+/// pub trait ContractA {
+///     fn a(&mut self) -> bool;
+/// }
+///
+/// let value = Some(true);
+/// let val: PromiseOrValue<bool> = if let Some(value) = value {
+///     PromiseOrValue::Value(value)
+/// } else {
+///     contract_a::ext("bob_near".parse().unwrap()).a().into()
+/// };
+/// ```
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+pub enum PromiseOrValue<T> {
+    Promise(Promise),
+    Value(T),
+}
+
+#[cfg(feature = "abi")]
+impl<T> BorshSchema for PromiseOrValue<T>
+where
+    T: BorshSchema,
+{
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<borsh::schema::Declaration, borsh::schema::Definition>,
+    ) {
+        T::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        T::declaration()
+    }
+}
+
+impl<T> From<Promise> for PromiseOrValue<T> {
+    fn from(promise: Promise) -> Self {
+        PromiseOrValue::Promise(promise)
+    }
+}
+
+impl<T: borsh::BorshSerialize> borsh::BorshSerialize for PromiseOrValue<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        match self {
+            // Only actual value is serialized.
+            PromiseOrValue::Value(x) => x.serialize(writer),
+            // The promise is dropped to cause env::promise calls.
+            PromiseOrValue::Promise(p) => p.serialize(writer),
+        }
+    }
+}
+
+#[cfg(feature = "abi")]
+impl<T: schemars::JsonSchema> schemars::JsonSchema for PromiseOrValue<T> {
+    fn schema_name() -> String {
+        format!("PromiseOrValue{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        T::json_schema(gen)
+    }
+}
+
+/// Marker types for the serializer of the value returned by a [`TypedPromise`].
+pub mod serializer {
+    /// The value is serialized with JSON, the default.
+    pub enum Json {}
+
+    /// The value is serialized with Borsh, i.e. the method has `#[result_serializer(borsh)]`.
+    pub enum Borsh {}
+}
+
+/// A [`Promise`] which returns a value of type `T`, serialized with `S`, when it succeeds.
+///
+/// The methods generated by `#[ext_contract]` and `#[near]` on the `Ext` structs return typed
+/// promises, so that the callback scheduled with [`TypedPromise::then`] must expect the value
+/// returned by the called method, see [`PromiseCallback`].
+///
+/// Typed promises can be joined with [`TypedPromise::and`] and [`join_all`], keeping track of the
+/// result of each of the joined promises. The callback scheduled after a join receives all the
+/// results through a single `#[callback_result]` argument: a tuple of
+/// `Result<T, PromiseError>` for [`PromiseJoin2`] and a `Vec` of them for [`PromiseJoinAll`].
+///
+/// # Example
+/// ```no_run
+/// # use near_sdk::{env, ext_contract, near, Gas, PromiseError, TypedPromise};
+/// #[ext_contract(ext_oracle)]
+/// pub trait Oracle {
+///     fn price(&self) -> u128;
+///     fn symbol(&self) -> String;
+/// }
+///
+/// #[near(contract_state)]
+/// #[derive(Default)]
+/// pub struct Contract {}
+///
+/// #[near]
+/// impl Contract {
+///     pub fn query(&self) -> TypedPromise<Option<String>> {
+///         let oracle = || ext_oracle::ext("oracle.near".parse().unwrap());
+///         let price: TypedPromise<u128> = oracle().price();
+///         let symbol: TypedPromise<String> = oracle().symbol();
+///         price.and(symbol).then(Self::ext(env::current_account_id()).on_query())
+///     }
+///
+///     #[private]
+///     pub fn on_query(
+///         &mut self,
+///         #[callback_result] results: (Result<u128, PromiseError>, Result<String, PromiseError>),
+///     ) -> Option<String> {
+///         let (price, symbol) = results;
+///         Some(format!("{} {}", price.ok()?, symbol.ok()?))
+///     }
+/// }
+/// ```
+pub struct TypedPromise<T, S = serializer::Json> {
+    promise: Promise,
+    _marker: PhantomData<fn() -> (T, S)>,
+}
+
+impl<T, S> TypedPromise<T, S> {
+    /// Wraps a promise which is known to return a value of type `T`.
+    pub fn new(promise: Promise) -> Self {
+        Self { promise, _marker: PhantomData }
+    }
+
+    /// Returns the underlying untyped promise.
+    pub fn into_promise(self) -> Promise {
+        self.promise
+    }
+
+    /// Joins this promise with another one, see [`Promise::and`].
+    pub fn and<U, S2>(self, other: TypedPromise<U, S2>) -> PromiseJoin2<T, U> {
+        PromiseJoin { promise: self.promise.and(other.promise), _marker: PhantomData }
+    }
+
+    /// Schedules `callback` after this promise, see [`Promise::then`]. A typed callback must
+    /// expect a value of type `T`.
+    pub fn then<C: PromiseCallback<T>>(self, callback: C) -> C::Output {
+        callback.schedule_after(self.promise)
+    }
+
+    /// Marks the promise as the return value of the current method, see [`Promise::as_return`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_return(self) -> Self {
+        Self::new(self.promise.as_return())
+    }
+
+    /// Returns a read-only view of the promise, see [`Promise::inspect`].
+    pub fn inspect(&self) -> PromiseView {
+        self.promise.inspect()
+    }
+}
+
+impl<T, S> From<TypedPromise<T, S>> for Promise {
+    fn from(promise: TypedPromise<T, S>) -> Self {
+        promise.promise
+    }
+}
+
+impl<T, S> From<TypedPromise<T, S>> for PromiseOrValue<T> {
+    fn from(promise: TypedPromise<T, S>) -> Self {
+        PromiseOrValue::Promise(promise.promise)
+    }
+}
+
+impl<T, S> serde::Serialize for TypedPromise<T, S> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.promise, serializer)
+    }
+}
+
+impl<T, S> borsh::BorshSerialize for TypedPromise<T, S> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        borsh::BorshSerialize::serialize(&self.promise, writer)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl<T: BorshSchema, S> BorshSchema for TypedPromise<T, S> {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<borsh::schema::Declaration, borsh::schema::Definition>,
+    ) {
+        T::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        T::declaration()
+    }
+}
+
+#[cfg(feature = "abi")]
+impl<T: schemars::JsonSchema, S> schemars::JsonSchema for TypedPromise<T, S> {
+    fn schema_name() -> String {
+        format!("TypedPromise{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        T::json_schema(gen)
+    }
+}
+
+/// A function call of a callback method which receives a value of type `In` from the promises
+/// it is scheduled after and returns a value of type `T`, serialized with `S`.
+///
+/// `In` is the type of the `#[callback_unwrap]` or `#[callback_result]` argument of the method,
+/// with the `Result` stripped, or a tuple of these types if there are several of them. These are
+/// returned by the methods of the `Ext` structs for methods with callback arguments.
+pub struct TypedCallback<In, T, S = serializer::Json> {
+    promise: Promise,
+    _marker: PhantomData<fn(In) -> (T, S)>,
+}
+
+impl<In, T, S> TypedCallback<In, T, S> {
+    /// Wraps a function call of a method expecting `In` and returning `T`.
+    pub fn new(promise: Promise) -> Self {
+        Self { promise, _marker: PhantomData }
+    }
+
+    /// Returns the underlying untyped promise.
+    pub fn into_promise(self) -> Promise {
+        self.promise
+    }
+}
+
+impl<In, T, S> From<TypedCallback<In, T, S>> for Promise {
+    fn from(callback: TypedCallback<In, T, S>) -> Self {
+        callback.promise
+    }
+}
+
+/// A promise which can be scheduled after a promise returning a value of type `In`.
+///
+/// Any [`Promise`] or [`TypedPromise`] can be scheduled as it ignores the value, while a
+/// [`TypedCallback`] must expect exactly `In`, so that the following does not compile:
+///
+/// ```compile_fail
+/// # use near_sdk::{env, ext_contract, near, PromiseError, TypedPromise};
+/// #[ext_contract(ext_oracle)]
+/// pub trait Oracle {
+///     fn price(&self) -> u128;
+/// }
+///
+/// #[near(contract_state)]
+/// #[derive(Default)]
+/// pub struct Contract {}
+///
+/// #[near]
+/// impl Contract {
+///     pub fn query(&self) -> TypedPromise<()> {
+///         ext_oracle::ext("oracle.near".parse().unwrap())
+///             .price()
+///             .then(Self::ext(env::current_account_id()).on_price())
+///     }
+///
+///     #[private]
+///     pub fn on_price(&mut self, #[callback_result] price: Result<String, PromiseError>) {}
+/// }
+/// ```
+pub trait PromiseCallback<In> {
+    /// The promise returned once the callback is scheduled.
+    type Output;
+
+    /// Schedules this callback after `promise`.
+    fn schedule_after(self, promise: Promise) -> Self::Output;
+}
+
+impl<In> PromiseCallback<In> for Promise {
+    type Output = Promise;
+
+    fn schedule_after(self, promise: Promise) -> Self::Output {
+        promise.then(self)
+    }
+}
+
+impl<In, T, S> PromiseCallback<In> for TypedPromise<T, S> {
+    type Output = TypedPromise<T, S>;
+
+    fn schedule_after(self, promise: Promise) -> Self::Output {
+        TypedPromise::new(promise.then(self.promise))
+    }
+}
+
+impl<In, T, S> PromiseCallback<In> for TypedCallback<In, T, S> {
+    type Output = TypedPromise<T, S>;
+
+    fn schedule_after(self, promise: Promise) -> Self::Output {
+        TypedPromise::new(promise.then(self.promise))
+    }
+}
+
+/// Joined [`TypedPromise`]s, created with [`TypedPromise::and`], where `T` is the tuple of the
+/// types returned by each of the promises.
+///
+/// The callback receives the results either as a single
+/// `#[callback_result] results: (Result<A, PromiseError>, Result<B, PromiseError>, ...)`
+/// argument or as one `#[callback_result]` argument per promise. If all the promises return the
+/// same type, the results can also be received as a `Vec`, like for [`PromiseJoinAll`].
+///
+/// # Example
+/// ```no_run
+/// # use near_sdk::{env, near, PromiseError, TypedPromise};
+/// #[near(contract_state)]
+/// #[derive(Default)]
+/// pub struct Contract {}
+///
+/// #[near]
+/// impl Contract {
+///     pub fn value(&self, value: u64) -> u64 {
+///         value
+///     }
+///
+///     pub fn sum(&self) -> TypedPromise<u64> {
+///         let this = || Self::ext(env::current_account_id());
+///         this().value(1).and(this().value(2)).and(this().value(3)).then(this().on_sum())
+///     }
+///
+///     #[private]
+///     pub fn on_sum(&self, #[callback_result] values: Vec<Result<u64, PromiseError>>) -> u64 {
+///         values.into_iter().flatten().sum()
+///     }
+///
+///     pub fn describe(&self) -> TypedPromise<String> {
+///         let this = || Self::ext(env::current_account_id());
+///         this().value(1).and(this().sum()).then(this().on_describe())
+///     }
+///
+///     #[private]
+///     pub fn on_describe(
+///         &self,
+///         #[callback_unwrap] value: u64,
+///         #[callback_result] sum: Result<u64, PromiseError>,
+///     ) -> String {
+///         format!("{value} {sum:?}")
+///     }
+/// }
+/// ```
+pub struct PromiseJoin<T> {
+    promise: Promise,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Two joined [`TypedPromise`]s.
+pub type PromiseJoin2<A, B> = PromiseJoin<(A, B)>;
+
+impl<T> PromiseJoin<T> {
+    /// Returns the underlying untyped joint promise.
+    pub fn into_promise(self) -> Promise {
+        self.promise
+    }
+
+    /// Schedules `callback` after all the promises finish, see [`Promise::then`].
+    pub fn then<C: PromiseCallback<T>>(self, callback: C) -> C::Output {
+        callback.schedule_after(self.promise)
+    }
+}
+
+impl<T> From<PromiseJoin<T>> for Promise {
+    fn from(join: PromiseJoin<T>) -> Self {
+        join.promise
+    }
+}
+
+macro_rules! impl_promise_join {
+    ($($ty:ident),+; $next:ident) => {
+        impl<$($ty),+> PromiseJoin<($($ty,)+)> {
+            /// Joins one more promise, see [`Promise::and`].
+            pub fn and<$next, S>(
+                self,
+                other: TypedPromise<$next, S>,
+            ) -> PromiseJoin<($($ty,)+ $next,)> {
+                PromiseJoin { promise: self.promise.and(other.promise), _marker: PhantomData }
+            }
+        }
+    };
+}
+
+impl_promise_join!(A, B; C);
+impl_promise_join!(A, B, C; D);
+impl_promise_join!(A, B, C, D; E);
+impl_promise_join!(A, B, C, D, E; F);
+impl_promise_join!(A, B, C, D, E, F; G);
+impl_promise_join!(A, B, C, D, E, F, G; H);
+
+/// Allows callbacks receiving a `Vec` of results to be scheduled after joined promises returning
+/// the same type.
+macro_rules! impl_vec_callback {
+    ($($ty:ident),+) => {
+        impl<T, R, S> PromiseCallback<($(impl_vec_callback!(@same $ty T),)+)>
+            for TypedCallback<Vec<T>, R, S>
+        {
+            type Output = TypedPromise<R, S>;
+
+            fn schedule_after(self, promise: Promise) -> Self::Output {
+                TypedPromise::new(promise.then(self.promise))
+            }
+        }
+    };
+    (@same $ty:ident $same:ident) => { $same };
+}
+
+impl_vec_callback!(A, B);
+impl_vec_callback!(A, B, C);
+impl_vec_callback!(A, B, C, D);
+impl_vec_callback!(A, B, C, D, E);
+impl_vec_callback!(A, B, C, D, E, F);
+impl_vec_callback!(A, B, C, D, E, F, G);
+impl_vec_callback!(A, B, C, D, E, F, G, H);
+
+/// Any number of joined [`TypedPromise`]s of the same type, created with [`join_all`].
+///
+/// The callback receives the results, in the order of the joined promises, as
+/// `#[callback_result] results: Vec<Result<T, PromiseError>>`.
+pub struct PromiseJoinAll<T> {
+    promises: Vec<Promise>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> From<PromiseJoinAll<T>> for Promise {
+    fn from(join: PromiseJoinAll<T>) -> Self {
+        join.into_promise()
+    }
+}
+
+impl<T> PromiseJoinAll<T> {
+    /// Returns the underlying untyped joint promise.
+    ///
+    /// # Panics
+    /// If there are no promises to join.
+    pub fn into_promise(self) -> Promise {
+        self.promises
+            .into_iter()
+            .reduce(Promise::and)
+            .unwrap_or_else(|| crate::env::panic_str("Cannot join an empty list of promises"))
+    }
+
+    /// Schedules `callback` after all the promises finish, see [`Promise::then`].
+    pub fn then<C: PromiseCallback<Vec<T>>>(self, callback: C) -> C::Output {
+        callback.schedule_after(self.into_promise())
+    }
+}
+
+/// Joins all the given promises, see [`PromiseJoinAll`].
+pub fn join_all<T, S>(promises: impl IntoIterator<Item = TypedPromise<T, S>>) -> PromiseJoinAll<T> {
+    PromiseJoinAll {
+        promises: promises.into_iter().map(TypedPromise::into_promise).collect(),
+        _marker: PhantomData,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use crate::mock::MockAction;
+    use crate::test_utils::test_env::{alice, bob};
+    use crate::test_utils::{get_created_receipts, get_resumed_yields};
+    use crate::{
+        join_all, serde_json, test_utils::VMContextBuilder, testing_env, AccountId, Allowance,
+        CryptoHash, FunctionCallKeyPermission, Gas, GasWeight, KeyPermissionError, NearToken,
+        Promise, PromiseActionView, PromiseView, PublicKey, TypedPromise, YieldId,
+    };
+
+    fn pk() -> PublicKey {
+        "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse().unwrap()
+    }
+
+    fn get_actions() -> std::vec::IntoIter<MockAction> {
+        let receipts = get_created_receipts();
+        let first_receipt = receipts.into_iter().next().unwrap();
+        first_receipt.actions.into_iter()
+    }
+
+    fn has_add_key_with_full_access(public_key: PublicKey, nonce: Option<u64>) -> bool {
+        let public_key = near_crypto::PublicKey::try_from(public_key).unwrap();
+        get_actions().any(|el| {
+            matches!(
+                el,
+                MockAction::AddKeyWithFullAccess { public_key: p, nonce: n, receipt_index: _, }
+                if p == public_key
+                    && (nonce.is_none() || Some(n) == nonce)
+            )
+        })
+    }
+
+    fn has_add_key_with_function_call(
+        public_key: PublicKey,
+        allowance: u128,
+        receiver_id: AccountId,
+        function_names: String,
+        nonce: Option<u64>,
+    ) -> bool {
+        let public_key = near_crypto::PublicKey::try_from(public_key).unwrap();
+        get_actions().any(|el| {
+            matches!(
+                el,
+                MockAction::AddKeyWithFunctionCall {
+                    public_key: p,
+                    allowance: a,
+                    receiver_id: r,
+                    method_names,
+                    nonce: n,
+                    receipt_index: _,
+                }
+                if p == public_key
+                    && a.unwrap() == NearToken::from_yoctonear(allowance)
+                    && r == receiver_id
+                    && method_names.clone() == function_names.split(',').collect::<Vec<_>>()
+                    && (nonce.is_none() || Some(n) == nonce)
+            )
+        })
+    }
+
+    #[test]
+    fn test_add_full_access_key() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
 
         let public_key: PublicKey = pk();
 
@@ -751,6 +1970,65 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_add_function_call_key() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+
+        let permission = FunctionCallKeyPermission::new(bob())
+            .with_method_names(["method_a", "method_b"])
+            .unwrap()
+            .with_allowance(NearToken::from_yoctonear(100))
+            .unwrap();
+        {
+            Promise::new(alice()).create_account().add_function_call_key_with_nonce(
+                pk(),
+                permission,
+                42,
+            );
+        }
+
+        assert!(has_add_key_with_function_call(
+            pk(),
+            100,
+            bob(),
+            "method_a,method_b".to_string(),
+            Some(42)
+        ));
+    }
+
+    #[test]
+    fn test_function_call_key_permission_limits() {
+        let permission = FunctionCallKeyPermission::new(bob());
+        assert!(matches!(permission.allowance(), Allowance::Unlimited));
+        assert!(permission.method_names().is_empty());
+
+        assert_eq!(
+            permission.clone().with_allowance(NearToken::from_near(0)),
+            Err(KeyPermissionError::ZeroAllowance)
+        );
+        assert_eq!(
+            permission.clone().with_method_names(["a,b"]),
+            Err(KeyPermissionError::InvalidMethodName("a,b".to_string()))
+        );
+        assert_eq!(
+            permission.clone().with_method_names([""]),
+            Err(KeyPermissionError::InvalidMethodName(String::new()))
+        );
+        let long_name = "a".repeat(FunctionCallKeyPermission::MAX_METHOD_NAME_LEN + 1);
+        assert_eq!(
+            permission.clone().with_method_names([long_name.clone()]),
+            Err(KeyPermissionError::MethodNameTooLong(long_name))
+        );
+        // 8 names of 249 bytes take 2000 bytes with their terminating bytes.
+        let mut names = vec!["a".repeat(249); 8];
+        assert!(permission.clone().with_method_names(&names).is_ok());
+        names[0].push('a');
+        assert_eq!(
+            permission.with_method_names(names),
+            Err(KeyPermissionError::MethodNamesTooLong(2001))
+        );
+    }
+
     #[test]
     fn test_add_access_key() {
         testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
@@ -860,4 +2138,296 @@ mod tests {
         });
         assert!(has_action);
     }
+
+    fn call(account_id: AccountId, method: &str) -> TypedPromise<u64> {
+        TypedPromise::new(Promise::new(account_id).function_call(
+            method.to_string(),
+            vec![],
+            NearToken::from_near(0),
+            Gas::from_tgas(5),
+        ))
+    }
+
+    /// Returns the methods called by the promises the callback depends on, in the order in
+    /// which their results are passed to the callback.
+    fn callback_dependencies() -> Vec<String> {
+        let receipts = get_created_receipts();
+        let callback = receipts.iter().find(|receipt| !receipt.receipt_indices.is_empty()).unwrap();
+        callback
+            .receipt_indices
+            .iter()
+            .map(|&index| {
+                let receipt = receipts
+                    .iter()
+                    .find(|receipt| receipt.actions[0].receipt_index() == Some(index))
+                    .unwrap();
+                match &receipt.actions[0] {
+                    MockAction::FunctionCallWeight { method_name, .. } => {
+                        String::from_utf8(method_name.clone()).unwrap()
+                    }
+                    action => panic!("unexpected action {:?}", action),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_inspect() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+        let public_key = pk();
+        let promise = Promise::new(alice())
+            .create_account()
+            .transfer(NearToken::from_near(1))
+            .add_access_key_allowance(
+                public_key.clone(),
+                Allowance::limited(NearToken::from_millinear(250)).unwrap(),
+                bob(),
+                "a,b".to_string(),
+            )
+            .and(Promise::new(bob()).function_call_weight(
+                "a".to_string(),
+                b"{}".to_vec(),
+                NearToken::from_near(2),
+                Gas::from_tgas(5),
+                GasWeight(2),
+            ))
+            .and(Promise::new(alice()).delete_account(bob()))
+            .then(Promise::new(bob()).function_call(
+                "b".to_string(),
+                vec![],
+                NearToken::from_near(0),
+                Gas::from_tgas(10),
+            ));
+
+        let view = promise.inspect();
+        assert_eq!(view.total_deposit(), NearToken::from_near(3));
+        assert_eq!(view.total_static_gas(), Gas::from_tgas(15));
+        assert_eq!(
+            view.to_string(),
+            "join
+  - alice.near
+      create_account
+      transfer 1.00 NEAR
+      add_access_key ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp for bob.near [a, b], allowance 0.250 NEAR, nonce 0
+  - bob.near
+      function_call a(2 bytes), deposit 2.00 NEAR, gas 5.0 Tgas, weight 2
+  - alice.near
+      delete_account, beneficiary bob.near
+then bob.near
+  function_call b(0 bytes), deposit 0 NEAR, gas 10.0 Tgas, weight 0"
+        );
+        let PromiseView::Batch { after: Some(after), .. } = &view else { panic!() };
+        let PromiseView::Join { promises } = after.as_ref() else { panic!() };
+        assert_eq!(
+            promises[1],
+            PromiseView::Batch {
+                receiver_id: bob(),
+                actions: vec![PromiseActionView::FunctionCall {
+                    function_name: "a".to_string(),
+                    arguments: b"{}".to_vec().into(),
+                    deposit: NearToken::from_near(2),
+                    static_gas: Gas::from_tgas(5),
+                    gas_weight: 2,
+                }],
+                after: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_inspect_json() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+        let promise = Promise::new(bob())
+            .function_call(
+                "a".to_string(),
+                b"{}".to_vec(),
+                NearToken::from_yoctonear(1),
+                Gas::from_gas(7),
+            )
+            .then(Promise::new(alice()).transfer(NearToken::from_yoctonear(2)));
+        assert_eq!(
+            serde_json::to_value(promise.inspect()).unwrap(),
+            serde_json::json!({
+                "type": "batch",
+                "receiver_id": "alice.near",
+                "actions": [{ "type": "transfer", "deposit": "2" }],
+                "after": {
+                    "type": "batch",
+                    "receiver_id": "bob.near",
+                    "actions": [{
+                        "type": "function_call",
+                        "function_name": "a",
+                        "arguments": "e30=",
+                        "deposit": "1",
+                        "static_gas": "7",
+                        "gas_weight": 0,
+                    }],
+                    "after": null,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_global_contract_actions() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+        let code_hash: CryptoHash = [7; 32];
+        let promise = Promise::new(alice())
+            .deploy_global_contract(vec![1, 2, 3])
+            .deploy_global_contract_by_account_id(vec![4, 5])
+            .then(
+                Promise::new("child.alice.near".parse().unwrap())
+                    .create_account()
+                    .use_global_contract(code_hash)
+                    .use_global_contract_by_account_id(alice())
+                    .transfer(NearToken::from_near(1)),
+            );
+        assert_eq!(
+            promise.inspect().to_string(),
+            "alice.near
+  deploy_global_contract 3 bytes
+  deploy_global_contract_by_account_id 2 bytes
+then child.alice.near
+  create_account
+  use_global_contract US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx
+  use_global_contract_by_account_id alice.near
+  transfer 1.00 NEAR"
+        );
+        drop(promise);
+
+        let receipts = get_created_receipts();
+        assert_eq!(
+            receipts[0].actions,
+            vec![
+                MockAction::DeployGlobalContract { receipt_index: 0, code: vec![1, 2, 3] },
+                MockAction::DeployGlobalContractByAccountId { receipt_index: 0, code: vec![4, 5] },
+            ]
+        );
+        assert_eq!(
+            receipts[1].actions,
+            vec![
+                MockAction::CreateAccount { receipt_index: 1 },
+                MockAction::UseGlobalContract { receipt_index: 1, code_hash },
+                MockAction::UseGlobalContractByAccountId { receipt_index: 1, account_id: alice() },
+                MockAction::Transfer { receipt_index: 1, deposit: NearToken::from_near(1) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_then_refund_on_failure() {
+        testing_env!(VMContextBuilder::new().current_account_id(alice()).build());
+        let promise = Promise::new(bob())
+            .function_call(
+                "buy".to_string(),
+                b"{}".to_vec(),
+                NearToken::from_near(1),
+                Gas::from_tgas(10),
+            )
+            .then_refund_on_failure(bob(), NearToken::from_near(1));
+        let PromiseView::Batch { receiver_id, actions, .. } = promise.inspect() else { panic!() };
+        assert_eq!(receiver_id, alice());
+        assert_eq!(
+            actions,
+            vec![PromiseActionView::FunctionCall {
+                function_name: "__near_refund_on_failure".to_string(),
+                arguments: br#"{"refund_to":"bob.near","amount":"1000000000000000000000000"}"#
+                    .to_vec()
+                    .into(),
+                deposit: NearToken::from_yoctonear(0),
+                static_gas: Gas::from_tgas(5),
+                gas_weight: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_yield_and_resume() {
+        testing_env!(VMContextBuilder::new().current_account_id(alice()).build());
+        let yielded = Promise::yield_on("on_resume".to_string(), b"{}".to_vec(), Gas::from_tgas(5));
+        let yield_id = yielded.yield_id();
+        assert_eq!(
+            yielded.into_promise().inspect().to_string(),
+            format!(
+                "yield {yield_id}\n  function_call on_resume(2 bytes), deposit 0 NEAR, gas 5.0 \
+                 Tgas, weight 0"
+            )
+        );
+
+        assert!(Promise::resume(&yield_id, &"payload"));
+        assert!(!Promise::resume(&YieldId::from([0; 32]), &"payload"));
+        assert_eq!(
+            get_resumed_yields(),
+            vec![
+                (yield_id, b"\"payload\"".to_vec()),
+                (YieldId::from([0; 32]), b"\"payload\"".to_vec())
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot callback yielded promise.")]
+    fn test_yield_after_promise() {
+        testing_env!(VMContextBuilder::new().current_account_id(alice()).build());
+        let yielded = Promise::yield_on("on_resume".to_string(), b"{}".to_vec(), Gas::from_tgas(5));
+        let _ = Promise::new(bob()).transfer(NearToken::from_near(1)).then(yielded);
+    }
+
+    #[test]
+    fn test_yield_id_json() {
+        let yield_id = YieldId::from([1; 32]);
+        let json = serde_json::to_string(&yield_id).unwrap();
+        assert_eq!(json, "\"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi\"");
+        assert_eq!(serde_json::from_str::<YieldId>(&json).unwrap(), yield_id);
+        assert_eq!(CryptoHash::from(yield_id), [1; 32]);
+    }
+
+    #[test]
+    fn test_remaining_gas() {
+        testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(100)).build());
+        let gas = Promise::remaining_gas(Gas::from_tgas(30));
+        assert!(gas <= Gas::from_tgas(70));
+        assert!(gas > Gas::from_tgas(69));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough gas for the call chain: 150.0 Tgas is reserved")]
+    fn test_remaining_gas_insufficient() {
+        testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(100)).build());
+        Promise::remaining_gas(Gas::from_tgas(150));
+    }
+
+    #[test]
+    fn test_typed_join() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+
+        {
+            let callback = Promise::new(alice()).create_account();
+            call(bob(), "a").and(call(alice(), "b")).then(callback);
+        }
+
+        assert_eq!(get_created_receipts().len(), 3);
+        assert_eq!(callback_dependencies(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_join_all() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+
+        {
+            let callback = Promise::new(alice()).create_account();
+            join_all(["a", "b", "c"].map(|method| call(bob(), method))).then(callback);
+        }
+
+        assert_eq!(get_created_receipts().len(), 4);
+        assert_eq!(callback_dependencies(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot join an empty list of promises")]
+    fn test_join_all_empty() {
+        testing_env!(VMContextBuilder::new().signer_account_id(alice()).build());
+
+        join_all(Vec::<TypedPromise<u64>>::new()).into_promise();
+    }
 }
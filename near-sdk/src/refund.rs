@@ -0,0 +1,140 @@
+//! Refunds of deposits that were forwarded with a failed cross-contract call, see
+//! [`Promise::then_refund_on_failure`].
+//!
+//! The callback is exported by `near-sdk` itself under the name [`REFUND_ON_FAILURE_METHOD`], so
+//! the contract does not have to declare one. It is only compiled in with the
+//! `refund-on-failure` feature.
+
+use crate::{env, near, serde_json, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+/// Name of the callback method scheduled by [`Promise::then_refund_on_failure`].
+pub const REFUND_ON_FAILURE_METHOD: &str = "__near_refund_on_failure";
+
+/// Static gas attached to the refund callback.
+pub const REFUND_ON_FAILURE_GAS: Gas = Gas::from_tgas(5);
+
+/// Arguments of the refund callback.
+#[near(inside_nearsdk, serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefundArgs {
+    /// Account which receives the refund.
+    pub refund_to: AccountId,
+    /// Amount refunded if the call failed.
+    pub amount: NearToken,
+}
+
+#[derive(serde::Serialize)]
+struct RefundEvent<'a> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'static str,
+    data: [&'a RefundArgs; 1],
+}
+
+/// Implementation of the refund callback.
+///
+/// If the promise it is scheduled after succeeded, its result is returned as is. Otherwise
+/// `amount` is transferred to `refund_to` and a NEP-297 `deposit_refund` event is logged:
+///
+/// ```text
+/// EVENT_JSON:{"standard":"near-sdk","version":"1.0.0","event":"deposit_refund","data":[{"refund_to":"alice.near","amount":"1000"}]}
+/// ```
+///
+/// Panics if it is not called by the contract itself as the callback of a single promise.
+pub fn refund_on_failure() {
+    if env::current_account_id() != env::predecessor_account_id() {
+        env::panic_str(&format!("Method {REFUND_ON_FAILURE_METHOD} is private"));
+    }
+    let args: RefundArgs = serde_json::from_slice(&env::input().unwrap_or_default())
+        .unwrap_or_else(|_| env::panic_str("Failed to deserialize input from JSON."));
+    if env::promise_results_count() != 1 {
+        env::panic_str(&format!(
+            "Method {REFUND_ON_FAILURE_METHOD} should be called as the callback of a single promise"
+        ));
+    }
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => env::value_return(&value),
+        PromiseResult::Failed => {
+            if args.amount.is_zero() {
+                return;
+            }
+            Promise::new(args.refund_to.clone()).transfer(args.amount);
+            let event = RefundEvent {
+                standard: "near-sdk",
+                version: "1.0.0",
+                event: "deposit_refund",
+                data: [&args],
+            };
+            let json = serde_json::to_string(&event).unwrap_or_else(|_| env::abort());
+            env::log_str(&format!("EVENT_JSON:{json}"));
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub extern "C" fn __near_refund_on_failure() {
+    env::setup_panic_hook();
+    refund_on_failure();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+    use crate::{test_vm_config, testing_env};
+    use near_parameters::RuntimeFeesConfig;
+
+    fn setup(predecessor: &str, result: PromiseResult) {
+        let mut context = VMContextBuilder::new()
+            .current_account_id("market.near".parse().unwrap())
+            .predecessor_account_id(predecessor.parse().unwrap())
+            .build();
+        context.input = serde_json::to_vec(&RefundArgs {
+            refund_to: "alice.near".parse().unwrap(),
+            amount: NearToken::from_near(1),
+        })
+        .unwrap();
+        testing_env!(
+            context,
+            test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    #[test]
+    fn refunds_failed_call() {
+        setup("market.near", PromiseResult::Failed);
+        refund_on_failure();
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, "alice.near".parse::<AccountId>().unwrap());
+        assert_eq!(
+            get_logs(),
+            vec![
+                "EVENT_JSON:{\"standard\":\"near-sdk\",\"version\":\"1.0.0\",\
+                 \"event\":\"deposit_refund\",\"data\":[{\"refund_to\":\"alice.near\",\
+                 \"amount\":\"1000000000000000000000000\"}]}"
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_deposit_of_successful_call() {
+        setup("market.near", PromiseResult::Successful(b"\"ok\"".to_vec()));
+        refund_on_failure();
+
+        assert!(get_created_receipts().is_empty());
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Method __near_refund_on_failure is private")]
+    fn rejects_other_callers() {
+        setup("alice.near", PromiseResult::Failed);
+        refund_on_failure();
+    }
+}
@@ -0,0 +1,337 @@
+//! Storage backends the collections of [`store`](crate::store) read from and write to.
+//!
+//! By default collections use [`EnvStorage`], which goes through the `storage_*` functions of
+//! [`env`](crate::env) and therefore through the NEAR runtime or the
+//! [`MockedBlockchain`](crate::mock::MockedBlockchain). Outside of Wasm, [`with_backend`] swaps the
+//! backend for the current thread, so that the same collections can run over a plain
+//! [`BTreeMap`], a [`StateDump`](crate::store::offchain::StateDump) or a [`RecordingBackend`]:
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use near_sdk::store::backend::{with_backend, RecordingBackend, StorageAccess};
+//! use near_sdk::store::Vector;
+//!
+//! let mut backend = RecordingBackend::new(BTreeMap::new());
+//! with_backend(&mut backend, || {
+//!     let mut vec: Vector<u8> = Vector::new(b"v");
+//!     vec.push(1);
+//! });
+//! assert_eq!(backend.accesses(), &[StorageAccess::Write(b"v\0\0\0\0".to_vec())]);
+//! assert_eq!(backend.inner().get(b"v\0\0\0\0".as_slice()), Some(&vec![1]));
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::env;
+
+/// Key-value storage used by the collections of [`store`](crate::store).
+pub trait StorageBackend {
+    /// Reads the value stored under the given key.
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Writes key-value into storage. Returns `true` if a value existed under the key before.
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool;
+
+    /// Removes the value stored under the given key. Returns `true` if the value existed.
+    fn storage_remove(&mut self, key: &[u8]) -> bool;
+
+    /// Checks if there is a value stored under the given key.
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        self.storage_read(key).is_some()
+    }
+}
+
+/// Contract storage, accessed through [`env`](crate::env). This is the default backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnvStorage;
+
+impl StorageBackend for EnvStorage {
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        env::storage_read(key)
+    }
+
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool {
+        env::storage_write(key, value)
+    }
+
+    fn storage_remove(&mut self, key: &[u8]) -> bool {
+        env::storage_remove(key)
+    }
+
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        env::storage_has_key(key)
+    }
+}
+
+impl StorageBackend for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.get(key).cloned()
+    }
+
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.insert(key.to_vec(), value.to_vec()).is_some()
+    }
+
+    fn storage_remove(&mut self, key: &[u8]) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<B: StorageBackend + ?Sized> StorageBackend for &mut B {
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        (**self).storage_read(key)
+    }
+
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool {
+        (**self).storage_write(key, value)
+    }
+
+    fn storage_remove(&mut self, key: &[u8]) -> bool {
+        (**self).storage_remove(key)
+    }
+
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        (**self).storage_has_key(key)
+    }
+}
+
+/// A storage access made through a [`RecordingBackend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageAccess {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    Remove(Vec<u8>),
+    HasKey(Vec<u8>),
+}
+
+impl StorageAccess {
+    /// The key that was accessed.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::Read(key) | Self::Write(key) | Self::Remove(key) | Self::HasKey(key) => key,
+        }
+    }
+}
+
+/// Backend that forwards to another backend and records every key that is accessed, e.g. to
+/// analyze the storage reads and writes a method makes.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingBackend<B = EnvStorage> {
+    inner: B,
+    accesses: Vec<StorageAccess>,
+}
+
+impl<B: StorageBackend> RecordingBackend<B> {
+    /// Creates a backend recording all accesses made to `inner`.
+    pub fn new(inner: B) -> Self {
+        Self { inner, accesses: Vec::new() }
+    }
+
+    /// Accesses recorded so far, in the order they were made.
+    pub fn accesses(&self) -> &[StorageAccess] {
+        &self.accesses
+    }
+
+    /// Takes the recorded accesses, leaving the record empty.
+    pub fn take_accesses(&mut self) -> Vec<StorageAccess> {
+        std::mem::take(&mut self.accesses)
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the wrapped backend.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: StorageBackend> StorageBackend for RecordingBackend<B> {
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.accesses.push(StorageAccess::Read(key.to_vec()));
+        self.inner.storage_read(key)
+    }
+
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.accesses.push(StorageAccess::Write(key.to_vec()));
+        self.inner.storage_write(key, value)
+    }
+
+    fn storage_remove(&mut self, key: &[u8]) -> bool {
+        self.accesses.push(StorageAccess::Remove(key.to_vec()));
+        self.inner.storage_remove(key)
+    }
+
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        self.accesses.push(StorageAccess::HasKey(key.to_vec()));
+        self.inner.storage_has_key(key)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod active {
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+
+    use super::StorageBackend;
+    use crate::env;
+
+    type BackendPtr = NonNull<dyn StorageBackend + 'static>;
+
+    thread_local! {
+        static ACTIVE_BACKEND: Cell<Option<BackendPtr>> = const { Cell::new(None) };
+        /// Set while a method of the active backend runs.
+        static IN_USE: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Panics if called from within a method of the active backend, which would otherwise get
+    /// another mutable reference to itself.
+    fn assert_not_in_use() {
+        if IN_USE.with(Cell::get) {
+            env::panic_str("Storage backends can't access the collections of `store`");
+        }
+    }
+
+    /// Runs `f` with collections using `backend` on the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a method of a storage backend, as do collections used from there.
+    pub fn with_backend<B, R>(backend: &mut B, f: impl FnOnce() -> R) -> R
+    where
+        B: StorageBackend,
+    {
+        /// Restores the previously active backend, even if `f` panics.
+        struct Restore(Option<BackendPtr>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                ACTIVE_BACKEND.with(|active| active.set(self.0));
+            }
+        }
+
+        assert_not_in_use();
+        let backend: NonNull<dyn StorageBackend + '_> = NonNull::from(backend);
+        //* SAFETY: The pointer is only dereferenced by `with_active_backend` while it is set,
+        //*         and `Restore` unsets it before the borrow of `backend` ends.
+        let backend: BackendPtr = unsafe { std::mem::transmute(backend) };
+        let _restore = Restore(ACTIVE_BACKEND.with(|active| active.replace(Some(backend))));
+        f()
+    }
+
+    /// Runs `f` on the backend set by [`with_backend`] on the current thread. If there is none,
+    /// `f` is given back.
+    pub(crate) fn with_active_backend<F, R>(f: F) -> Result<R, F>
+    where
+        F: FnOnce(&mut dyn StorageBackend) -> R,
+    {
+        /// Clears `IN_USE`, even if the backend panics.
+        struct Release;
+
+        impl Drop for Release {
+            fn drop(&mut self) {
+                IN_USE.with(|in_use| in_use.set(false));
+            }
+        }
+
+        let Some(mut backend) = ACTIVE_BACKEND.with(Cell::get) else {
+            return Err(f);
+        };
+        assert_not_in_use();
+        IN_USE.with(|in_use| in_use.set(true));
+        let _release = Release;
+        //* SAFETY: `with_backend` keeps the backend borrowed mutably while the pointer is set, and
+        //*         `IN_USE` makes sure that only one reference to it is handed out at a time.
+        Ok(f(unsafe { backend.as_mut() }))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use active::with_active_backend;
+#[cfg(not(target_arch = "wasm32"))]
+pub use active::with_backend;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::LookupMap;
+
+    #[test]
+    fn btree_map_backend() {
+        let mut storage = BTreeMap::new();
+        with_backend(&mut storage, || {
+            let mut map: LookupMap<u8, u8> = LookupMap::new(b"m");
+            map.insert(1, 2);
+            map.flush();
+            assert_eq!(map.get(&1), Some(&2));
+        });
+        assert_eq!(storage.get(b"m\x01".as_slice()), Some(&vec![2]));
+        assert!(!env::storage_has_key(b"m\x01"));
+    }
+
+    #[test]
+    fn nested_backends() {
+        let mut outer = BTreeMap::new();
+        let mut inner = BTreeMap::new();
+        with_backend(&mut outer, || {
+            with_backend(&mut inner, || {
+                LookupMap::<u8, u8>::new(b"i").set(1, Some(1));
+            });
+            LookupMap::<u8, u8>::new(b"o").set(1, Some(1));
+        });
+        assert_eq!(outer.keys().collect::<Vec<_>>(), vec![b"o\x01"]);
+        assert_eq!(inner.keys().collect::<Vec<_>>(), vec![b"i\x01"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Storage backends can't access the collections of `store`")]
+    fn reentrant_backend() {
+        struct Reentrant;
+
+        impl StorageBackend for Reentrant {
+            fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+                LookupMap::<u8, u8>::new(b"r").get(&0);
+                Some(key.to_vec())
+            }
+
+            fn storage_write(&mut self, _key: &[u8], _value: &[u8]) -> bool {
+                false
+            }
+
+            fn storage_remove(&mut self, _key: &[u8]) -> bool {
+                false
+            }
+        }
+
+        with_backend(&mut Reentrant, || {
+            LookupMap::<u8, u8>::new(b"m").get(&1);
+        });
+    }
+
+    #[test]
+    fn recording_backend() {
+        let mut backend = RecordingBackend::new(EnvStorage);
+        with_backend(&mut backend, || {
+            let mut map: LookupMap<u8, u8> = LookupMap::new(b"m");
+            map.insert(1, 1);
+            map.flush();
+            assert!(!map.contains_key(&2));
+        });
+        assert_eq!(
+            backend.take_accesses(),
+            vec![
+                StorageAccess::Read(b"m\x01".to_vec()),
+                StorageAccess::Write(b"m\x01".to_vec()),
+                StorageAccess::HasKey(b"m\x02".to_vec()),
+            ]
+        );
+        assert!(backend.accesses().is_empty());
+        assert_eq!(env::storage_read(b"m\x01"), Some(vec![1]));
+    }
+}
@@ -4,6 +4,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk_macros::near;
 use once_cell::unsync::OnceCell;
 
+use crate::store::storage;
 use crate::utils::StableMap;
 use crate::{env, CacheEntry, EntryState, IntoStorageKey};
 
@@ -61,11 +62,11 @@ where
                             buf.clear();
                             BorshSerialize::serialize(modified, &mut buf)
                                 .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
-                            env::storage_write(&key_buf, &buf);
+                            storage::storage_write(&key_buf, &buf);
                         }
                         None => {
                             // Element was removed, clear the storage for the value
-                            env::storage_remove(&key_buf);
+                            storage::storage_remove(&key_buf);
                         }
                     }
 
@@ -104,7 +105,7 @@ where
         let entry = self.cache.get(index).get_or_init(|| {
             let mut buf = Vec::with_capacity(self.prefix.len() + 4);
             Self::index_to_lookup_key(&self.prefix, index, &mut buf);
-            let storage_bytes = env::storage_read(&buf);
+            let storage_bytes = storage::storage_read(&buf);
             let value = storage_bytes.as_deref().map(Self::deserialize_element);
             CacheEntry::new_cached(value)
         });
@@ -118,7 +119,7 @@ where
         entry.get_or_init(|| {
             let mut key = Vec::with_capacity(prefix.len() + 4);
             Self::index_to_lookup_key(prefix, index, &mut key);
-            let storage_bytes = env::storage_read(&key);
+            let storage_bytes = storage::storage_read(&key);
             let value = storage_bytes.as_deref().map(Self::deserialize_element);
             CacheEntry::new_cached(value)
         });
@@ -12,9 +12,9 @@ use once_cell::unsync::OnceCell;
 use near_sdk_macros::near;
 
 use crate::env;
-use crate::store::ERR_INCONSISTENT_STATE;
+use crate::store::{storage, ERR_INCONSISTENT_STATE};
 use crate::utils::{CacheEntry, EntryState};
-use crate::IntoStorageKey;
+use crate::{DeserializationError, IntoStorageKey, StorageError};
 
 const ERR_VALUE_SERIALIZATION: &str = "Cannot serialize value with Borsh";
 const ERR_VALUE_DESERIALIZATION: &str = "Cannot deserialize value with Borsh";
@@ -32,18 +32,27 @@ pub(crate) fn load_and_deserialize<T>(key: &[u8]) -> CacheEntry<T>
 where
     T: BorshDeserialize,
 {
-    let bytes = expect_key_exists(env::storage_read(key));
+    let bytes = expect_key_exists(storage::storage_read(key));
     let val =
         T::try_from_slice(&bytes).unwrap_or_else(|_| env::panic_str(ERR_VALUE_DESERIALIZATION));
     CacheEntry::new_cached(Some(val))
 }
 
+fn try_load_and_deserialize<T>(key: &[u8]) -> Result<CacheEntry<T>, StorageError>
+where
+    T: BorshDeserialize,
+{
+    let bytes = storage::storage_read(key).ok_or(StorageError::NotFound)?;
+    let val = T::try_from_slice(&bytes).map_err(DeserializationError::new)?;
+    Ok(CacheEntry::new_cached(Some(val)))
+}
+
 pub(crate) fn serialize_and_store<T>(key: &[u8], value: &T)
 where
     T: BorshSerialize,
 {
     let serialized = to_vec(value).unwrap_or_else(|_| env::panic_str(ERR_VALUE_SERIALIZATION));
-    env::storage_write(key, &serialized);
+    storage::storage_write(key, &serialized);
 }
 
 /// An persistent lazily loaded value, that stores a value in the storage.
@@ -122,7 +131,7 @@ where
 
     /// Removes the underlying storage item. Useful for deprecating the obsolete [`Lazy`] values.
     pub fn remove(&mut self) -> bool {
-        env::storage_remove(&self.storage_key)
+        storage::storage_remove(&self.storage_key)
     }
 }
 
@@ -141,6 +150,35 @@ where
         expect_consistent_state(entry.value().as_ref())
     }
 
+    /// Returns a reference to the lazily loaded storage value, or an error if the value at the
+    /// key does not exist or can't be deserialized. Unlike [`get`](Self::get), this doesn't
+    /// panic, so the contract can recover from a value written in an older format.
+    ///
+    /// The value is only cached if it was loaded successfully.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::borsh;
+    /// use near_sdk::store::Lazy;
+    /// use near_sdk::StorageError;
+    ///
+    /// let mut old = Lazy::new(b"a", 1u8);
+    /// old.flush();
+    /// let serialized = borsh::to_vec(&old).unwrap();
+    ///
+    /// // The type of the value was changed by an upgrade of the contract.
+    /// let new: Lazy<u64> = borsh::from_slice(&serialized).unwrap();
+    /// assert!(matches!(new.try_get(), Err(StorageError::Deserialization(_))));
+    ///
+    /// old.remove();
+    /// let new: Lazy<u64> = borsh::from_slice(&serialized).unwrap();
+    /// assert_eq!(new.try_get(), Err(StorageError::NotFound));
+    /// ```
+    pub fn try_get(&self) -> Result<&T, StorageError> {
+        let entry = self.cache.get_or_try_init(|| try_load_and_deserialize(&self.storage_key))?;
+        entry.value().as_ref().ok_or(StorageError::InconsistentState)
+    }
+
     /// Returns a reference to the lazily loaded storage value.
     /// The load from storage only happens once, and if the value is already cached, it will not
     /// be reloaded.
@@ -199,6 +237,26 @@ mod tests {
         assert!(!env::storage_has_key(b"m"));
     }
 
+    #[test]
+    pub fn test_try_get() {
+        let mut lazy = Lazy::new(b"t", 8u8);
+        lazy.flush();
+        let serialized = to_vec(&lazy).unwrap();
+
+        let loaded = Lazy::<u8>::try_from_slice(&serialized).unwrap();
+        assert_eq!(loaded.try_get(), Ok(&8));
+
+        let mismatched = Lazy::<String>::try_from_slice(&serialized).unwrap();
+        assert!(matches!(mismatched.try_get(), Err(StorageError::Deserialization(_))));
+        // The failed load isn't cached, so the value can still be replaced.
+        env::storage_write(b"t", &to_vec("fixed").unwrap());
+        assert_eq!(mismatched.try_get().map(String::as_str), Ok("fixed"));
+
+        lazy.remove();
+        let removed = Lazy::<u8>::try_from_slice(&serialized).unwrap();
+        assert_eq!(removed.try_get(), Err(StorageError::NotFound));
+    }
+
     #[test]
     pub fn test_debug() {
         let mut lazy = Lazy::new(b"m", 8u8);
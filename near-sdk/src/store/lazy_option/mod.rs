@@ -6,6 +6,7 @@ use once_cell::unsync::OnceCell;
 
 use crate::env;
 use crate::store::lazy::{load_and_deserialize, serialize_and_store};
+use crate::store::storage;
 use crate::utils::{CacheEntry, EntryState};
 use crate::IntoStorageKey;
 
@@ -89,7 +90,7 @@ where
             match v.value().as_ref() {
                 Some(value) => serialize_and_store(&self.prefix, value),
                 None => {
-                    env::storage_remove(&self.prefix);
+                    storage::storage_remove(&self.prefix);
                 }
             }
 
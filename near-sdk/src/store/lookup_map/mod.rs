@@ -10,8 +10,9 @@ use once_cell::unsync::OnceCell;
 
 use super::ERR_NOT_EXIST;
 use crate::store::key::{Identity, ToKey};
+use crate::store::storage;
 use crate::utils::{EntryState, StableMap};
-use crate::{env, CacheEntry, IntoStorageKey};
+use crate::{env, CacheEntry, DeserializationError, IntoStorageKey};
 
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
 
@@ -219,10 +220,25 @@ where
         K: Borrow<Q>,
     {
         let key = H::to_key(prefix, key, &mut Vec::new());
-        let storage_bytes = env::storage_read(key.as_ref());
+        let storage_bytes = storage::storage_read(key.as_ref());
         (key, storage_bytes.as_deref().map(Self::deserialize_element))
     }
 
+    fn try_load_element<Q: ?Sized>(
+        prefix: &[u8],
+        key: &Q,
+    ) -> Result<(H::KeyType, Option<V>), DeserializationError>
+    where
+        Q: BorshSerialize,
+        K: Borrow<Q>,
+    {
+        let key = H::to_key(prefix, key, &mut Vec::new());
+        let element = storage::storage_read(key.as_ref())
+            .map(|bytes| V::try_from_slice(&bytes).map_err(DeserializationError::new))
+            .transpose()?;
+        Ok((key, element))
+    }
+
     /// Returns a reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type, but
@@ -254,6 +270,38 @@ where
         entry.value().as_ref()
     }
 
+    /// Returns a reference to the value corresponding to the key, or an error if the stored value
+    /// can't be deserialized. Unlike [`get`](Self::get), this doesn't panic, so the contract can
+    /// skip or migrate entries written in an older format.
+    ///
+    /// The value is only cached if it was loaded successfully.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::LookupMap;
+    ///
+    /// let mut map: LookupMap<u32, u8> = LookupMap::new(b"m");
+    /// map.insert(1, 7);
+    /// map.flush();
+    ///
+    /// let map: LookupMap<u32, String> = LookupMap::new(b"m");
+    /// assert!(map.try_get(&1).is_err());
+    /// assert_eq!(map.try_get(&2), Ok(None));
+    /// ```
+    pub fn try_get<Q: ?Sized>(&self, k: &Q) -> Result<Option<&V>, DeserializationError>
+    where
+        K: Borrow<Q>,
+        Q: BorshSerialize + ToOwned<Owned = K>,
+    {
+        let cached = self.cache.get(k.to_owned());
+        let entry = cached.value.get_or_try_init(|| {
+            let (key, element) = Self::try_load_element(&self.prefix, k)?;
+            let _ = cached.hash.set(key);
+            Ok::<_, DeserializationError>(CacheEntry::new_cached(element))
+        })?;
+        Ok(entry.value().as_ref())
+    }
+
     pub(crate) fn get_mut_inner<Q: ?Sized>(&mut self, k: &Q) -> &mut CacheEntry<V>
     where
         K: Borrow<Q>,
@@ -353,7 +401,7 @@ where
 
         // Value is not in cache, check if storage has value for given key.
         let storage_key = H::to_key(&self.prefix, k, &mut Vec::new());
-        let contains = env::storage_has_key(storage_key.as_ref());
+        let contains = storage::storage_has_key(storage_key.as_ref());
 
         if !contains {
             // If value not in cache and not in storage, can set a cached `None`
@@ -443,11 +491,11 @@ where
                             buf.clear();
                             BorshSerialize::serialize(modified, &mut buf)
                                 .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
-                            env::storage_write(key.as_ref(), &buf);
+                            storage::storage_write(key.as_ref(), &buf);
                         }
                         None => {
                             // Element was removed, clear the storage for the value
-                            env::storage_remove(key.as_ref());
+                            storage::storage_remove(key.as_ref());
                         }
                     }
 
@@ -485,6 +533,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_get() {
+        let mut map = LookupMap::new(b"m");
+        map.insert(1u32, 5u64);
+        map.insert(2u32, 6u64);
+        map.flush();
+
+        // Overwrite one entry with a value of a different type.
+        let mut corrupted = LookupMap::new(b"m");
+        corrupted.set(1u32, Some(7u8));
+        corrupted.flush();
+
+        let map: LookupMap<u32, u64> = LookupMap::new(b"m");
+        assert!(map.try_get(&1).is_err());
+        assert_eq!(map.try_get(&2), Ok(Some(&6)));
+        assert_eq!(map.try_get(&3), Ok(None));
+        // The failed entry isn't cached.
+        env::storage_write(&[b"m".as_slice(), &1u32.to_le_bytes()].concat(), &8u64.to_le_bytes());
+        assert_eq!(map.try_get(&1), Ok(Some(&8)));
+    }
+
     #[test]
     fn test_insert_has_key() {
         let mut map = LookupMap::new(b"m");
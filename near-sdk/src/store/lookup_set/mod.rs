@@ -1,7 +1,8 @@
 mod impls;
 
 use crate::store::key::{Identity, ToKey};
-use crate::{env, IntoStorageKey};
+use crate::store::storage;
+use crate::IntoStorageKey;
 use borsh::BorshSerialize;
 use std::borrow::Borrow;
 use std::fmt;
@@ -115,7 +116,7 @@ where
         Q: BorshSerialize,
     {
         let lookup_key = H::to_key(&self.prefix, value, &mut Vec::new());
-        env::storage_has_key(lookup_key.as_ref())
+        storage::storage_has_key(lookup_key.as_ref())
     }
 
     /// Adds a value to the set.
@@ -125,7 +126,7 @@ where
     /// If the set did have this value present, false is returned.
     pub fn insert(&mut self, value: T) -> bool {
         let lookup_key = H::to_key(&self.prefix, &value, &mut Vec::new());
-        !env::storage_write(lookup_key.as_ref(), &[])
+        !storage::storage_write(lookup_key.as_ref(), &[])
     }
 
     /// Removes a value from the set. Returns whether the value was present in the set.
@@ -138,7 +139,7 @@ where
         Q: BorshSerialize,
     {
         let lookup_key = H::to_key(&self.prefix, value, &mut Vec::new());
-        env::storage_remove(lookup_key.as_ref())
+        storage::storage_remove(lookup_key.as_ref())
     }
 }
 
@@ -95,6 +95,11 @@
 //! * [`near_sdk::env::storage_remove`](crate::env::storage_remove)
 //! * [`near_sdk::env::storage_has_key`](crate::env::storage_has_key)
 //!
+//! These calls are made through [`backend::EnvStorage`]. Outside of Wasm, the collections can run
+//! over other storage backends, see the [`backend`] module. With the `non-contract-usage` feature,
+//! they can also be opened off-chain against a raw dump of the contract state, see the
+//! [`offchain`] module.
+//!
 //! ## Module's glossary:
 //!
 //! The collections are as follows:
@@ -177,6 +182,12 @@ pub(crate) use self::free_list::FreeList;
 /// Storage key hash function types and trait to override map hash functions.
 pub mod key;
 
+pub mod backend;
+mod storage;
+
+#[cfg(all(feature = "non-contract-usage", not(target_arch = "wasm32")))]
+pub mod offchain;
+
 pub(crate) const ERR_INCONSISTENT_STATE: &str =
     "The collection is in an inconsistent state. Did previous smart \
         contract execution terminate unexpectedly?";
@@ -0,0 +1,283 @@
+//! Off-chain access to the raw state of a contract.
+//!
+//! A [`StateDump`] holds the key-value pairs of a contract's storage, as returned by the
+//! `view_state` RPC query. Collections from [`store`](crate::store) can be opened against it
+//! with the same types and prefixes as the contract uses, which avoids duplicating collection
+//! internals (such as the layout of indices or free list buckets) in off-chain tooling.
+//!
+//! ```
+//! use near_sdk::store::{offchain::StateDump, LookupMap};
+//!
+//! let json = r#"{
+//!     "values": [{ "key": "bQIAAABpZA==", "value": "CgAAAAAAAAAAAAAAAAAAAA==" }]
+//! }"#;
+//! let mut state = StateDump::from_view_state_json(json).unwrap();
+//!
+//! let value = state.view(|| {
+//!     let map: LookupMap<String, u128> = LookupMap::new(b"m");
+//!     map.get("id").copied()
+//! });
+//! assert_eq!(value, Some(10));
+//! ```
+//!
+//! Only storage accesses made by the collections of [`store`](crate::store) are served from the
+//! dump. Calls made directly to [`env`](crate::env) still go to the host.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use base64::Engine;
+use borsh::BorshDeserialize;
+
+use super::backend::{with_backend, StorageBackend};
+
+/// Key under which the contract struct is stored, see [`env::state_read`](crate::env::state_read).
+const STATE_KEY: &[u8] = b"STATE";
+
+/// Raw key-value state of a contract, loaded into memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDump {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl StateDump {
+    /// Creates an empty state dump.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the output of a `view_state` RPC query.
+    ///
+    /// Accepts either the full JSON-RPC response, its `result` object or just the `values` array,
+    /// where each value has base64 encoded `key` and `value` fields.
+    pub fn from_view_state_json(json: &str) -> Result<Self, StateDumpError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let value = value.get("result").unwrap_or(&value);
+        let value = value.get("values").unwrap_or(value);
+        let items: Vec<ViewStateItem> = serde_json::from_value(value.clone())?;
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut entries = BTreeMap::new();
+        for ViewStateItem { key, value } in items {
+            entries.insert(engine.decode(key)?, engine.decode(value)?);
+        }
+        Ok(Self { entries })
+    }
+
+    /// Returns the value stored under `key`.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(Vec::as_slice)
+    }
+
+    /// Inserts a key-value pair, returning the value previously stored under the key.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Option<Vec<u8>> {
+        self.entries.insert(key.to_vec(), value.to_vec())
+    }
+
+    /// Removes the value stored under `key`, returning it.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.remove(key)
+    }
+
+    /// Returns `true` if there is a value stored under `key`.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Number of key-value pairs in the dump.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the dump contains no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the key-value pairs in the dump, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries.iter().map(|(k, v)| (k.as_slice(), v.as_slice()))
+    }
+
+    /// Deserializes the contract struct, which the `#[near(contract_state)]` macro stores under the
+    /// `STATE` key. Returns `Ok(None)` if the contract has no state.
+    pub fn state<T: BorshDeserialize>(&self) -> Result<Option<T>, StateDumpError> {
+        self.get(STATE_KEY).map(T::try_from_slice).transpose().map_err(Into::into)
+    }
+
+    /// Runs `f` with collections from [`store`](crate::store) reading from and writing to this
+    /// dump on the current thread, see [`with_backend`].
+    ///
+    /// Changes are flushed to the dump when collections are dropped or flushed within `f`, as
+    /// they would be to contract storage.
+    pub fn view<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        with_backend(self, f)
+    }
+}
+
+impl StorageBackend for StateDump {
+    fn storage_read(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.get(key).map(<[u8]>::to_vec)
+    }
+
+    fn storage_write(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.insert(key, value).is_some()
+    }
+
+    fn storage_remove(&mut self, key: &[u8]) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn storage_has_key(&mut self, key: &[u8]) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for StateDump
+where
+    K: Into<Vec<u8>>,
+    V: Into<Vec<u8>>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self { entries: iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect() }
+    }
+}
+
+impl From<BTreeMap<Vec<u8>, Vec<u8>>> for StateDump {
+    fn from(entries: BTreeMap<Vec<u8>, Vec<u8>>) -> Self {
+        Self { entries }
+    }
+}
+
+impl From<StateDump> for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn from(state: StateDump) -> Self {
+        state.entries
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ViewStateItem {
+    key: String,
+    value: String,
+}
+
+/// Error returned when a [`StateDump`] cannot be loaded or decoded.
+#[derive(Debug)]
+pub struct StateDumpError {
+    kind: StateDumpErrorKind,
+}
+
+#[derive(Debug)]
+enum StateDumpErrorKind {
+    Json(serde_json::Error),
+    Base64(base64::DecodeError),
+    Borsh(std::io::Error),
+}
+
+impl fmt::Display for StateDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            StateDumpErrorKind::Json(e) => write!(f, "invalid view state json: {}", e),
+            StateDumpErrorKind::Base64(e) => write!(f, "base64 decoding error: {}", e),
+            StateDumpErrorKind::Borsh(e) => write!(f, "cannot deserialize state: {}", e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for StateDumpError {
+    fn from(e: serde_json::Error) -> Self {
+        Self { kind: StateDumpErrorKind::Json(e) }
+    }
+}
+
+impl From<base64::DecodeError> for StateDumpError {
+    fn from(e: base64::DecodeError) -> Self {
+        Self { kind: StateDumpErrorKind::Base64(e) }
+    }
+}
+
+impl From<std::io::Error> for StateDumpError {
+    fn from(e: std::io::Error) -> Self {
+        Self { kind: StateDumpErrorKind::Borsh(e) }
+    }
+}
+
+impl std::error::Error for StateDumpError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::near;
+    use crate::store::{IterableMap, LookupMap, Vector};
+
+    #[near(inside_nearsdk)]
+    struct Contract {
+        owners: LookupMap<u64, String>,
+        values: Vector<u32>,
+        records: IterableMap<String, u64>,
+    }
+
+    fn contract() -> Contract {
+        Contract {
+            owners: LookupMap::new(b"o"),
+            values: Vector::new(b"v"),
+            records: IterableMap::new(b"r"),
+        }
+    }
+
+    #[test]
+    fn round_trip_through_dump() {
+        let mut state = StateDump::new();
+        state.view(|| {
+            let mut contract = contract();
+            contract.owners.insert(1, "alice.near".to_string());
+            contract.values.extend([1, 2, 3]);
+            contract.records.insert("a".to_string(), 5);
+            contract.records.insert("b".to_string(), 6);
+            crate::store::storage::storage_write(STATE_KEY, &borsh::to_vec(&contract).unwrap());
+        });
+        // Values, records with their index and the contract state itself.
+        assert_eq!(state.len(), 3 + 2 * 2 + 1 + 1);
+
+        let contract: Contract = state.state().unwrap().unwrap();
+        state.view(|| {
+            assert_eq!(contract.owners.get(&1).map(String::as_str), Some("alice.near"));
+            assert_eq!(contract.values.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+            assert_eq!(
+                contract.records.iter().map(|(k, v)| (k.as_str(), *v)).collect::<Vec<_>>(),
+                vec![("a", 5), ("b", 6)]
+            );
+        });
+    }
+
+    #[test]
+    fn view_does_not_touch_env_storage() {
+        let mut state = StateDump::new();
+        state.view(|| {
+            let mut map: LookupMap<u8, u8> = LookupMap::new(b"m");
+            map.insert(1, 2);
+        });
+        assert!(!crate::env::storage_has_key(b"m\x01"));
+        assert_eq!(state.get(b"m\x01"), Some(&[2][..]));
+    }
+
+    #[test]
+    fn parse_view_state() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": "dontcare",
+            "result": {
+                "values": [{ "key": "U1RBVEU=", "value": "AQI=", "proof": [] }],
+                "block_height": 1,
+                "block_hash": "11111111111111111111111111111111"
+            }
+        }"#;
+        let state = StateDump::from_view_state_json(json).unwrap();
+        assert_eq!(state.iter().collect::<Vec<_>>(), vec![(&b"STATE"[..], &[1, 2][..])]);
+
+        let values = r#"[{ "key": "U1RBVEU=", "value": "AQI=" }]"#;
+        assert_eq!(StateDump::from_view_state_json(values).unwrap(), state);
+
+        assert!(StateDump::from_view_state_json(r#"[{ "key": "!", "value": "" }]"#).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+//! Storage access used by the collections of this module.
+//!
+//! Collections go through these functions instead of calling [`env`](crate::env) directly, so
+//! that they can run over the [`StorageBackend`] set with
+//! [`with_backend`](super::backend::with_backend) outside of Wasm.
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::backend::with_active_backend;
+use super::backend::{EnvStorage, StorageBackend};
+
+/// Runs `f` on the active storage backend, which is [`EnvStorage`] unless another one was set.
+fn with_storage<R>(f: impl FnOnce(&mut dyn StorageBackend) -> R) -> R {
+    #[cfg(not(target_arch = "wasm32"))]
+    let f = match with_active_backend(f) {
+        Ok(result) => return result,
+        Err(f) => f,
+    };
+    f(&mut EnvStorage)
+}
+
+/// Reads the value stored under the given key.
+pub(crate) fn storage_read(key: &[u8]) -> Option<Vec<u8>> {
+    with_storage(|storage| storage.storage_read(key))
+}
+
+/// Writes key-value into storage. Returns `true` if a value existed under the key before.
+pub(crate) fn storage_write(key: &[u8], value: &[u8]) -> bool {
+    with_storage(|storage| storage.storage_write(key, value))
+}
+
+/// Removes the value stored under the given key. Returns `true` if the value existed.
+pub(crate) fn storage_remove(key: &[u8]) -> bool {
+    with_storage(|storage| storage.storage_remove(key))
+}
+
+/// Checks if there is a value stored under the given key.
+pub(crate) fn storage_has_key(key: &[u8]) -> bool {
+    with_storage(|storage| storage.storage_has_key(key))
+}
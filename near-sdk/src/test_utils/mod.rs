@@ -4,6 +4,7 @@ pub mod test_env;
 
 pub(crate) mod context;
 use crate::mock::Receipt;
+use crate::{PromiseResult, YieldId};
 #[allow(deprecated)]
 pub use context::{accounts, testing_env_with_promise_results, VMContextBuilder};
 
@@ -98,6 +99,51 @@ pub fn get_created_receipts() -> Vec<Receipt> {
     crate::mock::with_mocked_blockchain(|b| b.created_receipts())
 }
 
+/// Returns the ids and payloads of the yielded promises resumed with [`Promise::resume`]. Only
+/// available in unit tests.
+///
+/// [`Promise::resume`]: crate::Promise::resume
+pub fn get_resumed_yields() -> Vec<(YieldId, Vec<u8>)> {
+    crate::mock::with_mocked_blockchain(|b| b.resumed_yields())
+}
+
+/// Promise result received by the callback of a yielded promise resumed with `payload`, which can
+/// be passed to [`testing_env!`] to call the callback in unit tests.
+///
+/// # Example
+/// ```
+/// use near_sdk::test_utils::{yield_resumed, yield_timed_out, VMContextBuilder};
+/// use near_sdk::{env, test_vm_config, testing_env, PromiseResult, RuntimeFeesConfig};
+///
+/// let context = VMContextBuilder::new().build();
+/// testing_env!(
+///     context.clone(),
+///     test_vm_config(),
+///     RuntimeFeesConfig::test(),
+///     Default::default(),
+///     vec![yield_resumed(&"signature")],
+/// );
+/// assert_eq!(env::promise_result(0), PromiseResult::Successful(b"\"signature\"".to_vec()));
+///
+/// testing_env!(
+///     context,
+///     test_vm_config(),
+///     RuntimeFeesConfig::test(),
+///     Default::default(),
+///     vec![yield_timed_out()],
+/// );
+/// assert_eq!(env::promise_result(0), PromiseResult::Failed);
+/// ```
+pub fn yield_resumed<T: serde::Serialize>(payload: &T) -> PromiseResult {
+    PromiseResult::Successful(serde_json::to_vec(payload).unwrap())
+}
+
+/// Promise result received by the callback of a yielded promise which timed out, see
+/// [`yield_resumed`].
+pub fn yield_timed_out() -> PromiseResult {
+    PromiseResult::Failed
+}
+
 /// Objects stored on the trie directly should have identifiers. If identifier is not provided
 /// explicitly than `Default` trait would use this index to generate an id.
 #[cfg(test)]
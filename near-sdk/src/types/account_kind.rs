@@ -0,0 +1,77 @@
+use near_account_id::{AccountIdRef, AccountType};
+
+/// Kind of a NEAR account, as determined by its ID.
+///
+/// # Example
+/// ```
+/// use near_sdk::{AccountId, AccountKind};
+///
+/// let alice: AccountId = "alice.near".parse().unwrap();
+/// assert_eq!(AccountKind::of(&alice), AccountKind::SubAccount);
+///
+/// let eth: AccountId = "0xb794f5ea0ba39494ce839613fffba74279579268".parse().unwrap();
+/// assert_eq!(AccountKind::of(&eth), AccountKind::EthImplicit);
+/// assert!(AccountKind::of(&eth).is_implicit());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountKind {
+    /// 64 character hex encoding of an ed25519 public key.
+    NearImplicit,
+    /// `0x` followed by the 40 character hex encoding of an Ethereum address.
+    EthImplicit,
+    /// Named account without a parent, e.g. `near`.
+    TopLevel,
+    /// Named account created by its parent account, e.g. `alice.near`.
+    SubAccount,
+}
+
+impl AccountKind {
+    /// Returns the kind of the given account.
+    pub fn of(account_id: &AccountIdRef) -> Self {
+        match account_id.get_account_type() {
+            AccountType::NearImplicitAccount => Self::NearImplicit,
+            AccountType::EthImplicitAccount => Self::EthImplicit,
+            AccountType::NamedAccount if account_id.get_parent_account_id().is_none() => {
+                Self::TopLevel
+            }
+            AccountType::NamedAccount => Self::SubAccount,
+        }
+    }
+
+    /// Whether the account is implicit, i.e. derived from a key or address and created by
+    /// transferring tokens to it.
+    pub fn is_implicit(&self) -> bool {
+        matches!(self, Self::NearImplicit | Self::EthImplicit)
+    }
+
+    /// Whether the account is a named one.
+    pub fn is_named(&self) -> bool {
+        !self.is_implicit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_kinds() {
+        for (account_id, kind) in [
+            ("near", AccountKind::TopLevel),
+            ("system", AccountKind::TopLevel),
+            ("alice.near", AccountKind::SubAccount),
+            ("app.alice.near", AccountKind::SubAccount),
+            (
+                "0a5e97870f263700f46aa00d967821199b9bc5a120fa5fee9fd1e3f5ff1ce4b8",
+                AccountKind::NearImplicit,
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", AccountKind::EthImplicit),
+            // Too short to be implicit.
+            ("0xb794f5ea0ba39494ce839613fffba7427957926", AccountKind::TopLevel),
+        ] {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(AccountKind::of(account_id), kind, "{}", account_id);
+            assert_eq!(kind.is_named(), !kind.is_implicit());
+        }
+    }
+}
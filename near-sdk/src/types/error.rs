@@ -77,3 +77,67 @@ impl FunctionError for Abort {
         crate::env::abort()
     }
 }
+
+/// Error of deserializing a value read from storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializationError {
+    message: String,
+}
+
+impl DeserializationError {
+    /// Creates an error with the message of the underlying error.
+    pub fn new(error: impl std::fmt::Display) -> Self {
+        Self { message: error.to_string() }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for DeserializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot deserialize value with Borsh: {}", self.message)
+    }
+}
+
+impl std::error::Error for DeserializationError {}
+
+/// Error of reading a value from storage, returned by the `try_` variants of reads which
+/// otherwise panic, like [`store::Lazy::try_get`](crate::store::Lazy::try_get).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StorageError {
+    /// There is no value under the key.
+    NotFound,
+    /// The stored value can't be deserialized, e.g. because it was written by an older version of
+    /// the contract.
+    Deserialization(DeserializationError),
+    /// The collection is in an inconsistent state.
+    InconsistentState,
+}
+
+impl From<DeserializationError> for StorageError {
+    fn from(error: DeserializationError) -> Self {
+        Self::Deserialization(error)
+    }
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("No value found for the given key"),
+            Self::Deserialization(error) => error.fmt(f),
+            Self::InconsistentState => f.write_str(crate::store::ERR_INCONSISTENT_STATE),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialization(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -4,10 +4,16 @@ pub use self::vm_types::*;
 mod public_key;
 pub use self::public_key::{CurveType, PublicKey};
 
+mod signature;
+pub use self::signature::{Ed25519Signature, ParseSignatureError, Secp256k1Signature, Signature};
+
 mod primitives;
 pub use self::primitives::*;
 
 pub use near_account_id::{AccountId, AccountIdRef};
+
+mod account_kind;
+pub use self::account_kind::AccountKind;
 /// A wrapper struct for `u64` that represents gas. And provides helpful methods to convert to and from tera-gas and giga-gas.
 pub use near_gas::NearGas as Gas;
 /// A wrapper struct for `u128` that represents tokens. And provides helpful methods to convert with a proper precision.
@@ -16,6 +22,10 @@ pub use near_token::NearToken;
 mod error;
 pub use self::error::Abort;
 pub use self::error::FunctionError;
+pub use self::error::{DeserializationError, StorageError};
+
+mod versioned_state;
+pub use self::versioned_state::VersionedState;
 
 /// Raw type for duration in nanoseconds
 pub type Duration = u64;
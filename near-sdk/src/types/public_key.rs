@@ -3,6 +3,8 @@ use bs58::decode::Error as B58Error;
 use near_sdk_macros::near;
 use std::{convert::TryFrom, io};
 
+use crate::{AccountId, Signature};
+
 /// PublicKey curve
 #[near(inside_nearsdk, serializers=[borsh(use_discriminant = true)])]
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, Eq, PartialEq)]
@@ -141,6 +143,68 @@ impl PublicKey {
     pub fn curve_type(&self) -> CurveType {
         CurveType::from_u8(self.data[0]).unwrap_or_else(|_| crate::env::abort())
     }
+
+    /// Verifies the signature of `message` by this key, using the host function of the key's curve.
+    ///
+    /// For secp256k1 keys, `message` is the 32-byte hash that was signed and the key is compared
+    /// against the one recovered from the signature, see [`Secp256k1Signature`]. Recovering the
+    /// key requires the `unstable` feature, without which secp256k1 signatures are never valid.
+    ///
+    /// Returns `false` if the signature is for another curve than the key.
+    ///
+    /// [`Secp256k1Signature`]: crate::Secp256k1Signature
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        match (self.curve_type(), signature) {
+            (CurveType::ED25519, Signature::ED25519(signature)) => {
+                let public_key = self.data[1..].try_into().unwrap_or_else(|_| crate::env::abort());
+                signature.verify(message, public_key)
+            }
+            (CurveType::SECP256K1, Signature::SECP256K1(signature)) => {
+                #[cfg(feature = "unstable")]
+                match message.try_into() {
+                    Ok(hash) => signature.verify(hash, self),
+                    Err(_) => false,
+                }
+                #[cfg(not(feature = "unstable"))]
+                {
+                    let _ = signature;
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the implicit account controlled by this key: the hex encoding of an ed25519 key,
+    /// or the [`Address`](crate::crypto::ethereum::Address) of a secp256k1 key.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::{AccountKind, PublicKey};
+    ///
+    /// let public_key: PublicKey = "ed25519:H9k5eiU4xXS3M4z8HzKJSLaZdqGdGwBG49o7orNC4eZW".parse().unwrap();
+    /// let account_id = public_key.to_implicit_account_id();
+    /// assert_eq!(account_id, "eff85923feec05711f9ee4de85d010ece899bef01bab4871604cfd1af16d2119");
+    /// assert_eq!(AccountKind::of(&account_id), AccountKind::NearImplicit);
+    /// ```
+    pub fn to_implicit_account_id(&self) -> AccountId {
+        let account_id = match self.curve_type() {
+            CurveType::ED25519 => to_hex(&self.data[1..]),
+            CurveType::SECP256K1 => {
+                let public_key = self.data[1..].try_into().unwrap_or_else(|_| crate::env::abort());
+                crate::crypto::ethereum::Address::from_public_key(public_key).to_string()
+            }
+        };
+        account_id.parse().unwrap_or_else(|_| crate::env::abort())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    bytes
+        .iter()
+        .flat_map(|b| [HEX[(b >> 4) as usize] as char, HEX[(b & 0xf) as usize] as char])
+        .collect()
 }
 
 impl From<PublicKey> for Vec<u8> {
@@ -308,6 +372,29 @@ mod tests {
         assert_eq!(actual, "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp");
     }
 
+    #[test]
+    fn test_implicit_account_id() {
+        assert_eq!(
+            expected_key().to_implicit_account_id(),
+            "4da7e0f4096aaf2ce55e371657cd3089ba1e9f59f4d6e27bd02e472a16a61dc1"
+        );
+
+        // Public key of the secret key `1`, i.e. the generator point of secp256k1.
+        let mut data = [0; 64];
+        data[..32].copy_from_slice(&[
+            0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+            0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b,
+            0x16, 0xf8, 0x17, 0x98,
+        ]);
+        data[32..].copy_from_slice(&[
+            0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65, 0x5d, 0xa4, 0xfb, 0xfc, 0x0e, 0x11,
+            0x08, 0xa8, 0xfd, 0x17, 0xb4, 0x48, 0xa6, 0x85, 0x54, 0x19, 0x9c, 0x47, 0xd0, 0x8f,
+            0xfb, 0x10, 0xd4, 0xb8,
+        ]);
+        let key = PublicKey::from_parts(CurveType::SECP256K1, data.to_vec()).unwrap();
+        assert_eq!(key.to_implicit_account_id(), "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+    }
+
     #[test]
     fn test_public_key_borsh_format_change() {
         // Original struct to reference Borsh serialization from
@@ -0,0 +1,379 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use bs58::decode::Error as B58Error;
+use std::convert::TryFrom;
+
+use crate::CurveType;
+#[cfg(feature = "unstable")]
+use crate::PublicKey;
+
+/// Ed25519 signature: the 64 bytes of `R` and `s`.
+///
+/// Serialized to JSON as a base58 string with an `ed25519:` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct Ed25519Signature(pub [u8; 64]);
+
+/// Recoverable secp256k1 signature: the 32 bytes of `r`, the 32 bytes of `s` and the recovery
+/// byte `v`.
+///
+/// Serialized to JSON as a base58 string with a `secp256k1:` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub struct Secp256k1Signature(pub [u8; 65]);
+
+/// Signature of either of the curves supported for [`PublicKey`](crate::PublicKey)s.
+///
+/// Borsh serialization matches the one of the `Signature` of `near-crypto`: the [`CurveType`]
+/// byte followed by the signature bytes. JSON serialization is a base58 string prefixed with the
+/// curve, e.g. `ed25519:...`.
+///
+/// # Example
+/// ```
+/// use near_sdk::{PublicKey, Signature};
+///
+/// let public_key: PublicKey = "ed25519:BWJZuKWeVvgtkiAk5Vwjoit55YfoFRya4GmX4ZzGj1SS".parse().unwrap();
+/// let signature: Signature = "ed25519:2KGH7ohdidWnrFaCZvgFnyie7aa7hMj77iNGwAMJda1ZShGLv2pzUxYvp7mFiBsXJ18rDYCsYCHoPuPTrgvpoksh"
+///     .parse()
+///     .unwrap();
+/// assert!(public_key.verify(b"Hello world!", &signature));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+pub enum Signature {
+    ED25519(Ed25519Signature),
+    SECP256K1(Secp256k1Signature),
+}
+
+impl Ed25519Signature {
+    /// Verifies the signature of `message` with the given ed25519 public key.
+    pub fn verify(&self, message: &[u8], public_key: &[u8; 32]) -> bool {
+        crate::env::ed25519_verify(&self.0, message, public_key)
+    }
+}
+
+impl Secp256k1Signature {
+    /// The recovery byte `v`, normalized to `0` or `1` if it is given in the Ethereum `27`/`28`
+    /// form.
+    pub fn recovery_id(&self) -> u8 {
+        match self.0[64] {
+            v @ (27 | 28) => v - 27,
+            v => v,
+        }
+    }
+
+    /// Recovers the uncompressed public key which signed the 32-byte message `hash`. Signatures
+    /// with `s` in the upper half of the curve order are rejected as malleable.
+    ///
+    /// Returns `None` if the recovery failed, or if the recovery byte isn't `0`, `1`, `27` or `28`.
+    #[cfg(feature = "unstable")]
+    pub fn recover(&self, hash: &[u8; 32]) -> Option<PublicKey> {
+        // The host aborts the call on other recovery ids instead of failing the recovery.
+        let v = self.recovery_id();
+        if v > 1 {
+            return None;
+        }
+        let key = crate::env::ecrecover(hash, &self.0[..64], v, true)?;
+        Some(PublicKey::from_parts(CurveType::SECP256K1, key.to_vec()).unwrap_or_else(|_| {
+            crate::env::panic_str("ecrecover returned a key of unexpected length")
+        }))
+    }
+
+    /// Verifies that the signer recovered from `hash` is `public_key`, e.g. a key stored in the
+    /// contract state.
+    #[cfg(feature = "unstable")]
+    pub fn verify(&self, hash: &[u8; 32], public_key: &PublicKey) -> bool {
+        self.recover(hash).as_ref() == Some(public_key)
+    }
+}
+
+impl Signature {
+    /// Curve of the signature.
+    pub fn curve_type(&self) -> CurveType {
+        match self {
+            Self::ED25519(_) => CurveType::ED25519,
+            Self::SECP256K1(_) => CurveType::SECP256K1,
+        }
+    }
+
+    /// Bytes of the signature, without the curve.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::ED25519(signature) => &signature.0,
+            Self::SECP256K1(signature) => &signature.0,
+        }
+    }
+
+    /// Creates a signature from the bytes for the given curve.
+    pub fn from_parts(curve: CurveType, data: &[u8]) -> Result<Self, ParseSignatureError> {
+        let invalid_length =
+            || ParseSignatureError { kind: ParseSignatureErrorKind::InvalidLength(data.len()) };
+        match curve {
+            CurveType::ED25519 => {
+                Ok(Self::ED25519(Ed25519Signature(data.try_into().map_err(|_| invalid_length())?)))
+            }
+            CurveType::SECP256K1 => Ok(Self::SECP256K1(Secp256k1Signature(
+                data.try_into().map_err(|_| invalid_length())?,
+            ))),
+        }
+    }
+}
+
+impl From<Ed25519Signature> for Signature {
+    fn from(signature: Ed25519Signature) -> Self {
+        Self::ED25519(signature)
+    }
+}
+
+impl From<Secp256k1Signature> for Signature {
+    fn from(signature: Secp256k1Signature) -> Self {
+        Self::SECP256K1(signature)
+    }
+}
+
+impl From<&Signature> for String {
+    fn from(signature: &Signature) -> Self {
+        let prefix = match signature.curve_type() {
+            CurveType::ED25519 => "ed25519:",
+            CurveType::SECP256K1 => "secp256k1:",
+        };
+        [prefix, &bs58::encode(signature.as_bytes()).into_string()].concat()
+    }
+}
+
+impl std::fmt::Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self))
+    }
+}
+
+impl std::str::FromStr for Signature {
+    type Err = ParseSignatureError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (curve, data) = match value.split_once(':') {
+            Some((curve, data)) => (
+                curve.parse::<CurveType>().map_err(|_| ParseSignatureError {
+                    kind: ParseSignatureErrorKind::UnknownCurve,
+                })?,
+                data,
+            ),
+            // If there is no prefix, ED25519 is the default, as for public keys.
+            None => (CurveType::ED25519, value),
+        };
+        Self::from_parts(curve, &bs58::decode(data).into_vec()?)
+    }
+}
+
+macro_rules! impl_signature_conversions {
+    ($ty:ident, $variant:ident) => {
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                Signature::from(*self).fmt(f)
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = ParseSignatureError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value.parse::<Signature>()? {
+                    Signature::$variant(signature) => Ok(signature),
+                    _ => Err(ParseSignatureError { kind: ParseSignatureErrorKind::UnknownCurve }),
+                }
+            }
+        }
+
+        impl TryFrom<Signature> for $ty {
+            type Error = ParseSignatureError;
+
+            fn try_from(signature: Signature) -> Result<Self, Self::Error> {
+                match signature {
+                    Signature::$variant(signature) => Ok(signature),
+                    _ => Err(ParseSignatureError { kind: ParseSignatureErrorKind::UnknownCurve }),
+                }
+            }
+        }
+    };
+}
+
+impl_signature_conversions!(Ed25519Signature, ED25519);
+impl_signature_conversions!(Secp256k1Signature, SECP256K1);
+
+macro_rules! impl_string_serde {
+    ($($ty:ty),*) => {
+        $(
+            impl serde::Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    let s: String = serde::Deserialize::deserialize(deserializer)?;
+                    s.parse().map_err(serde::de::Error::custom)
+                }
+            }
+
+            #[cfg(feature = "abi")]
+            impl schemars::JsonSchema for $ty {
+                fn is_referenceable() -> bool {
+                    false
+                }
+
+                fn schema_name() -> String {
+                    String::schema_name()
+                }
+
+                fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                    String::json_schema(gen)
+                }
+            }
+        )*
+    };
+}
+
+impl_string_serde!(Signature, Ed25519Signature, Secp256k1Signature);
+
+/// Error returned when a [`Signature`] or the signature of one curve can't be parsed from a string
+/// or built from its bytes.
+#[derive(Debug)]
+pub struct ParseSignatureError {
+    kind: ParseSignatureErrorKind,
+}
+
+#[derive(Debug)]
+enum ParseSignatureErrorKind {
+    /// The signature doesn't have the length of its curve.
+    InvalidLength(usize),
+    /// The signature is not valid base58.
+    Base58(B58Error),
+    /// The curve prefix is unknown, or is not the curve of the expected signature type.
+    UnknownCurve,
+}
+
+impl std::fmt::Display for ParseSignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ParseSignatureErrorKind::InvalidLength(l) => {
+                write!(f, "invalid length of the signature, got {}", l)
+            }
+            ParseSignatureErrorKind::Base58(e) => write!(f, "base58 decoding error: {}", e),
+            ParseSignatureErrorKind::UnknownCurve => write!(f, "unknown or unexpected curve kind"),
+        }
+    }
+}
+
+impl From<B58Error> for ParseSignatureError {
+    fn from(e: B58Error) -> Self {
+        Self { kind: ParseSignatureErrorKind::Base58(e) }
+    }
+}
+
+impl std::error::Error for ParseSignatureError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PublicKey;
+
+    const ED25519_SIGNATURE: [u8; 64] = [
+        145, 193, 203, 18, 114, 227, 14, 117, 33, 213, 121, 66, 130, 14, 25, 4, 36, 120, 46, 142,
+        226, 215, 7, 66, 122, 112, 97, 30, 249, 135, 61, 165, 221, 249, 252, 23, 105, 40, 56, 70,
+        31, 152, 236, 141, 154, 122, 207, 20, 75, 118, 79, 90, 168, 6, 221, 122, 213, 29, 126, 196,
+        216, 104, 191, 6,
+    ];
+
+    const ED25519_PUBLIC_KEY: [u8; 32] = [
+        32, 122, 6, 120, 146, 130, 30, 37, 215, 112, 241, 251, 160, 196, 124, 17, 255, 75, 129, 62,
+        84, 22, 46, 206, 158, 184, 57, 224, 118, 35, 26, 182,
+    ];
+
+    const MESSAGE: &[u8] = b"kajdlfkjalkfjaklfjdkladjfkljadsk";
+
+    #[test]
+    fn signature_str_round_trip() {
+        let signature = Signature::ED25519(Ed25519Signature(ED25519_SIGNATURE));
+        let s = signature.to_string();
+        assert!(s.starts_with("ed25519:"));
+        assert_eq!(s.parse::<Signature>().unwrap(), signature);
+        assert_eq!(s.parse::<Ed25519Signature>().unwrap().0, ED25519_SIGNATURE);
+        assert!(s.parse::<Secp256k1Signature>().is_err());
+
+        let secp = Signature::SECP256K1(Secp256k1Signature([3; 65]));
+        assert_eq!(secp.to_string().parse::<Signature>().unwrap(), secp);
+        assert!("secp256k1:3Mz".parse::<Signature>().is_err());
+        assert!("p256:3Mz".parse::<Signature>().is_err());
+    }
+
+    #[test]
+    fn signature_serialization() {
+        let signature = Signature::ED25519(Ed25519Signature(ED25519_SIGNATURE));
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(json, format!("\"{}\"", signature));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), signature);
+
+        let borsh = borsh::to_vec(&signature).unwrap();
+        assert_eq!(borsh[0], CurveType::ED25519 as u8);
+        assert_eq!(&borsh[1..], &ED25519_SIGNATURE);
+        assert_eq!(Signature::try_from_slice(&borsh).unwrap(), signature);
+    }
+
+    #[test]
+    fn verify_ed25519() {
+        let public_key =
+            PublicKey::from_parts(CurveType::ED25519, ED25519_PUBLIC_KEY.to_vec()).unwrap();
+        let signature = Signature::ED25519(Ed25519Signature(ED25519_SIGNATURE));
+        assert!(public_key.verify(MESSAGE, &signature));
+        assert!(!public_key.verify(b"another message", &signature));
+        assert!(!public_key.verify(MESSAGE, &Signature::SECP256K1(Secp256k1Signature([0; 65]))));
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn verify_secp256k1() {
+        let hash: [u8; 32] =
+            hex::decode("ce0677bb30baa8cf067c88db9811f4333d131bf8bcf12fe7065d211dce971008")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let mut bytes = [0; 65];
+        hex::decode_to_slice("90f27b8b488db00b00606796d2987f6a5f59ae62ea05effe84fef5b8b0e549984a691139ad57a3f0b906637673aa2f63d1f55cb1a69199d4009eea23ceaddc93", &mut bytes[..64]).unwrap();
+        bytes[64] = 1;
+        let signature = Secp256k1Signature(bytes);
+        let public_key = PublicKey::from_parts(
+            CurveType::SECP256K1,
+            hex::decode("e32df42865e97135acfb65f3bae71bdc86f4d49150ad6a440b6f15878109880a0a2b2667f7e725ceea70c673093bf67663e0312623c8e091b13cf2c0f11ef652").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(signature.recover(&hash), Some(public_key.clone()));
+        assert!(signature.verify(&hash, &public_key));
+        assert!(public_key.verify(&hash, &signature.into()));
+        assert!(!public_key.verify(&[0; 32], &signature.into()));
+        // Ethereum style recovery byte.
+        bytes[64] = 28;
+        assert!(public_key.verify(&hash, &Secp256k1Signature(bytes).into()));
+        // Not a 32-byte hash.
+        assert!(!public_key.verify(b"short", &signature.into()));
+        // Recovery id the host doesn't accept.
+        bytes[64] = 5;
+        let signature = Secp256k1Signature(bytes);
+        assert_eq!(signature.recover(&hash), None);
+        assert!(!signature.verify(&hash, &public_key));
+        assert!(!public_key.verify(&hash, &signature.into()));
+    }
+
+    #[cfg(not(feature = "unstable"))]
+    #[test]
+    fn verify_secp256k1_without_unstable() {
+        let public_key = PublicKey::from_parts(CurveType::SECP256K1, vec![1; 64]).unwrap();
+        assert!(!public_key.verify(&[0; 32], &Signature::SECP256K1(Secp256k1Signature([1; 65]))));
+    }
+}
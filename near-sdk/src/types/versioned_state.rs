@@ -0,0 +1,11 @@
+/// Contract state with a version, implemented by `#[near(version = N)]`.
+///
+/// Init methods of a contract declared with `#[near(contract_state, version = N)]` store the
+/// version alongside the state, and its other methods refuse to read a state of another version.
+/// The `migrate` method generated from the contract's
+/// [`#[migrate(...)]`](crate::near#nearcontract_state-version--n-and-migrate-annotate-structsenums-and-methods)
+/// functions uses the stored version to pick the migrations to run. Previous versions of the
+/// state are declared with `#[near(version = N)]` alone.
+pub trait VersionedState {
+    const STATE_VERSION: u32;
+}